@@ -0,0 +1,35 @@
+//! Criterion benchmarks for every day, run with `cargo bench`.
+//!
+//! The days are binaries, so each one is pulled in as a module.
+
+#![allow(dead_code)]
+
+use aoc_utils::bench::{bench_day, bench_day_both};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+#[path = "../src/bin/day01.rs"]
+mod day01;
+#[path = "../src/bin/day02.rs"]
+mod day02;
+
+// days 5 and 7 are still being written, so aren't benchmarked yet
+
+fn bench_day01(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day01",
+        day01::INPUT,
+        day01::parse_input,
+        |input| day01::part1(input),
+        |input| day01::part2(input),
+    );
+}
+
+fn bench_day02(c: &mut Criterion) {
+    bench_day_both(c, "day02", day02::INPUT, day02::parse_input, |input| {
+        day02::solve(input).unwrap()
+    });
+}
+
+criterion_group!(benches, bench_day01, bench_day02);
+criterion_main!(benches);
@@ -0,0 +1,4 @@
+fn main() -> anyhow::Result<()> {
+    let out_dir = std::env::var("OUT_DIR")?;
+    aoc_utils::answers::generate_example_tests("src/test_inputs", out_dir)
+}
@@ -1,38 +1,31 @@
-#![feature(test)]
-extern crate test;
-
 use anyhow::*;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
-const INPUT_NUM: usize = 0;
 type Input = Vec<usize>;
 type PartInput = [usize];
 
-fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
+pub(crate) const INPUT: &str = include_str!("../inputs/day01.inp");
 
-    println!("part 1: {}", part_1);
-    println!("part 2: {}", part_2);
-
-    Ok(())
+fn main() -> Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
 }
 
-fn get_input() -> Result<Input> {
-    let in_str = [include_str!("../inputs/day01.inp")][INPUT_NUM];
-
-    in_str
-        .lines()
-        .map(|num| Ok(num.parse::<usize>()?))
-        .collect()
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    in_str.lines().map(|num| src.number(num)).collect()
 }
 
-fn part1(input: &PartInput) -> usize {
+pub(crate) fn part1(input: &PartInput) -> usize {
     input.iter().map(|mass| mass / 3 + 2).sum()
 }
 
-fn part2(input: &PartInput) -> usize {
+pub(crate) fn part2(input: &PartInput) -> usize {
     input
         .iter()
         .map(|mass| {
@@ -49,20 +42,3 @@ fn part2(input: &PartInput) -> usize {
         })
         .sum()
 }
-
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| part1(&input))
-}
-
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| part2(&input))
-}
-
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
-}
@@ -1,35 +1,25 @@
-#![feature(test)]
-extern crate test;
-
 use anyhow::*;
+use aoc_utils::harness;
+use aoc_utils::parse::ParseError;
 
 use aoc::intcode::{IntCodeComputer, MemItem};
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day05.inp");
 
 fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let part_1 = part1(&input)?;
-    let part_2 = part2(&input)?;
-
-    println!("part 1: {}", part_1);
-    println!("part 2: {}", part_2);
-
-    Ok(())
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input).unwrap(),
+        |input| part2(input).unwrap(),
+    )
 }
 
-fn get_input() -> Result<IntCodeComputer> {
-    let in_str = [
-        include_str!("../inputs/day05.inp"),
-        include_str!("../test_inputs/day05.inp1"),
-        include_str!("../test_inputs/day05.inp2"),
-    ][INPUT_NUM];
-
-    Ok(in_str.parse()?)
+pub(crate) fn parse_input(in_str: &str) -> Result<IntCodeComputer, ParseError> {
+    in_str.parse()
 }
 
-fn part1(input: &IntCodeComputer) -> Result<MemItem> {
+pub(crate) fn part1(input: &IntCodeComputer) -> Result<MemItem> {
     let mut comp = input.clone();
 
     comp.input(1);
@@ -40,7 +30,7 @@ fn part1(input: &IntCodeComputer) -> Result<MemItem> {
         .ok_or_else(|| format_err!("Failed to get output - queue empty."))
 }
 
-fn part2(input: &IntCodeComputer) -> Result<MemItem> {
+pub(crate) fn part2(input: &IntCodeComputer) -> Result<MemItem> {
     let mut comp = input.clone();
 
     comp.input(5);
@@ -50,19 +40,24 @@ fn part2(input: &IntCodeComputer) -> Result<MemItem> {
         .ok_or_else(|| format_err!("Failed to get output - queue empty."))
 }
 
-#[bench]
-fn bench_part1(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| part2(&input))
-}
+    /// The examples are whole programs, checked by the memory they leave behind.
+    fn solve_part1(in_str: &str) -> String {
+        let mut computer = parse_input(in_str).unwrap();
+        computer.run().unwrap();
+
+        (0..in_str.trim().split(',').count())
+            .map(|addr| computer.read(addr).unwrap().to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn solve_part2(_in_str: &str) -> String {
+        unreachable!("the examples only run programs")
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day05.rs"));
 }
@@ -1,6 +1,3 @@
-#![feature(test)]
-extern crate test;
-
 use anyhow::*;
 
 use aoc::intcode::{IntCodeComputer, MemItem};
@@ -34,14 +31,17 @@ fn determine_settings(mut computer: IntCodeComputer, amp: MemItem) -> Result<(Me
     computer.input(amp);
     computer.input(val);
     computer.run()?;
-    let value = computer.next_output()
+    let value = computer
+        .next_output()
         .ok_or_else(|| format_err!("Failed to get output from computer."))?;
 
     Ok((value / val, value % val))
 }
 
-fn part1(input: &IntCodeComputer) -> Result<MemItem> {
-    let coefficients: Vec<_> = (0..=4).map(|amp| determine_settings(input.clone(), amp)).collect::<Result<_>>()?;
+pub(crate) fn part1(input: &IntCodeComputer) -> Result<MemItem> {
+    let coefficients: Vec<_> = (0..=4)
+        .map(|amp| determine_settings(input.clone(), amp))
+        .collect::<Result<_>>()?;
 
     let mut part1: Vec<MemItem> = vec![0; 32];
 
@@ -59,7 +59,7 @@ fn part1(input: &IntCodeComputer) -> Result<MemItem> {
 }
 
 /*
-fn part2(input: &IntCodeComputer) -> Result<MemItem> {
+pub(crate) fn part2(input: &IntCodeComputer) -> Result<MemItem> {
     let mut comp = input.clone();
 
     comp.input(5);
@@ -70,21 +70,5 @@ fn part2(input: &IntCodeComputer) -> Result<MemItem> {
 }
 */
 
-#[bench]
-fn bench_part1(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| part1(&input))
-}
-
 /*
-#[bench]
-fn bench_part2(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| part2(&input))
-}
 */
-
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
-}
@@ -1,4 +1,5 @@
-use anyhow::*;
+use anyhow::{bail, ensure, format_err, Result};
+use aoc_utils::parse::{ParseError, Source};
 
 use std::convert::TryInto;
 use std::ops;
@@ -48,11 +49,9 @@ impl IntCodeComputer {
 
     pub fn write(&mut self, addr: Address, value: MemItem) -> Result<()> {
         let len = self.memory.len();
-        let mem = self.memory.get_mut(addr).ok_or_else(|| format_err!(
-            "Cannot write to address {}, memory has size {}",
-            addr,
-            len,
-        ))?;
+        let mem = self.memory.get_mut(addr).ok_or_else(|| {
+            format_err!("Cannot write to address {}, memory has size {}", addr, len,)
+        })?;
         *mem = value;
         Ok(())
     }
@@ -89,8 +88,12 @@ impl IntCodeComputer {
         }
 
         match instr {
-            1 => { self.arith_op(ops::Add::add, arg!(1), arg!(2), arg!(3))?; }
-            2 => { self.arith_op(ops::Mul::mul, arg!(1), arg!(2), arg!(3))?; }
+            1 => {
+                self.arith_op(ops::Add::add, arg!(1), arg!(2), arg!(3))?;
+            }
+            2 => {
+                self.arith_op(ops::Mul::mul, arg!(1), arg!(2), arg!(3))?;
+            }
             99 => {
                 self.halted = true;
             }
@@ -119,13 +122,14 @@ impl IntCodeComputer {
 }
 
 impl std::str::FromStr for IntCodeComputer {
-    type Err = std::num::ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
         let instructions = s
             .trim()
             .split(',')
-            .map(|num| num.parse::<MemItem>())
+            .map(|num| src.number::<MemItem>(num))
             .collect::<Result<IntCode, Self::Err>>()?;
 
         Ok(IntCodeComputer::new(instructions))
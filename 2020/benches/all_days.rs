@@ -0,0 +1,315 @@
+//! Criterion benchmarks for every day, run with `cargo bench`.
+//!
+//! The days are binaries, so each one is pulled in as a module.
+
+#![allow(dead_code)]
+
+use aoc_utils::bench::{bench_day, bench_parse};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+#[path = "../src/bin/day01.rs"]
+mod day01;
+#[path = "../src/bin/day02.rs"]
+mod day02;
+#[path = "../src/bin/day03.rs"]
+mod day03;
+#[path = "../src/bin/day04.rs"]
+mod day04;
+#[path = "../src/bin/day05.rs"]
+mod day05;
+#[path = "../src/bin/day06.rs"]
+mod day06;
+#[path = "../src/bin/day07.rs"]
+mod day07;
+#[path = "../src/bin/day08.rs"]
+mod day08;
+#[path = "../src/bin/day09.rs"]
+mod day09;
+#[path = "../src/bin/day10.rs"]
+mod day10;
+#[path = "../src/bin/day11.rs"]
+mod day11;
+#[path = "../src/bin/day12.rs"]
+mod day12;
+#[path = "../src/bin/day13.rs"]
+mod day13;
+#[path = "../src/bin/day14.rs"]
+mod day14;
+#[path = "../src/bin/day15.rs"]
+mod day15;
+#[path = "../src/bin/day16.rs"]
+mod day16;
+#[path = "../src/bin/day17.rs"]
+mod day17;
+#[path = "../src/bin/day18.rs"]
+mod day18;
+#[path = "../src/bin/day19.rs"]
+mod day19;
+#[path = "../src/bin/day20.rs"]
+mod day20;
+#[path = "../src/bin/day21.rs"]
+mod day21;
+#[path = "../src/bin/day22.rs"]
+mod day22;
+
+fn bench_day01(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day01",
+        day01::INPUT,
+        day01::parse_input,
+        |input| day01::part1(input),
+        |input| day01::part2(input),
+    );
+}
+
+fn bench_day02(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day02",
+        day02::INPUT,
+        day02::parse_input,
+        day02::part1,
+        day02::part2,
+    );
+}
+
+fn bench_day03(c: &mut Criterion) {
+    let input = bench_parse(c, "day03", day03::INPUT, day03::parse_input);
+    let part_1 = day03::part1(&input);
+    c.bench_function("day03_part1", |b| {
+        b.iter(|| day03::part1(black_box(&input)))
+    });
+    c.bench_function("day03_part2", |b| {
+        b.iter(|| day03::part2(black_box(&input), part_1))
+    });
+}
+
+fn bench_day04(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day04",
+        day04::INPUT,
+        day04::parse_input,
+        |input| day04::part1(input),
+        |input| day04::part2(input),
+    );
+}
+
+fn bench_day05(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day05",
+        day05::INPUT,
+        day05::parse_input,
+        |input| day05::part1(input),
+        |input| day05::part2(input),
+    );
+}
+
+fn bench_day06(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day06",
+        day06::INPUT,
+        day06::parse_input,
+        |input| day06::part1(input),
+        |input| day06::part2(input),
+    );
+}
+
+fn bench_day07(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day07",
+        day07::INPUT,
+        day07::parse_input,
+        day07::part1,
+        day07::part2,
+    );
+}
+
+fn bench_day08(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day08",
+        day08::INPUT,
+        day08::parse_input,
+        |input| day08::part1(input),
+        |input| day08::part2(input),
+    );
+}
+
+fn bench_day09(c: &mut Criterion) {
+    let input = bench_parse(c, "day09", day09::INPUT, day09::parse_input);
+    let part_1 = day09::part1(&input);
+    c.bench_function("day09_part1", |b| {
+        b.iter(|| day09::part1(black_box(&input)))
+    });
+    c.bench_function("day09_part2", |b| {
+        b.iter(|| day09::part2(black_box(&input), part_1))
+    });
+}
+
+fn bench_day10(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day10",
+        day10::INPUT,
+        day10::parse_input,
+        |input| day10::part1(input),
+        |input| day10::part2(input),
+    );
+}
+
+fn bench_day11(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day11",
+        day11::INPUT,
+        day11::parse_input,
+        day11::part1,
+        day11::part2,
+    );
+}
+
+fn bench_day12(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day12",
+        day12::INPUT,
+        day12::parse_input,
+        |input| day12::part1(input),
+        |input| day12::part2(input),
+    );
+}
+
+fn bench_day13(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day13",
+        day13::INPUT,
+        day13::parse_input,
+        day13::part1,
+        day13::part2,
+    );
+}
+
+fn bench_day14(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day14",
+        day14::INPUT,
+        day14::parse_input,
+        |input| day14::part1(input),
+        |input| day14::part2(input),
+    );
+}
+
+fn bench_day15(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day15",
+        day15::INPUT,
+        day15::parse_input,
+        |input| day15::part1(input),
+        |input| day15::part2(input),
+    );
+}
+
+fn bench_day16(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day16",
+        day16::INPUT,
+        day16::parse_input,
+        day16::part1,
+        day16::part2,
+    );
+}
+
+fn bench_day17(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day17",
+        day17::INPUT,
+        day17::parse_input,
+        day17::part1,
+        day17::part2,
+    );
+}
+
+fn bench_day18(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day18",
+        day18::INPUT,
+        day18::parse_input,
+        |input| day18::part1(input),
+        |input| day18::part2(input),
+    );
+}
+
+fn bench_day19(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day19",
+        day19::INPUT,
+        day19::parse_input,
+        day19::part1,
+        day19::part2,
+    );
+}
+
+fn bench_day20(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day20",
+        day20::INPUT,
+        day20::parse_input,
+        |tiles| day20::part1(tiles),
+        |tiles| day20::part2(tiles),
+    );
+}
+
+fn bench_day21(c: &mut Criterion) {
+    let input = bench_parse(c, "day21", day21::INPUT, day21::parse_input);
+    c.bench_function("day21_solve", |b| {
+        b.iter(|| day21::solve(black_box(&input)))
+    });
+}
+
+fn bench_day22(c: &mut Criterion) {
+    // part 2 isn't finished yet
+    let (player_1, player_2) = bench_parse(c, "day22", day22::INPUT, day22::parse_input);
+    c.bench_function("day22_part1", |b| {
+        b.iter(|| day22::part1(black_box(&player_1), black_box(&player_2)))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_day01,
+    bench_day02,
+    bench_day03,
+    bench_day04,
+    bench_day05,
+    bench_day06,
+    bench_day07,
+    bench_day08,
+    bench_day09,
+    bench_day10,
+    bench_day11,
+    bench_day12,
+    bench_day13,
+    bench_day14,
+    bench_day15,
+    bench_day16,
+    bench_day17,
+    bench_day18,
+    bench_day19,
+    bench_day20,
+    bench_day21,
+    bench_day22,
+);
+criterion_main!(benches);
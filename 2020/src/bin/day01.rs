@@ -1,88 +1,68 @@
-#![feature(test)]
-extern crate test;
-
-use std::cmp::Ordering;
-
-fn main() {
-    let input = get_input();
-
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+use aoc_utils::harness::{self, Maybe};
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, k_sum};
+
+pub(crate) const INPUT: &str = include_str!("../inputs/day01.inp");
+
+/// What the entries have to add up to.
+const TARGET: i32 = 2020;
+
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| Maybe(part1(input)),
+        |input| Maybe(part2(input)),
+    )
 }
 
-fn get_input() -> Vec<i32> {
-    let mut input: Vec<i32> = include_str!("../inputs/day01.inp")
+pub(crate) fn parse_input(in_str: &str) -> Result<Vec<i32>, ParseError> {
+    let src = Source::new(in_str);
+    in_str
         .lines()
         .take_while(|line| !line.is_empty())
-        .map(|line| line.parse::<i32>().unwrap())
-        .collect();
-
-    input.sort_unstable();
-
-    input
+        .map(|line| src.number(line))
+        .collect()
 }
 
-fn part1(input: &[i32]) -> i32 {
-    let mut start = 0;
-    let mut end = input.len() - 1;
-
-    loop {
-        let sum = input[start] + input[end];
-
-        match sum.cmp(&2020) {
-            Ordering::Less => start += 1,
-            Ordering::Greater => end -= 1,
-            Ordering::Equal => break,
-        };
-    }
+/// The product of the first `k` entries found which add up to the target.
+fn entries_product(input: &[i32], k: usize) -> Option<i64> {
+    let combinations = k_sum::combinations(input, k, TARGET)?;
+    Some(checked::product(
+        combinations[0].iter().map(|&i| i64::from(input[i])),
+    ))
+}
 
-    input[start] * input[end]
+pub(crate) fn part1(input: &[i32]) -> Option<i64> {
+    entries_product(input, 2)
 }
 
-fn part2(input: &[i32]) -> i32 {
-    let mut start = 0;
-    let mut mid = 0;
-    let mut end = input.len() - 1;
+pub(crate) fn part2(input: &[i32]) -> Option<i64> {
+    entries_product(input, 3)
+}
 
-    loop {
-        let sum = input[start] + input[mid] + input[end];
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-        match sum.cmp(&2020) {
-            Ordering::Greater => {
-                mid = start;
-                end -= 1;
-            }
-            Ordering::Less => {
-                if mid == end {
-                    start += 1;
-                    mid = start;
-                } else {
-                    mid += 1;
-                }
-            }
-            Ordering::Equal => break,
-        }
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).unwrap().to_string()
     }
 
-    input[start] * input[mid] * input[end]
-}
-
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
-
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).unwrap().to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day01.rs"));
+
+    #[test]
+    fn no_solution() {
+        let input = parse_input("1000\n1\n-5\n").unwrap();
+        assert_eq!(part1(&input), None);
+        assert_eq!(part2(&input), None);
+        assert_eq!(
+            part1(&parse_input("1010\n1010\n").unwrap()),
+            Some(1010 * 1010)
+        );
+    }
 }
@@ -1,104 +1,415 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::trace;
 
-use std::num::ParseIntError;
+use std::fmt::{self, Debug, Display};
 use std::str::FromStr;
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day02.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(INPUT, parse_input, part1, part2)
 }
 
-fn get_input() -> Vec<(Policy, &'static str)> {
-    include_str!("../inputs/day02.inp")
+pub(crate) fn parse_input(in_str: &'static str) -> Result<Vec<(Policy, &'static str)>, ParseError> {
+    let src = Source::new(in_str);
+    in_str
         .lines()
         .take_while(|line| !line.is_empty())
         .map(|line| {
-            let mid = line.find(": ").unwrap();
-            let (pol, password) = line.split_at(mid);
-            let pass_start = 2;
-
-            let pol_fromstr = pol.parse::<Policy>().unwrap();
-            let (_, pass) = password.split_at(pass_start);
-
-            (pol_fromstr, pass)
+            let (pol, pass) = src.split_once(line, ": ")?;
+            Ok((src.parse::<Policy>(pol)?, pass))
         })
         .collect()
 }
 
+/// Something a password has to do to be allowed.
+pub(crate) trait Rule: Debug {
+    /// What the rule is called in diagnostics.
+    fn name(&self) -> &'static str;
+
+    fn allows(&self, password: &str) -> bool;
+
+    /// Write why the rule doesn't allow `password`, when it doesn't.
+    fn why(&self, password: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// The `a-b c` every policy starts with, which each part reads as a different rule.
+struct Letter<'a> {
+    character: char,
+    numbers: [usize; 2],
+    /// Where `a` and `b` are, for errors.
+    fragments: [&'a str; 2],
+}
+
+/// The sled rental place's reading of `min-max c`: `c` appears `min` to `max` times.
 #[derive(Debug)]
-struct Policy {
+struct CountRange {
     character: char,
     min: usize,
     max: usize,
 }
 
+impl CountRange {
+    fn read(src: &Source, letter: &Letter) -> Result<Self, ParseError> {
+        let [min, max] = letter.numbers;
+        if max < min {
+            return Err(src.error(letter.fragments[1], format!("at least {min}")));
+        }
+        Ok(CountRange {
+            character: letter.character,
+            min,
+            max,
+        })
+    }
+
+    fn matching(&self, password: &str) -> usize {
+        password.chars().filter(|&c| c == self.character).count()
+    }
+}
+
+impl Rule for CountRange {
+    fn name(&self) -> &'static str {
+        "count"
+    }
+
+    fn allows(&self, password: &str) -> bool {
+        (self.min..=self.max).contains(&self.matching(password))
+    }
+
+    fn why(&self, password: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} appears {} times, not {} to {}",
+            self.character,
+            self.matching(password),
+            self.min,
+            self.max
+        )
+    }
+}
+
+/// The toboggan company's reading of `a-b c`: `c` is at exactly one of positions `a` and `b`,
+/// counting from 1.
+#[derive(Debug)]
+struct Positions {
+    character: char,
+    positions: [usize; 2],
+}
+
+impl Positions {
+    fn read(src: &Source, letter: &Letter) -> Result<Self, ParseError> {
+        if let Some(i) = letter.numbers.iter().position(|&pos| pos == 0) {
+            return Err(src.error(letter.fragments[i], "a position counting from 1"));
+        }
+        Ok(Positions {
+            character: letter.character,
+            positions: letter.numbers,
+        })
+    }
+
+    /// The positions which hold the character.
+    fn holding<'a>(&'a self, password: &'a str) -> impl Iterator<Item = usize> + 'a {
+        self.positions
+            .into_iter()
+            .filter(|&pos| password.chars().nth(pos - 1) == Some(self.character))
+    }
+}
+
+impl Rule for Positions {
+    fn name(&self) -> &'static str {
+        "positions"
+    }
+
+    fn allows(&self, password: &str) -> bool {
+        self.holding(password).count() == 1
+    }
+
+    fn why(&self, password: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b] = self.positions;
+        let at = match self.holding(password).count() {
+            0 => "neither",
+            _ => "both",
+        };
+        write!(
+            f,
+            "{:?} is at {at} of positions {a} and {b}, not exactly one",
+            self.character
+        )
+    }
+}
+
+/// `not text`: the password doesn't contain `text`.
+#[derive(Debug)]
+struct Forbidden {
+    text: String,
+}
+
+impl Rule for Forbidden {
+    fn name(&self) -> &'static str {
+        "forbidden"
+    }
+
+    fn allows(&self, password: &str) -> bool {
+        !password.contains(&self.text)
+    }
+
+    fn why(&self, _password: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "contains {:?}", self.text)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Lower,
+    Upper,
+    Digit,
+    Symbol,
+}
+
+impl Class {
+    const ALL: [Class; 4] = [Class::Lower, Class::Upper, Class::Digit, Class::Symbol];
+
+    fn name(self) -> &'static str {
+        match self {
+            Class::Lower => "lower",
+            Class::Upper => "upper",
+            Class::Digit => "digit",
+            Class::Symbol => "symbol",
+        }
+    }
+
+    fn contains(self, c: char) -> bool {
+        match self {
+            Class::Lower => c.is_lowercase(),
+            Class::Upper => c.is_uppercase(),
+            Class::Digit => c.is_ascii_digit(),
+            Class::Symbol => !c.is_alphanumeric(),
+        }
+    }
+}
+
+/// `has class`: the password has at least one character of the class.
+#[derive(Debug)]
+struct Has {
+    class: Class,
+}
+
+impl Rule for Has {
+    fn name(&self) -> &'static str {
+        "class"
+    }
+
+    fn allows(&self, password: &str) -> bool {
+        password.chars().any(|c| self.class.contains(c))
+    }
+
+    fn why(&self, _password: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "has no {} character", self.class.name())
+    }
+}
+
+/// A password policy, `a-b c` followed by any more rules, each after a comma: `not text` forbids
+/// some text and `has class` requires a lower, upper, digit or symbol character.
+///
+/// `a-b c` is read both ways up front, so that checking a password builds nothing.
+#[derive(Debug)]
+pub(crate) struct Policy {
+    count_range: CountRange,
+    positions: Positions,
+    more: Vec<Box<dyn Rule>>,
+}
+
 impl FromStr for Policy {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let space_idx = s.find(' ').unwrap();
-        let (range, character) = s.split_at(space_idx);
+        let src = Source::new(s);
+        let mut clauses = s.split(", ");
+        let first = clauses.next().unwrap_or_default();
+        let (range, character) = src.split_once(first, " ")?;
+        let (a, b) = src.split_once(range, "-")?;
 
-        let dash_idx = range.find('-').unwrap();
-        let (min, _) = range.split_at(dash_idx);
-        let (_, max) = range.split_at(dash_idx + 1);
+        let mut chars = character.chars();
+        let (Some(character), None) = (chars.next(), chars.next()) else {
+            return Err(src.error(character, "a single character"));
+        };
+        let letter = Letter {
+            character,
+            numbers: [src.number(a)?, src.number(b)?],
+            fragments: [a, b],
+        };
 
-        let min_fromstr = min.parse::<usize>()?;
-        let max_fromstr = max.parse::<usize>()?;
+        let more = clauses
+            .map(|clause| -> Result<Box<dyn Rule>, ParseError> {
+                match clause.split_once(' ') {
+                    Some(("not", text)) if !text.is_empty() => Ok(Box::new(Forbidden {
+                        text: text.to_string(),
+                    })),
+                    Some(("has", class)) => Class::ALL
+                        .into_iter()
+                        .find(|c| c.name() == class)
+                        .map(|class| Box::new(Has { class }) as Box<dyn Rule>)
+                        .ok_or_else(|| src.error(class, "lower, upper, digit or symbol")),
+                    _ => Err(src.error(clause, "\"not <text>\" or \"has <class>\"")),
+                }
+            })
+            .collect::<Result<_, _>>()?;
 
         Ok(Policy {
-            character: character.chars().nth(1).unwrap(),
-            min: min_fromstr,
-            max: max_fromstr,
+            count_range: CountRange::read(&src, &letter)?,
+            positions: Positions::read(&src, &letter)?,
+            more,
         })
     }
 }
 
+/// A way of reading a policy's `a-b c` as a rule.
+pub(crate) type Reading = fn(&Policy) -> &dyn Rule;
+
 impl Policy {
-    fn allows(&self, password: &str) -> bool {
-        let num_matching = password.chars().filter(|&c| c == self.character).count();
+    fn count_range(&self) -> &dyn Rule {
+        &self.count_range
+    }
 
-        (self.min <= num_matching) && (self.max >= num_matching)
+    fn positions(&self) -> &dyn Rule {
+        &self.positions
     }
 
-    fn allows_new(&self, password: &str) -> bool {
-        let char1 = password.chars().nth(self.min - 1).unwrap();
-        let char2 = password.chars().nth(self.max - 1).unwrap();
+    /// The first rule `password` breaks, if any, with the policy's `a-b c` read as `reading` says.
+    fn broken_by(&self, reading: Reading, password: &str) -> Option<&dyn Rule> {
+        std::iter::once(reading(self))
+            .chain(self.more.iter().map(|rule| rule.as_ref()))
+            .find(|rule| !rule.allows(password))
+    }
+}
+
+/// Whether one line's password is allowed, and if not which rule it broke. Why it broke it is only
+/// worked out when the verdict is displayed.
+pub(crate) struct Verdict<'a> {
+    /// Counting from 1.
+    line: usize,
+    password: &'a str,
+    broken: Option<&'a dyn Rule>,
+}
 
-        (char1 == self.character) ^ (char2 == self.character)
+impl Verdict<'_> {
+    fn allowed(&self) -> bool {
+        self.broken.is_none()
     }
 }
 
-fn part1(input: &Vec<(Policy, &str)>) -> usize {
-    input.iter().filter(|(pol, pass)| pol.allows(pass)).count()
+impl Display for Verdict<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.password)?;
+        match self.broken {
+            Some(rule) => {
+                write!(f, " breaks {}: ", rule.name())?;
+                rule.why(self.password, f)
+            }
+            None => write!(f, " is allowed"),
+        }
+    }
 }
 
-fn part2(input: &Vec<(Policy, &str)>) -> usize {
+/// A verdict on every line's password.
+pub(crate) fn verdicts<'a>(
+    input: &'a [(Policy, &'a str)],
+    reading: Reading,
+) -> impl Iterator<Item = Verdict<'a>> {
     input
         .iter()
-        .filter(|(pol, pass)| pol.allows_new(pass))
+        .enumerate()
+        .map(move |(i, (policy, password))| Verdict {
+            line: i + 1,
+            password,
+            broken: policy.broken_by(reading, password),
+        })
+}
+
+fn count_allowed(input: &[(Policy, &str)], reading: Reading) -> usize {
+    let explaining = harness::explaining();
+    verdicts(input, reading)
+        .filter(|verdict| {
+            if !verdict.allowed() {
+                trace!("rejected", verdict);
+                if explaining {
+                    println!("{verdict}");
+                }
+            }
+            verdict.allowed()
+        })
         .count()
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
+pub(crate) fn part1(input: &Vec<(Policy, &str)>) -> usize {
+    count_allowed(input, Policy::count_range)
 }
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part2(&input))
+pub(crate) fn part2(input: &Vec<(Policy, &str)>) -> usize {
+    count_allowed(input, Policy::positions)
 }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test_inputs/day02.inp1");
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day02.rs"));
+
+    fn diagnose(input: &'static str, reading: Reading) -> Vec<String> {
+        let input = parse_input(input).unwrap();
+        verdicts(&input, reading)
+            .map(|verdict| verdict.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn diagnostics() {
+        assert_eq!(
+            diagnose(EXAMPLE, Policy::count_range),
+            [
+                "line 1: abcde is allowed",
+                "line 2: cdefg breaks count: 'b' appears 0 times, not 1 to 3",
+                "line 3: ccccccccc is allowed",
+            ]
+        );
+        assert_eq!(
+            diagnose(EXAMPLE, Policy::positions),
+            [
+                "line 1: abcde is allowed",
+                "line 2: cdefg breaks positions: 'b' is at neither of positions 1 and 3, not exactly one",
+                "line 3: ccccccccc breaks positions: 'c' is at both of positions 2 and 9, not exactly one",
+            ]
+        );
+    }
+
+    #[test]
+    fn more_rules() {
+        let input = "1-2 a, not ab, has digit: ba1\n\
+                     1-2 a, not ab, has digit: ab1\n\
+                     1-2 a, has upper, has symbol: aB\n";
+        assert_eq!(
+            diagnose(input, Policy::count_range),
+            [
+                "line 1: ba1 is allowed",
+                "line 2: ab1 breaks forbidden: contains \"ab\"",
+                "line 3: aB breaks class: has no symbol character",
+            ]
+        );
+
+        assert!(parse_input("1-2 a, has vowel: a\n").is_err());
+        assert!(parse_input("0-2 a: a\n").is_err());
+        assert!(parse_input("2-1 a: a\n").is_err());
+        assert!(parse_input("1-2 a, maybe b: a\n").is_err());
+    }
 }
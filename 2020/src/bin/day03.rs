@@ -1,23 +1,37 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 type Input = [Vec<char>];
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day03.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input, part_1);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run_both(INPUT, parse_input, |input| {
+        let part_1 = part1(&input);
+        (part_1, part2(&input, part_1))
+    })
 }
 
-fn get_input() -> Vec<Vec<char>> {
-    include_str!("../inputs/day03.inp")
-        .lines()
-        .take_while(|line| !line.is_empty())
-        .map(|line| line.chars().collect())
+pub(crate) fn parse_input(in_str: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let src = Source::new(in_str);
+    let lines: Vec<&str> = in_str.lines().take_while(|line| !line.is_empty()).collect();
+
+    let width = lines.first().map_or(0, |line| line.len());
+    if width == 0 {
+        return Err(src.error(in_str, "a map of trees"));
+    }
+
+    lines
+        .into_iter()
+        .map(|line| {
+            if let Some(i) = line.find(|c| c != '.' && c != '#') {
+                return Err(src.error_at(line, i, "'.' or '#'"));
+            }
+            if line.len() != width {
+                return Err(src.error(line, format!("a row {width} wide")));
+            }
+            Ok(line.chars().collect())
+        })
         .collect()
 }
 
@@ -33,7 +47,7 @@ impl Slope {
     }
 
     fn take_line(&self, depth: usize) -> bool {
-        depth % self.down == 0
+        depth.is_multiple_of(self.down)
     }
 
     fn hits(&self, trees: &Input) -> usize {
@@ -54,14 +68,14 @@ impl Slope {
     }
 }
 
-fn part1(input: &Input) -> usize {
+pub(crate) fn part1(input: &Input) -> usize {
     let slope = Slope { right: 3, down: 1 };
 
     slope.hits(input)
 }
 
-fn part2(input: &Input, part_1_ans: usize) -> usize {
-    let slopes = vec![
+pub(crate) fn part2(input: &Input, part_1_ans: usize) -> usize {
+    let slopes = [
         Slope { right: 1, down: 1 },
         Slope { right: 5, down: 1 },
         Slope { right: 7, down: 1 },
@@ -75,19 +89,18 @@ fn part2(input: &Input, part_1_ans: usize) -> usize {
         * part_1_ans
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part2(&input, 184))
-}
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        let input = parse_input(in_str).unwrap();
+        part2(&input, part1(&input)).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day03.rs"));
 }
@@ -1,32 +1,33 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
-use std::num::ParseIntError;
 use std::str::FromStr;
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day04.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
 }
 
-fn get_input() -> Vec<Passport> {
-    include_str!("../inputs/day04.inp")
+pub(crate) fn parse_input(in_str: &str) -> Result<Vec<Passport>, ParseError> {
+    let src = Source::new(in_str);
+    in_str
         .split("\n\n")
         .take_while(|line| !line.is_empty())
-        .map(|line| line.parse::<Passport>().unwrap())
+        .map(|line| src.parse::<Passport>(line))
         .collect()
 }
 
-fn part1(input: &[Passport]) -> usize {
+pub(crate) fn part1(input: &[Passport]) -> usize {
     input.iter().filter(|passport| passport.is_valid()).count()
 }
 
-fn part2(input: &[Passport]) -> usize {
+pub(crate) fn part2(input: &[Passport]) -> usize {
     input
         .iter()
         .filter(|passport| passport.is_valid_strict())
@@ -34,7 +35,7 @@ fn part2(input: &[Passport]) -> usize {
 }
 
 #[derive(Default, Debug)]
-struct Passport {
+pub(crate) struct Passport {
     birth_year: u32,
     issue_year: u32,
     expiration_year: u32,
@@ -46,18 +47,14 @@ struct Passport {
 }
 
 impl FromStr for Passport {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let pairs = s.split_whitespace().map(|pair| {
-            let (key, _) = pair.split_at(3);
-            let (_, val) = pair.split_at(4);
-            (key, val)
-        });
-
+        let src = Source::new(s);
         let mut passport: Passport = Default::default();
 
-        for (key, val) in pairs {
+        for pair in s.split_whitespace() {
+            let (key, val) = src.split_once(pair, ":")?;
             match key {
                 "byr" => passport.birth_year = val.parse::<u32>().unwrap_or(0),
                 "iyr" => passport.issue_year = val.parse::<u32>().unwrap_or(0),
@@ -68,7 +65,7 @@ impl FromStr for Passport {
                 "pid" => passport.passport_id = val.to_string(),
                 "cid" => passport.country_id = val.parse::<u32>().unwrap_or(0),
 
-                &_ => panic!("Got unexpected key: {:?} while parsing.", key),
+                &_ => return Err(src.error(key, "a passport field")),
             }
         }
 
@@ -115,16 +112,14 @@ impl Passport {
 
         if let Some(in_pos) = height_str.find("in") {
             let (num, _) = height_str.split_at(in_pos);
-            let height: i32 = num.parse().unwrap();
-
             // check the height is a valid height in inches
-            (59..=76).contains(&height)
+            num.parse()
+                .is_ok_and(|height: i32| (59..=76).contains(&height))
         } else if let Some(cm_pos) = height_str.find("cm") {
             let (num, _) = height_str.split_at(cm_pos);
-            let height: i32 = num.parse().unwrap();
-
             // check the height is a valid height in cm
-            (150..=193).contains(&height)
+            num.parse()
+                .is_ok_and(|height: i32| (150..=193).contains(&height))
         } else {
             false
         }
@@ -163,23 +158,21 @@ impl Passport {
             return false;
         }
 
-        u32::from_str_radix(self.passport_id.as_str(), 10).is_ok()
+        self.passport_id.parse::<u32>().is_ok()
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part2(&input))
-}
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day04.rs"));
 }
@@ -1,39 +1,40 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 type Input = Vec<BSPTicket>;
 type PartInput = [BSPTicket];
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day05.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
 }
 
-fn get_input() -> Input {
-    let mut input: Input = include_str!("../inputs/day05.inp")
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    let mut input: Input = in_str
         .lines()
         .take_while(|line| !line.is_empty())
-        .map(|line| line.parse::<BSPTicket>().unwrap())
-        .collect();
+        .map(|line| src.parse::<BSPTicket>(line))
+        .collect::<Result<_, _>>()?;
 
     input.sort();
 
-    input
+    Ok(input)
 }
 
-fn part1(input: &PartInput) -> usize {
+pub(crate) fn part1(input: &PartInput) -> usize {
     input.last().unwrap().seat_id
 }
 
-fn part2(input: &PartInput) -> usize {
+pub(crate) fn part2(input: &PartInput) -> usize {
     for item in input.windows(2) {
         if let [ticket1, ticket2] = item {
             if ticket1.seat_id + 2 == ticket2.seat_id {
@@ -46,22 +47,27 @@ fn part2(input: &PartInput) -> usize {
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct BSPTicket {
+pub(crate) struct BSPTicket {
     seat_id: usize,
 }
 
 impl FromStr for BSPTicket {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
+        if s.chars().count() != 10 {
+            return Err(src.error(s, "a seat of 10 letters"));
+        }
+
         // parse first 7 as binary number
         let mut seat_id: usize = 0;
 
-        for c in s.chars() {
+        for (i, c) in s.char_indices() {
             let bit = match c {
                 'F' | 'L' => 0,
                 'B' | 'R' => 1,
-                _ => panic!("Unrecognised element in bit pattern match: {:?}", c),
+                _ => return Err(src.error_at(s, i, "F, B, L or R")),
             };
 
             seat_id <<= 1;
@@ -72,19 +78,17 @@ impl FromStr for BSPTicket {
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part2(&input))
-}
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day05.rs"));
 }
@@ -1,32 +1,32 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
-use std::num::ParseIntError;
 use std::ops::{BitAnd, BitOr};
 use std::str::FromStr;
 
 type Input = Vec<Vec<DeclForm>>;
 type PartInput = [Vec<DeclForm>];
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day06.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
 }
 
-fn get_input() -> Input {
-    //include_str!("../test_inputs/day06.inp1")
-    include_str!("../inputs/day06.inp")
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    in_str
         .split("\n\n")
-        .map(|group| group.lines().map(|form| form.parse().unwrap()).collect())
+        .map(|group| group.lines().map(|form| src.parse(form)).collect())
         .collect()
 }
 
-fn part1(input: &PartInput) -> u32 {
+pub(crate) fn part1(input: &PartInput) -> u32 {
     input
         .iter()
         .map(|group| {
@@ -38,7 +38,7 @@ fn part1(input: &PartInput) -> u32 {
         .sum()
 }
 
-fn part2(input: &PartInput) -> u32 {
+pub(crate) fn part2(input: &PartInput) -> u32 {
     input
         .iter()
         .map(|group| {
@@ -51,7 +51,7 @@ fn part2(input: &PartInput) -> u32 {
 }
 
 #[derive(Debug, Copy, Clone)]
-struct DeclForm {
+pub(crate) struct DeclForm {
     bitset: usize,
 }
 
@@ -82,11 +82,15 @@ impl BitAnd for DeclForm {
 }
 
 impl FromStr for DeclForm {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
         let mut bitset: usize = 0;
-        for c in s.chars().filter(|c| c.is_lowercase()) {
+        for (i, c) in s.char_indices() {
+            if !c.is_ascii_lowercase() {
+                return Err(src.error_at(s, i, "a question from a to z"));
+            }
             let pos = c as usize - 'a' as usize;
 
             let bit = 1 << pos;
@@ -98,19 +102,17 @@ impl FromStr for DeclForm {
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part2(&input))
-}
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day06.rs"));
 }
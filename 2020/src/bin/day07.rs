@@ -1,136 +1,98 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::graph::{Graph, NodeId};
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
-use std::collections::{HashMap, HashSet};
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 type Colour = String;
-type Input = HashMap<Colour, Backpack>;
+/// Each colour of backpack, with an edge to each it has to contain, weighted by how many.
+type Input = Graph<Colour, usize>;
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day07.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(INPUT, parse_input, part1, part2)
 }
 
-fn get_input() -> Input {
-    let mut backpacks: Input = include_str!("../inputs/day07.inp")
-        .lines()
-        .map(|line| {
-            let sep = " bags contain ";
-            let index = line.find(sep).unwrap();
-
-            let colour = line.get(..index).unwrap();
-            let backpack_str = line.get(index + sep.len()..).unwrap();
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    let mut backpacks = Input::directed();
 
-            let backpack = backpack_str.parse().unwrap();
-
-            (colour.to_string(), backpack)
-        })
-        .collect();
-
-    // link the backpacks to those which can reach it
-    let backwards_connections: Vec<(Colour, Colour)> = backpacks
-        .iter()
-        .flat_map(|(backpack_name, backpack)| {
-            backpack
-                .can_contain
-                .iter()
-                .map(move |(contained, _)| (backpack_name.clone(), contained.clone()))
-        })
-        .collect();
+    for line in in_str.lines() {
+        let (colour, backpack_str) = src.split_once(line, " bags contain ")?;
+        let backpack: Backpack = src.parse(backpack_str)?;
 
-    for (container, containee) in backwards_connections.iter() {
-        backpacks
-            .entry(containee.to_string())
-            .and_modify(|b| b.contained_by.push(container.to_string()));
+        let container = backpacks.intern(colour.to_string());
+        for (contained, number) in backpack.can_contain {
+            let contained = backpacks.intern(contained);
+            backpacks.connect(container, contained, number);
+        }
     }
 
-    backpacks
+    Ok(backpacks)
 }
 
-fn part1(input: &Input) -> usize {
-    let mut could_contain: HashSet<&str> = HashSet::new();
-
-    let mut stack: Vec<&str> = vec!["shiny gold"];
-    while !stack.is_empty() {
-        let backpack = stack.pop().unwrap();
-        for container in input[backpack].contained_by.iter() {
-            stack.push(container.as_str());
-            if !could_contain.contains(container.as_str()) {
-                could_contain.insert(container.as_str());
-            }
-        }
-    }
+pub(crate) fn part1(input: &Input) -> usize {
+    let shiny_gold = input.id("shiny gold").unwrap();
 
-    could_contain.len()
+    // every backpack which can reach it going backwards, apart from itself
+    input.reversed().reachable_from(shiny_gold).len() - 1
 }
 
-fn part2(input: &Input) -> usize {
-    must_contain(input, "shiny gold")
+pub(crate) fn part2(input: &Input) -> usize {
+    must_contain(input, input.id("shiny gold").unwrap())
 }
 
-fn must_contain(input: &Input, backpack: &str) -> usize {
-    let contains = &input[backpack].can_contain;
-
-    contains.iter().fold(0, |acc, (colour, num)| {
+fn must_contain(input: &Input, backpack: NodeId) -> usize {
+    input.edges(backpack).iter().fold(0, |acc, &(colour, num)| {
         acc + num * (1 + must_contain(input, colour))
     })
 }
 
 #[derive(Debug)]
-struct Backpack {
-    contained_by: Vec<Colour>,
+pub(crate) struct Backpack {
     can_contain: Vec<(Colour, usize)>,
 }
 
 impl FromStr for Backpack {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
         let can_contain_fromstr = if s.starts_with("no") {
             Vec::new()
         } else {
             s.split(", ")
                 .map(|bp_str| {
-                    let first_space = bp_str.find(' ').unwrap();
-                    let last_space = bp_str.rfind(' ').unwrap();
-
-                    let number = bp_str.get(..first_space).unwrap();
-                    let bag = bp_str.get(first_space + 1..last_space).unwrap();
+                    let (number, bag) = src.split_once(bp_str, " ")?;
+                    let (bag, _) = bag
+                        .rsplit_once(' ')
+                        .ok_or_else(|| src.error(bag, "a colour then \"bags\""))?;
 
-                    let number_fromstr = number.parse().unwrap();
+                    let number_fromstr = src.number(number)?;
 
-                    (bag.to_string(), number_fromstr)
+                    Ok((bag.to_string(), number_fromstr))
                 })
-                .collect()
+                .collect::<Result<_, _>>()?
         };
 
         Ok(Backpack {
-            contained_by: Vec::new(),
             can_contain: can_contain_fromstr,
         })
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part2(&input))
-}
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day07.rs"));
 }
@@ -1,32 +1,29 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use std::collections::BTreeSet;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 type Input = Vec<Instruction>;
 type PartInput = [Instruction];
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day08.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
 }
 
-fn get_input() -> Input {
-    include_str!("../inputs/day08.inp")
-        //include_str!("../test_inputs/day08.inp1")
-        .lines()
-        .map(|line| line.parse().unwrap())
-        .collect()
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    in_str.lines().map(|line| src.parse(line)).collect()
 }
 
-fn part1(input: &PartInput) -> i64 {
+pub(crate) fn part1(input: &PartInput) -> i64 {
     let mut program = Program {
         instrs: input,
         ip: 0,
@@ -39,7 +36,7 @@ fn part1(input: &PartInput) -> i64 {
     }
 }
 
-fn part2(input: &PartInput) -> i64 {
+pub(crate) fn part2(input: &PartInput) -> i64 {
     let mut instrs = input.to_vec();
 
     for i in 0..input.len() {
@@ -108,7 +105,7 @@ impl Program<'_> {
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Instruction {
+pub(crate) enum Instruction {
     Nop(i64),
     Jmp(i64),
     Acc(i64),
@@ -124,21 +121,19 @@ impl Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let space = s.find(' ').unwrap();
-
-        let instr = s.get(..space).unwrap();
-        let num = s.get(space + 1..).unwrap();
+        let src = Source::new(s);
+        let (instr, num) = src.split_once(s, " ")?;
 
-        let num_fromstr = num.parse().unwrap();
+        let num_fromstr = src.number(num)?;
 
         Ok(match instr {
             "nop" => Instruction::Nop(num_fromstr),
             "acc" => Instruction::Acc(num_fromstr),
             "jmp" => Instruction::Jmp(num_fromstr),
-            _ => panic!("Unrecognised instruction: {:?}", s),
+            _ => return Err(src.error(instr, "nop, acc or jmp")),
         })
     }
 }
@@ -148,19 +143,17 @@ enum ReturnState {
     Return(i64),
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part2(&input))
-}
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day08.rs"));
 }
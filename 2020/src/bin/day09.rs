@@ -1,40 +1,31 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 
 type Input = Vec<usize>;
 type PartInput = [usize];
-const INPUT_NUM: i32 = 0;
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day09.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input, part_1);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run_both(INPUT, parse_input, |input| {
+        let part_1 = part1(&input);
+        (part_1, part2(&input, part_1))
+    })
 }
 
-fn get_input() -> Input {
-    let in_str = match INPUT_NUM {
-        0 => include_str!("../inputs/day09.inp"),
-        1 => include_str!("../test_inputs/day09.inp1"),
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
-    };
-
-    in_str.lines().map(|line| line.parse().unwrap()).collect()
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    in_str.lines().map(|line| src.number(line)).collect()
 }
 
-fn part1(input: &PartInput) -> usize {
-    let preamble_length: usize = match INPUT_NUM {
-        0 => 25,
-        1 => 5,
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
-    };
+pub(crate) fn part1(input: &PartInput) -> usize {
+    first_invalid(input, 25)
+}
 
+fn first_invalid(input: &PartInput, preamble_length: usize) -> usize {
     let mut prev_window: BTreeSet<usize> = BTreeSet::new();
 
     for &i in input.iter().take(preamble_length) {
@@ -75,7 +66,7 @@ fn part1(input: &PartInput) -> usize {
     unreachable!();
 }
 
-fn part2(input: &PartInput, part_1_ans: usize) -> usize {
+pub(crate) fn part2(input: &PartInput, part_1_ans: usize) -> usize {
     let mut start: usize = 0;
     let mut end: usize = 1;
     let mut sum = input[start] + input[end];
@@ -109,21 +100,18 @@ fn part2(input: &PartInput, part_1_ans: usize) -> usize {
     max + min
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    let part_1_answer = part1(&input);
+    fn solve_part1(in_str: &str) -> String {
+        first_invalid(&parse_input(in_str).unwrap(), 5).to_string()
+    }
 
-    b.iter(|| part2(&input, part_1_answer))
-}
+    fn solve_part2(in_str: &str) -> String {
+        let input = parse_input(in_str).unwrap();
+        part2(&input, first_invalid(&input, 5)).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day09.rs"));
 }
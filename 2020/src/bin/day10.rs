@@ -1,36 +1,33 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness};
 
 type Input = Vec<usize>;
 type PartInput = [usize];
-const INPUT_NUM: i32 = 0;
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day10.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
 }
 
-fn get_input() -> Input {
-    let in_str = match INPUT_NUM {
-        0 => include_str!("../inputs/day10.inp"),
-        1 => include_str!("../test_inputs/day10.inp1"),
-        2 => include_str!("../test_inputs/day10.inp2"),
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
-    };
-
-    let mut inp: Input = in_str.lines().map(|line| line.parse().unwrap()).collect();
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    let mut inp: Input = in_str
+        .lines()
+        .map(|line| src.number(line))
+        .collect::<Result<_, _>>()?;
 
     inp.sort_unstable();
 
-    inp
+    Ok(inp)
 }
 
-fn part1(input: &PartInput) -> usize {
+pub(crate) fn part1(input: &PartInput) -> usize {
     let mut diff_1 = 0;
     let mut diff_3 = 1;
 
@@ -50,10 +47,10 @@ fn part1(input: &PartInput) -> usize {
         }
     }
 
-    diff_1 * diff_3
+    checked::mul(diff_1, diff_3)
 }
 
-fn part2(input: &PartInput) -> usize {
+pub(crate) fn part2(input: &PartInput) -> usize {
     let mut perms = 1;
     let mut group_len = 1;
 
@@ -70,14 +67,14 @@ fn part2(input: &PartInput) -> usize {
             group_len += 1
         } else {
             // extend match if larger groups are found
-            perms *= calc_perms(group_len);
+            perms = checked::mul(perms, calc_perms(group_len));
 
             // reset the length of the group
             group_len = 1;
         }
     }
 
-    perms * calc_perms(group_len)
+    checked::mul(perms, calc_perms(group_len))
 }
 
 fn calc_perms(group_size: usize) -> usize {
@@ -90,20 +87,17 @@ fn calc_perms(group_size: usize) -> usize {
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
 
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day10.rs"));
 }
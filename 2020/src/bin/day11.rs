@@ -1,38 +1,22 @@
-#![feature(test)]
-extern crate test;
-
-use grid::Grid;
+use aoc_utils::grid::{Grid, SURROUNDING};
+use aoc_utils::harness;
+use aoc_utils::parse::ParseError;
+use aoc_utils::record::{Palette, Recorder};
+use aoc_utils::trace;
 
 type Input = Grid<Cell>;
-const INPUT_NUM: i32 = 0;
-
-fn main() {
-    let input = get_input();
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
+pub(crate) const INPUT: &str = include_str!("../inputs/day11.inp");
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(INPUT, parse_input, part1, part2)
 }
 
-fn get_input() -> Input {
-    let in_str = match INPUT_NUM {
-        0 => include_str!("../inputs/day11.inp"),
-        1 => include_str!("../test_inputs/day11.inp1"),
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
-    };
-
-    Grid::from_vec(
-        in_str
-            .lines()
-            .flat_map(|line| line.chars().map(Cell::from_char))
-            .collect(),
-        in_str.find('\n').unwrap(),
-    )
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    Grid::parse(in_str, "'.', 'L' or '#'", Cell::from_char)
 }
 
-fn part1(input: &Input) -> usize {
+pub(crate) fn part1(input: &Input) -> usize {
     let ruleset = 1;
 
     // make two mutable copies of the state to evolve the state between
@@ -44,7 +28,7 @@ fn part1(input: &Input) -> usize {
     count_occupied(board_a)
 }
 
-fn part2(input: &Input) -> usize {
+pub(crate) fn part2(input: &Input) -> usize {
     let ruleset = 2;
 
     // make two mutable copies of the state to evolve the state between
@@ -53,25 +37,41 @@ fn part2(input: &Input) -> usize {
 
     evolve_till_stable(board_a, board_b, ruleset);
 
-    count_occupied(&board_a)
+    count_occupied(board_a)
 }
 
 fn evolve_till_stable(board: &mut Input, copy_board: &mut Input, ruleset: usize) {
+    let _span = trace::span("evolve");
     let mut iter_no = 0;
+    let mut recorder = Recorder::requested(&format!("part{ruleset}"), Cell::palette());
+    if let Some(recorder) = &mut recorder {
+        recorder.frame(board);
+    }
 
     loop {
-        let changes = match iter_no % 2 {
-            0 => evolve_board(board, copy_board, ruleset),
-            1 => evolve_board(copy_board, board, ruleset),
+        let (changes, after) = match iter_no % 2 {
+            0 => (evolve_board(board, copy_board, ruleset), &*copy_board),
+            1 => (evolve_board(copy_board, board, ruleset), &*board),
             _ => panic!("Bitch how did you break modulo??"),
         };
 
+        trace!("round", round = iter_no + 1, changes);
+        trace!("board", seats = after.map(Cell::to_char));
+
         if changes == 0 {
             break;
         }
 
+        if let Some(recorder) = &mut recorder {
+            recorder.frame(after);
+        }
+
         iter_no += 1;
     }
+
+    if let Some(recorder) = recorder {
+        recorder.finish();
+    }
 }
 
 fn evolve_board(before_state: &Input, after_state: &mut Input, ruleset: usize) -> usize {
@@ -85,49 +85,32 @@ fn evolve_board(before_state: &Input, after_state: &mut Input, ruleset: usize) -
 
     let non_floor_iter = before_state
         .iter()
-        .enumerate()
-        .zip(after_state.iter_mut())
+        .zip(after_state.cells_mut())
         .filter(|((_, cell), _)| !cell.is_floor());
 
-    'cell_loop: for ((cell_no, cell), after_cell) in non_floor_iter {
-        let cols = before_state.cols();
-        let (cell_x, cell_y) = div_rem(cell_no, cols);
-
+    'cell_loop: for ((pos, cell), after_cell) in non_floor_iter {
         let mut num_occupied = 0;
-        for i in 0..9 {
-            // i == 4 maps to an x and y offset of 0
-            if i == 4 {
-                continue;
-            };
-
-            let (x_off, y_off) = {
-                let (i, j) = div_rem(i, 3);
-
-                (i as isize - 1, j as isize - 1)
-            };
-
+        for delta in SURROUNDING {
             match ruleset {
                 1 => {
-                    let (x, y) = (cell_x as isize + x_off, cell_y as isize + y_off);
-
-                    if let Some(Cell::Occupied) = before_state.get(x as usize, y as usize) {
+                    let neighbour = before_state.offset(pos, delta);
+                    if let Some(Cell::Occupied) = neighbour.map(|pos| &before_state[pos]) {
                         num_occupied += 1;
                     }
                 }
                 2 => {
-                    let (mut x, mut y) = (cell_x as isize + x_off, cell_y as isize + y_off);
-                    loop {
-                        match before_state.get(x as usize, y as usize) {
-                            Some(Cell::Occupied) => {
+                    let mut seen = before_state.offset(pos, delta);
+                    while let Some(seen_pos) = seen {
+                        match before_state[seen_pos] {
+                            Cell::Occupied => {
                                 num_occupied += 1;
                                 break;
                             }
-                            Some(Cell::Floor) => (/* do nothing */),
-                            Some(Cell::Empty) | None => break,
+                            Cell::Floor => (/* do nothing */),
+                            Cell::Empty => break,
                         }
 
-                        x += x_off;
-                        y += y_off;
+                        seen = before_state.offset(seen_pos, delta);
                     }
                 }
                 _ => panic!("Unrecognised ruleset: {}", ruleset),
@@ -148,7 +131,7 @@ fn evolve_board(before_state: &Input, after_state: &mut Input, ruleset: usize) -
             num_changes += 1;
             Cell::Occupied
         } else {
-            before_state.get(cell_x, cell_y).unwrap().clone()
+            cell.clone()
         };
     }
 
@@ -156,23 +139,27 @@ fn evolve_board(before_state: &Input, after_state: &mut Input, ruleset: usize) -
 }
 
 fn count_occupied(board: &Input) -> usize {
-    board.iter().filter(|cell| cell.is_occupied()).count()
+    board
+        .cells()
+        .iter()
+        .filter(|cell| cell.is_occupied())
+        .count()
 }
 
-#[derive(Debug, Clone)]
-enum Cell {
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Cell {
     Floor,
     Empty,
     Occupied,
 }
 
 impl Cell {
-    fn from_char(c: char) -> Cell {
+    fn from_char(c: char) -> Option<Cell> {
         match c {
-            '.' => Cell::Floor,
-            '#' => Cell::Occupied,
-            'L' => Cell::Empty,
-            _ => panic!("Cannot convert {:?} to Cell.", c),
+            '.' => Some(Cell::Floor),
+            '#' => Some(Cell::Occupied),
+            'L' => Some(Cell::Empty),
+            _ => None,
         }
     }
 
@@ -184,6 +171,12 @@ impl Cell {
         }
     }
 
+    fn palette() -> Palette<Cell> {
+        Palette::new([40, 40, 40])
+            .with(Cell::Empty, [90, 160, 90])
+            .with(Cell::Occupied, [230, 120, 40])
+    }
+
     fn is_floor(&self) -> bool {
         matches!(self, Cell::Floor)
     }
@@ -195,40 +188,17 @@ impl Cell {
     }
 }
 
-fn div_rem(lhs: usize, rhs: usize) -> (usize, usize) {
-    let div = lhs / rhs;
-    let rem = lhs % rhs;
-
-    (div, rem)
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[allow(dead_code)]
-fn print_board(input: &Input) {
-    for row in 0..input.rows() {
-        println!(
-            "{}",
-            input
-                .iter_row(row)
-                .map(|cell| cell.to_char())
-                .collect::<String>()
-        );
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
     }
-}
-
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day11.rs"));
 }
@@ -1,41 +1,44 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 type Input = Vec<(Direction, i64)>;
 type PartInput = [(Direction, i64)];
-const INPUT_NUM: i32 = 0;
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day12.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
 }
 
-fn get_input() -> Input {
-    let in_str = match INPUT_NUM {
-        0 => include_str!("../inputs/day12.inp"),
-        1 => include_str!("../test_inputs/day12.inp1"),
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
-    };
-
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
     in_str
         .lines()
         .map(|line| {
-            let (dir, num) = line.split_at(1);
+            let Some(dir_str) = line.get(..1) else {
+                return Err(src.error(line, "an action"));
+            };
+            let num_str = &line[1..];
+
+            let dir: Direction = src.parse(dir_str)?;
+            let num = src.number(num_str)?;
+            if dir.is_turn() && !matches!(num, 90 | 180 | 270) {
+                return Err(src.error(num_str, "a turn of 90, 180 or 270 degrees"));
+            }
 
-            (dir.parse().unwrap(), num.parse().unwrap())
+            Ok((dir, num))
         })
         .collect()
 }
 
-fn part1(input: &PartInput) -> usize {
+pub(crate) fn part1(input: &PartInput) -> usize {
     let mut ship = Ship::new();
 
     for (action, value) in input.iter() {
@@ -51,7 +54,7 @@ fn part1(input: &PartInput) -> usize {
     ship.position.manhattan_distance()
 }
 
-fn part2(input: &PartInput) -> usize {
+pub(crate) fn part2(input: &PartInput) -> usize {
     let mut ship = Ship::new();
     let mut waypoint = Point::new_at(10, 1);
 
@@ -166,7 +169,7 @@ impl Point {
 }
 
 #[derive(Debug, Copy, Clone)]
-enum Direction {
+pub(crate) enum Direction {
     North,
     South,
     East,
@@ -222,7 +225,7 @@ impl Direction {
 }
 
 impl FromStr for Direction {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
@@ -233,25 +236,22 @@ impl FromStr for Direction {
             "L" => Direction::Left,
             "R" => Direction::Right,
             "F" => Direction::Forward,
-            _ => panic!("Invalid direction: {:?}", s),
+            _ => return Err(Source::new(s).error(s, "one of N, S, E, W, L, R or F")),
         })
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
 
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day12.rs"));
 }
@@ -1,46 +1,40 @@
-#![feature(test)]
-extern crate test;
-
-use aoc::bit_ops::BitOps;
+use aoc_utils::bit_ops::BitOps;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use either::{Either, Left, Right};
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 use ahash::AHashMap;
 
 type Input = Vec<Either<Mask, MemAction>>;
 type PartInput = [Either<Mask, MemAction>];
-const INPUT_NUM: i32 = 0;
-
-fn main() {
-    let input = get_input();
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
+pub(crate) const INPUT: &str = include_str!("../inputs/day14.inp");
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
 }
 
-fn get_input() -> Input {
-    let in_str = match INPUT_NUM {
-        0 => include_str!("../inputs/day14.inp"),
-        1 => include_str!("../test_inputs/day14.inp1"),
-        2 => include_str!("../test_inputs/day14.inp2"),
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
-    };
-
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
     in_str
         .lines()
-        .map(|line| match line.chars().nth(1) {
-            Some('a') => Left(line.parse::<Mask>().unwrap()),
-            _ => Right(line.parse::<MemAction>().unwrap()),
+        .map(|line| {
+            Ok(match line.chars().nth(1) {
+                Some('a') => Left(src.parse::<Mask>(line)?),
+                _ => Right(src.parse::<MemAction>(line)?),
+            })
         })
         .collect()
 }
 
-fn part1(input: &PartInput) -> usize {
+pub(crate) fn part1(input: &PartInput) -> usize {
     let mut mask: Mask = Default::default();
 
     let mut mem = AHashMap::default();
@@ -57,7 +51,7 @@ fn part1(input: &PartInput) -> usize {
     mem.values().sum()
 }
 
-fn part2(input: &PartInput) -> usize {
+pub(crate) fn part2(input: &PartInput) -> usize {
     let mut mask: Mask = Default::default();
 
     let mut mem = AHashMap::default();
@@ -78,7 +72,7 @@ fn part2(input: &PartInput) -> usize {
 }
 
 #[derive(Debug, Copy, Clone)]
-struct Mask {
+pub(crate) struct Mask {
     one_mask: usize,
     zero_mask: usize,
 }
@@ -107,19 +101,25 @@ impl Default for Mask {
 }
 
 impl FromStr for Mask {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mask_bits = 36;
 
+        let src = Source::new(s);
+        let bits = src.strip_prefix(s, "mask = ")?;
+        if bits.len() != mask_bits {
+            return Err(src.error(bits, format!("{mask_bits} mask bits")));
+        }
+
         let mut mask: Mask = Default::default();
 
-        for (i, c) in s.chars().skip(7).enumerate() {
+        for (i, c) in bits.bytes().enumerate() {
             match c {
-                '1' => mask.one_mask.set_bit(mask_bits - 1 - i as u32),
-                '0' => mask.zero_mask.clear_bit(mask_bits - 1 - i as u32),
-                'X' => (),
-                _ => panic!("Invalid character {:?} in mask.", c),
+                b'1' => mask.one_mask.set_bit(mask_bits as u32 - 1 - i as u32),
+                b'0' => mask.zero_mask.clear_bit(mask_bits as u32 - 1 - i as u32),
+                b'X' => (),
+                _ => return Err(src.error_at(bits, i, "0, 1 or X")),
             }
         }
 
@@ -128,24 +128,27 @@ impl FromStr for Mask {
 }
 
 #[derive(Debug, Copy, Clone)]
-struct MemAction {
+pub(crate) struct MemAction {
     addr: usize,
     value: usize,
 }
 
 impl FromStr for MemAction {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let end_brac = s.find(']').unwrap();
-        let last_space = s.rfind(' ').unwrap();
+        let src = Source::new(s);
+        let rest = src.strip_prefix(s, "mem[")?;
+        let (addr_str, value_str) = src.split_once(rest, "] = ")?;
 
-        let addr_fromstr = s.get(4..end_brac).unwrap().parse::<usize>()?;
-        let value_fromstr = s.get(last_space + 1..).unwrap().parse::<usize>()?;
+        let addr: usize = src.number(addr_str)?;
+        if addr >> 36 != 0 {
+            return Err(src.error(addr_str, "a 36 bit address"));
+        }
 
         Ok(MemAction {
-            addr: addr_fromstr,
-            value: value_fromstr,
+            addr,
+            value: src.number(value_str)?,
         })
     }
 }
@@ -194,20 +197,17 @@ impl Iterator for FloatingMask {
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
 
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day14.rs"));
 }
@@ -1,5 +1,5 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness};
 
 use rustc_hash::FxHashMap;
 
@@ -7,44 +7,34 @@ type Map = FxHashMap<usize, usize>;
 
 type Input = Vec<usize>;
 type PartInput = [usize];
-const INPUT_NUM: usize = 0;
 
-fn main() {
-    let input = get_input(INPUT_NUM);
+pub(crate) const INPUT: &str = include_str!("../inputs/day15.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
 }
 
-fn get_input(input_num: usize) -> Input {
-    let in_str = match input_num {
-        0 => include_str!("../inputs/day15.inp"),
-        1 => include_str!("../test_inputs/day15.inp1"),
-        2 => include_str!("../test_inputs/day15.inp2"),
-        3 => include_str!("../test_inputs/day15.inp3"),
-        4 => include_str!("../test_inputs/day15.inp4"),
-        5 => include_str!("../test_inputs/day15.inp5"),
-        6 => include_str!("../test_inputs/day15.inp6"),
-        7 => include_str!("../test_inputs/day15.inp7"),
-        _ => panic!("Unknown input number: {:?}", input_num),
-    };
-
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
     in_str
+        .trim_end()
         .split(',')
-        .map(|num| num.trim_end().parse().unwrap())
+        .map(|num| src.number(num))
         .collect()
 }
 
-fn part1(input: &PartInput) -> usize {
+pub(crate) fn part1(input: &PartInput) -> usize {
     let mut game = Game::new(input);
 
     game.nth(2020 - 1).unwrap()
 }
 
-fn part2(input: &PartInput) -> usize {
+pub(crate) fn part2(input: &PartInput) -> usize {
     let mut game = Game::new(input);
 
     game.nth(30000000 - 1).unwrap()
@@ -84,8 +74,9 @@ impl Iterator for Game<'_> {
             next_no = 0;
         } else {
             let last_seen = self.last_turns[&self.last_no];
-            next_no = self.iter_no - last_seen - 1;
-            self.last_turns.insert(self.last_no, self.iter_no - 1);
+            next_no = checked::sub(checked::sub(self.iter_no, last_seen), 1);
+            self.last_turns
+                .insert(self.last_no, checked::sub(self.iter_no, 1));
         }
 
         // update map entry
@@ -103,20 +94,17 @@ impl Iterator for Game<'_> {
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input(0);
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input(0);
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
 
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input(0));
+    include!(concat!(env!("OUT_DIR"), "/examples/day15.rs"));
 }
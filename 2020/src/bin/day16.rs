@@ -1,76 +1,57 @@
-#![feature(binary_heap_into_iter_sorted)]
-#![feature(test)]
-extern crate test;
+use aoc_utils::bit_ops::BitSet;
+use aoc_utils::harness;
+use aoc_utils::interval::IntervalSet;
+use aoc_utils::parse::{ParseError, Source};
 
-use aoc::bit_ops::BitOps;
-
-use std::num::ParseIntError;
-use std::ops::RangeInclusive;
+use std::ops::Range;
 use std::str::FromStr;
 
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
-
 type Ticket = Vec<usize>;
 type Input = (Vec<Rule>, Ticket, Vec<Ticket>);
 
-const INPUT_NUM: i32 = 0;
-
-fn main() {
-    let input = get_input();
-
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
+pub(crate) const INPUT: &str = include_str!("../inputs/day16.inp");
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(INPUT, parse_input, part1, part2)
 }
 
-fn get_input() -> Input {
-    let in_str = match INPUT_NUM {
-        0 => include_str!("../inputs/day16.inp"),
-        1 => include_str!("../test_inputs/day16.inp1"),
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
-    };
-
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
     let mut blocks = in_str.split("\n\n");
     let rules: Vec<Rule> = blocks
         .next()
-        .unwrap()
+        .unwrap_or(src.end())
         .lines()
-        .map(|line| line.parse().unwrap())
-        .collect();
+        .map(|line| src.parse(line))
+        .collect::<Result<_, _>>()?;
 
-    let your_ticket_str = blocks.next().unwrap();
-    let your_ticket_offset = your_ticket_str.find('\n').unwrap();
-    let ticket_str = your_ticket_str.get(your_ticket_offset + 1..).unwrap();
-    let your_ticket: Ticket = parse_ticket(ticket_str);
+    let your_ticket_str = blocks.next().unwrap_or(src.end());
+    let ticket_str = src.strip_prefix(your_ticket_str, "your ticket:\n")?;
+    let your_ticket: Ticket = parse_ticket(&src, ticket_str.trim_end(), rules.len())?;
 
-    let nearby_tickets: Vec<Ticket> = blocks
-        .next()
-        .unwrap()
+    let nearby_str = blocks.next().unwrap_or(src.end());
+    let nearby_tickets: Vec<Ticket> = src
+        .strip_prefix(nearby_str, "nearby tickets:\n")?
         .lines()
-        .skip(1)
-        .map(|line| parse_ticket(line))
-        .collect();
+        .map(|line| parse_ticket(&src, line, rules.len()))
+        .collect::<Result<_, _>>()?;
 
-    (rules, your_ticket, nearby_tickets)
+    Ok((rules, your_ticket, nearby_tickets))
 }
 
-fn part1(input: &Input) -> usize {
+pub(crate) fn part1(input: &Input) -> usize {
     let (rules, _, nearby) = input;
+    let any_rule = rules
+        .iter()
+        .fold(IntervalSet::new(), |valid, rule| valid.union(&rule.valid));
 
     nearby
         .iter()
-        .flat_map(|ticket| {
-            ticket
-                .iter()
-                .filter(|num| !rules.iter().any(|rule| rule.validate(*num)))
-        })
+        .flat_map(|ticket| ticket.iter().filter(|&&num| !any_rule.contains(num as i64)))
         .sum()
 }
 
-fn part2(input: &Input) -> usize {
+pub(crate) fn part2(input: &Input) -> usize {
     let (rules, my_ticket, nearby) = input;
 
     // iterator over valid nearby tickets
@@ -78,144 +59,103 @@ fn part2(input: &Input) -> usize {
         // filter on first ticket number for each one
         ticket
             .iter()
-            .all(|ref num| rules.iter().any(|rule| rule.validate(num)))
+            .all(|num| rules.iter().any(|rule| rule.validate(num)))
     });
 
-    // represent a rule being applicable to a position by
-    // bit <pos> being set in the usize at position <rule_num>
-    let mut poss_rules = vec![usize::MAX; rules.len()];
+    // the ticket positions each rule could still apply to
+    let mut poss_fields: Vec<BitSet> = vec![(0..rules.len()).collect(); rules.len()];
 
     for ticket in nearby_filt {
         for (tic_idx, num) in ticket.iter().enumerate() {
             for (rul_idx, rule) in rules.iter().enumerate() {
-                if poss_rules[rul_idx].test_bit(tic_idx as u32) && !rule.validate(num) {
-                    poss_rules[rul_idx].clear_bit(tic_idx as u32);
+                if !rule.validate(num) {
+                    poss_fields[rul_idx].remove(tic_idx);
                 }
             }
         }
     }
 
-    let mut minheap: BinaryHeap<RuleBits> = Default::default();
-
-    let mask = usize::MAX >> (64 - rules.len());
-    for (rule_no, rule) in poss_rules.iter().enumerate() {
-        let rb = RuleBits {
-            rule_no,
-            bit_pat: *rule & mask,
-        };
-        minheap.push(rb);
-    }
+    // the rule with the fewest options is down to one, which rules it out for the others
+    let mut by_options: Vec<usize> = (0..rules.len()).collect();
+    by_options.sort_by_key(|&rule_no| poss_fields[rule_no].len());
 
     let mut product = 1;
-    let mut eliminated = mask;
-    for rule in minheap.into_iter_sorted() {
-        let masked = rule.bit_pat & eliminated;
-
-        let field_no = masked.trailing_zeros();
+    let mut eliminated = BitSet::with_capacity(rules.len());
+    for rule_no in by_options {
+        let field_no = (&poss_fields[rule_no] - &eliminated)
+            .first()
+            .expect("a field left for every rule");
 
-        eliminated.clear_bit(field_no);
+        eliminated.insert(field_no);
 
-        if rules[rule.rule_no].rule_name.starts_with("departure") {
-            product *= my_ticket[field_no as usize];
+        if rules[rule_no].rule_name.starts_with("departure") {
+            product *= my_ticket[field_no];
         }
     }
 
     product
 }
 
-fn parse_ticket(s: &str) -> Vec<usize> {
-    s.split(',').map(|line| line.parse().unwrap()).collect()
+fn parse_ticket(src: &Source, s: &str, fields: usize) -> Result<Ticket, ParseError> {
+    let ticket: Ticket = s
+        .split(',')
+        .map(|num| src.number(num))
+        .collect::<Result<_, _>>()?;
+    if ticket.len() != fields {
+        return Err(src.error(s, format!("{fields} fields, one for each rule")));
+    }
+    Ok(ticket)
 }
 
 #[derive(Debug)]
-struct Rule {
+pub(crate) struct Rule {
     rule_name: String,
-    range_1: RangeInclusive<usize>,
-    range_2: RangeInclusive<usize>,
+    valid: IntervalSet,
 }
 
 impl Rule {
     fn validate(&self, n: &usize) -> bool {
-        self.range_1.contains(n) || self.range_2.contains(n)
+        self.valid.contains(*n as i64)
     }
 }
 
 impl FromStr for Rule {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let colon_pos = s.find(':').unwrap();
-        let rule_name = s.get(..colon_pos).unwrap();
-
-        let o_pos = s.find(" o").unwrap();
-
-        let rule_1_str = s.get(colon_pos + 2..o_pos).unwrap();
-        let rule_2_str = s.get(o_pos + 4..).unwrap();
-
-        let r1_dash_pos = rule_1_str.find('-').unwrap();
-        let r2_dash_pos = rule_2_str.find('-').unwrap();
-
-        let r1_lower = rule_1_str.get(..r1_dash_pos).unwrap();
-        let r1_upper = rule_1_str.get(r1_dash_pos + 1..).unwrap();
-
-        let r2_lower = rule_2_str.get(..r2_dash_pos).unwrap();
-        let r2_upper = rule_2_str.get(r2_dash_pos + 1..).unwrap();
-
-        let r1_low_fromstr: usize = r1_lower.parse()?;
-        let r1_upp_fromstr: usize = r1_upper.parse()?;
-        let r2_low_fromstr: usize = r2_lower.parse()?;
-        let r2_upp_fromstr: usize = r2_upper.parse()?;
+        let src = Source::new(s);
+        let (rule_name, ranges) = src.split_once(s, ": ")?;
+        let (rule_1_str, rule_2_str) = src.split_once(ranges, " or ")?;
 
         Ok(Self {
             rule_name: rule_name.to_string(),
-            range_1: (r1_low_fromstr..=r1_upp_fromstr),
-            range_2: (r2_low_fromstr..=r2_upp_fromstr),
+            valid: [
+                parse_range(&src, rule_1_str)?,
+                parse_range(&src, rule_2_str)?,
+            ]
+            .into_iter()
+            .collect(),
         })
     }
 }
 
-struct RuleBits {
-    rule_no: usize,
-    bit_pat: usize,
+fn parse_range(src: &Source, s: &str) -> Result<Range<i64>, ParseError> {
+    let (lower, upper) = src.split_once(s, "-")?;
+    let (lower, upper): (u32, u32) = (src.number(lower)?, src.number(upper)?);
+    Ok(i64::from(lower)..i64::from(upper) + 1)
 }
 
-impl Ord for RuleBits {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.bit_pat
-            .count_ones()
-            .cmp(&other.bit_pat.count_ones())
-            .reverse()
-    }
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-impl PartialOrd for RuleBits {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
     }
-}
 
-impl PartialEq for RuleBits {
-    fn eq(&self, other: &Self) -> bool {
-        self.bit_pat.count_ones() == other.bit_pat.count_ones()
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
     }
-}
-
-impl Eq for RuleBits {}
-
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
-
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
-
-    b.iter(|| part2(&input))
-}
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day16.rs"));
 }
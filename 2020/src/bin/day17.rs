@@ -1,5 +1,10 @@
-#![feature(test)]
-extern crate test;
+use std::fmt::{self, Display};
+
+use aoc_utils::grid::Grid;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::record::{Palette, Recorder};
+use aoc_utils::trace;
 
 use either::{Either, Left, Right};
 use rustc_hash::FxHashSet;
@@ -9,49 +14,54 @@ type Coord4d = (i32, i32, i32, i32);
 type Position = Either<Coord3d, Coord4d>;
 type Input = FxHashSet<Position>;
 
-const INPUT_NUM: i32 = 0;
-
-fn main() {
-    let input = get_input();
-
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
+pub(crate) const INPUT: &str = include_str!("../inputs/day17.inp");
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(INPUT, parse_input, part1, part2)
 }
 
-fn get_input() -> Input {
-    let in_str = match INPUT_NUM {
-        0 => include_str!("../inputs/day17.inp"),
-        1 => include_str!("../test_inputs/day17.inp1"),
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
-    };
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    let mut active: Input = Default::default();
+
+    for (y, line) in in_str.lines().enumerate() {
+        for (x, c) in line.bytes().enumerate() {
+            match c {
+                b'#' => {
+                    active.insert(Left((x as i32, y as i32, 0i32)));
+                }
+                b'.' => (),
+                _ => return Err(src.error_at(line, x, "\".\" or \"#\"")),
+            }
+        }
+    }
 
-    in_str
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter(|(_, c)| *c == '#')
-                .map(move |(x, _)| Left((x as i32, y as i32, 0i32)))
-        })
-        .collect()
+    Ok(active)
 }
 
-fn part1(input: &Input) -> usize {
+pub(crate) fn part1(input: &Input) -> usize {
+    let mut recorder = Recorder::requested("part1", column_palette());
+    draw(&mut recorder, input, input);
+
     let mut grid = cycle(input);
+    draw(&mut recorder, input, &grid);
+    traced(1, &grid);
 
-    for _ in 0..5 {
+    for cycle_no in 2..=6 {
         grid = cycle(&grid);
+        draw(&mut recorder, input, &grid);
+        traced(cycle_no, &grid);
+    }
+
+    if let Some(recorder) = recorder {
+        recorder.finish();
     }
 
     grid.len()
 }
 
 // use either on the pos to get the nice overrides
-fn part2(input: &Input) -> usize {
+pub(crate) fn part2(input: &Input) -> usize {
     let mut grid: Input = Default::default();
 
     // add the w thing
@@ -61,13 +71,27 @@ fn part2(input: &Input) -> usize {
             .map(|pos| pos.left_and_then(|(x, y, z)| Right((x, y, z, 0i32)))),
     );
 
-    for _ in 0..6 {
+    let mut recorder = Recorder::requested("part2", column_palette());
+    draw(&mut recorder, input, &grid);
+
+    for cycle_no in 1..=6 {
         grid = cycle(&grid);
+        draw(&mut recorder, input, &grid);
+        traced(cycle_no, &grid);
+    }
+
+    if let Some(recorder) = recorder {
+        recorder.finish();
     }
 
     grid.len()
 }
 
+fn traced(cycle: usize, space: &Input) {
+    trace!("cycle", cycle, active = space.len());
+    trace!("layers", layers = Layers(space));
+}
+
 fn cycle(space: &Input) -> Input {
     let mut new_space: Input = Default::default();
 
@@ -105,78 +129,92 @@ fn count_neighbours(space: &Input, pos: Position) -> usize {
     num_neighbours
 }
 
-#[allow(dead_code)]
-fn print_grid(grid: &Input) {
-    let is_coord3d = grid.iter().next().unwrap().is_left();
+/// How far the cubes can spread past the input in six cycles.
+const SPREAD: i32 = 6;
+/// Columns with more active cubes than this are drawn the same.
+const LEVELS: usize = 8;
 
-    let mut x_bounds = (0, 0);
-    let mut y_bounds = (0, 0);
-    let mut z_bounds = (0, 0);
-    let mut w_bounds = (0, 0);
+fn column_palette() -> Palette<usize> {
+    (1..=LEVELS as u8).fold(Palette::new([0, 0, 0]), |palette, n| {
+        palette.with(n as usize, [n * 31, n * 24, 64 + n * 20])
+    })
+}
 
-    for coord in grid.iter() {
-        let (x, y, z, w) = match coord {
-            Left((x, y, z)) => (x, y, z, &0i32),
-            Right((x, y, z, w)) => (x, y, z, w),
-        };
+/// Record a frame of how many cubes are active in each column through z (and w), covering
+/// everywhere they can spread to from `input`.
+fn draw(recorder: &mut Option<Recorder<usize>>, input: &Input, space: &Input) {
+    let Some(recorder) = recorder else {
+        return;
+    };
 
-        if x < &x_bounds.0 {
-            x_bounds.0 = *x
-        }
-        if x > &x_bounds.1 {
-            x_bounds.1 = *x
+    let x_y = |pos: &Position| match *pos {
+        Left((x, y, _)) => (x, y),
+        Right((x, y, _, _)) => (x, y),
+    };
+    let width = input.iter().map(|pos| x_y(pos).0 + 1).max().unwrap_or(0) + 2 * SPREAD;
+    let height = input.iter().map(|pos| x_y(pos).1 + 1).max().unwrap_or(0) + 2 * SPREAD;
+
+    let mut columns = Grid::from_vec(vec![0; (width * height) as usize], width as usize);
+    for pos in space {
+        let (x, y) = x_y(pos);
+        let column = ((y + SPREAD) as usize, (x + SPREAD) as usize);
+        if let Some(count) = columns.get_mut(column) {
+            *count = (*count + 1).min(LEVELS);
         }
+    }
 
-        if y < &y_bounds.0 {
-            y_bounds.0 = *y
-        }
-        if y > &y_bounds.1 {
-            y_bounds.1 = *y
-        }
+    recorder.frame(&columns);
+}
 
-        if z < &z_bounds.0 {
-            z_bounds.0 = *z
-        }
-        if z > &z_bounds.1 {
-            z_bounds.1 = *z
-        }
+/// Each z (and w) layer of the active cubes, like the puzzle draws them.
+struct Layers<'a>(&'a Input);
 
-        if !is_coord3d {
-            if w < &w_bounds.0 {
-                w_bounds.0 = *w
-            }
-            if w > &w_bounds.1 {
-                w_bounds.1 = *w
+impl Display for Layers<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(first) = self.0.iter().next() else {
+            return Ok(());
+        };
+        let is_coord3d = first.is_left();
+        let coords = || {
+            self.0.iter().map(|pos| match *pos {
+                Left((x, y, z)) => [x, y, z, 0],
+                Right((x, y, z, w)) => [x, y, z, w],
+            })
+        };
+
+        let mut bounds = [(0, 0); 4];
+        for coord in coords() {
+            for (bound, n) in bounds.iter_mut().zip(coord) {
+                *bound = (bound.0.min(n), bound.1.max(n));
             }
         }
-    }
+        let [x_bounds, y_bounds, z_bounds, w_bounds] = bounds;
+
+        for w in w_bounds.0..=w_bounds.1 {
+            for z in z_bounds.0..=z_bounds.1 {
+                if is_coord3d {
+                    writeln!(f, "z={}", z)?;
+                } else {
+                    writeln!(f, "z={}, w={}", z, w)?;
+                }
 
-    for w in w_bounds.0..=w_bounds.1 {
-        for z in z_bounds.0..=z_bounds.1 {
-            if is_coord3d {
-                println!("z={}", z);
-            } else {
-                println!("z={}, w={}", z, w);
-            }
+                for y in y_bounds.0..=y_bounds.1 {
+                    for x in x_bounds.0..=x_bounds.1 {
+                        let point = if is_coord3d {
+                            Left((x, y, z))
+                        } else {
+                            Right((x, y, z, w))
+                        };
 
-            for y in y_bounds.0..=y_bounds.1 {
-                for x in x_bounds.0..=x_bounds.1 {
-                    let point = if is_coord3d {
-                        Left((x, y, z))
-                    } else {
-                        Right((x, y, z, w))
-                    };
-
-                    if grid.contains(&point) {
-                        print!("#");
-                    } else {
-                        print!(".");
+                        write!(f, "{}", if self.0.contains(&point) { '#' } else { '.' })?;
                     }
+                    writeln!(f)?;
                 }
-                println!();
+                writeln!(f)?;
             }
-            println!();
         }
+
+        Ok(())
     }
 }
 
@@ -227,27 +265,17 @@ impl Iterator for Neighbours {
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| part1(&input))
-}
-
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-    b.iter(|| part2(&input))
-}
-
-#[bench]
-fn bench_cycle(b: &mut test::Bencher) {
-    let input = get_input();
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
 
-    b.iter(|| cycle(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day17.rs"));
 }
@@ -1,62 +1,82 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness};
 
 use std::iter::Peekable;
 
-const INPUT_NUM: i32 = 0;
-
 type Input = Vec<String>;
 type PartInput = [String];
 
-fn main() {
-    let input = get_input();
+pub(crate) const INPUT: &str = include_str!("../inputs/day18.inp");
 
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
+}
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    in_str
+        .lines()
+        .map(|line| {
+            check_expression(&src, line)?;
+
+            Ok(line
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>())
+        })
+        .collect()
 }
 
-fn get_input() -> Input {
-    match INPUT_NUM {
-        0 => include_str!("../inputs/day18.inp"),
-        1 => include_str!("../test_inputs/day18.inp1"),
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
+/// Check `line` is an expression `eval` can handle: single digits, `+`, `*` and balanced
+/// parentheses.
+fn check_expression(src: &Source, line: &str) -> Result<(), ParseError> {
+    let mut want_operand = true;
+    let mut depth = 0;
+
+    for (i, c) in line.bytes().enumerate() {
+        match (want_operand, c) {
+            (_, b' ') => (),
+            (true, b'0'..=b'9') => want_operand = false,
+            (true, b'(') => depth += 1,
+            (false, b'+' | b'*') => want_operand = true,
+            (false, b')') if depth > 0 => depth -= 1,
+            (true, _) => return Err(src.error_at(line, i, "a digit or \"(\"")),
+            (false, _) => return Err(src.error_at(line, i, "\"+\", \"*\" or \")\"")),
+        }
+    }
+
+    if want_operand {
+        Err(src.error_at(line, line.len(), "a digit or \"(\""))
+    } else if depth > 0 {
+        Err(src.error_at(line, line.len(), "\")\""))
+    } else {
+        Ok(())
     }
-    .lines()
-    .map(|line| {
-        line.chars()
-            .filter(|c| !c.is_whitespace())
-            .collect::<String>()
-    })
-    .collect()
 }
 
-fn part1(input: &PartInput) -> usize {
-    input
-        .iter()
-        .map(|line| {
-            let line_copy = line.clone();
+pub(crate) fn part1(input: &PartInput) -> usize {
+    checked::sum(input.iter().map(|line| {
+        let line_copy = line.clone();
 
-            let iter = &mut line_copy.chars().peekable();
+        let iter = &mut line_copy.chars().peekable();
 
-            eval(iter, false)
-        })
-        .sum()
+        eval(iter, false)
+    }))
 }
 
-fn part2(input: &PartInput) -> usize {
-    input
-        .iter()
-        .map(|line| {
-            let line_copy = line.clone();
+pub(crate) fn part2(input: &PartInput) -> usize {
+    checked::sum(input.iter().map(|line| {
+        let line_copy = line.clone();
 
-            let iter = &mut line_copy.chars().peekable();
+        let iter = &mut line_copy.chars().peekable();
 
-            eval(iter, true)
-        })
-        .sum()
+        eval(iter, true)
+    }))
 }
 
 fn eval<T: Iterator<Item = char>>(iter: &mut Peekable<T>, plus_higher: bool) -> usize {
@@ -83,7 +103,7 @@ fn eval<T: Iterator<Item = char>>(iter: &mut Peekable<T>, plus_higher: bool) ->
                 };
 
                 value = match op {
-                    '+' => value + num,
+                    '+' => checked::add(value, num),
                     '*' => {
                         if plus_higher {
                             // store value in sums if non zero
@@ -93,7 +113,7 @@ fn eval<T: Iterator<Item = char>>(iter: &mut Peekable<T>, plus_higher: bool) ->
 
                             num
                         } else {
-                            value * num
+                            checked::mul(value, num)
                         }
                     }
                     _ => panic!("magic shit bruv"),
@@ -120,27 +140,23 @@ fn eval<T: Iterator<Item = char>>(iter: &mut Peekable<T>, plus_higher: bool) ->
             sums.push(value);
         }
 
-        sums.iter().product()
+        checked::product(sums)
     } else {
         value
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-    b.iter(|| part1(&input))
-}
-
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
 
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day18.rs"));
 }
@@ -1,69 +1,52 @@
-#![feature(box_syntax, box_patterns, test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use rustc_hash::FxHashMap;
 
 use std::str::Chars;
 
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 type Rules = FxHashMap<usize, Rule>;
 type Cases = Vec<&'static str>;
 type Input = (Rules, Cases);
 
-const INPUT_NUM: i32 = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day19.inp");
 
-fn main() {
-    let input = get_input();
-
-    let part_1 = part1(&input);
-    let part_2 = part2(&input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run(INPUT, parse_input, part1, part2)
 }
 
-fn get_input() -> Input {
-    let input = match INPUT_NUM {
-        0 => include_str!("../inputs/day19.inp"),
-        1 => include_str!("../test_inputs/day19.inp1"),
-        2 => include_str!("../test_inputs/day19.inp2"),
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
-    };
-
-    let mut groups = input.split("\n\n");
-    let rules_str = groups.next().unwrap();
-    let tests_str = groups.next().unwrap();
+pub(crate) fn parse_input(in_str: &'static str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    let mut groups = in_str.split("\n\n");
+    let rules_str = groups.next().unwrap_or(src.end());
+    let tests_str = groups.next().unwrap_or(src.end());
 
     let rules: Rules = rules_str
         .lines()
         .map(|line| {
-            let colon_pos = line.find(':').unwrap();
-            let num_str = line.get(..colon_pos).unwrap();
-            let rule_no = num_str.parse::<usize>().unwrap();
+            let (num_str, rulestr) = src.split_once(line, ": ")?;
+            let rule_no = src.number(num_str)?;
+            let rule: Rule = src.parse(rulestr)?;
 
-            let space_pos = line.find(' ').unwrap();
-            let rulestr = line.get(space_pos + 1..).unwrap();
-            let rule: Rule = rulestr.parse().unwrap();
-
-            (rule_no, rule)
+            Ok((rule_no, rule))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let tests: Cases = tests_str.lines().collect();
 
-    (rules, tests)
+    Ok((rules, tests))
 }
 
-fn part1(input: &Input) -> usize {
+pub(crate) fn part1(input: &Input) -> usize {
     let (rules, cases) = input;
 
     cases
         .iter()
         .filter(|case| {
             let mut char_iter = case.chars();
-            let rule_matches = rules[&0].matches(&rules, &mut char_iter);
+            let rule_matches = rules[&0].matches(rules, &mut char_iter);
             let is_full_match = char_iter.next().is_none();
 
             rule_matches && is_full_match
@@ -71,7 +54,7 @@ fn part1(input: &Input) -> usize {
         .count()
 }
 
-fn part2(input: &Input) -> usize {
+pub(crate) fn part2(input: &Input) -> usize {
     let (rules, cases) = input;
 
     cases
@@ -84,7 +67,7 @@ fn part2(input: &Input) -> usize {
             loop {
                 let save_iter = iter.clone();
 
-                if !rules[&42].matches(&rules, &mut iter) {
+                if !rules[&42].matches(rules, &mut iter) {
                     iter = save_iter;
                     break;
                 } else {
@@ -96,7 +79,7 @@ fn part2(input: &Input) -> usize {
             loop {
                 let save_iter = iter.clone();
 
-                if !rules[&31].matches(&rules, &mut iter) {
+                if !rules[&31].matches(rules, &mut iter) {
                     iter = save_iter;
                     break;
                 } else {
@@ -110,36 +93,48 @@ fn part2(input: &Input) -> usize {
 }
 
 #[derive(Debug)]
-enum Rule {
+pub(crate) enum Rule {
     Sequence(Vec<usize>),
     Alternation(Box<(Rule, Rule)>),
     Atom(char),
 }
 
 impl FromStr for Rule {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(if s.starts_with('"') {
-            Rule::Atom(s.chars().nth(1).unwrap())
+        let src = Source::new(s);
+        Ok(if let Some(quoted) = s.strip_prefix('"') {
+            let atom = src.strip_suffix(quoted, "\"")?;
+            let mut chars = atom.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Rule::Atom(c),
+                _ => return Err(src.error(atom, "a single character")),
+            }
         } else if s.contains('|') {
-            let split: Vec<&str> = s.split(" | ").collect();
+            let (first, second) = src.split_once(s, " | ")?;
 
-            Rule::Alternation(box (split[0].parse()?, split[1].parse()?))
+            Rule::Alternation(Box::new((src.parse(first)?, src.parse(second)?)))
         } else {
-            Rule::Sequence(s.split_whitespace().map(|i| i.parse().unwrap()).collect())
+            Rule::Sequence(
+                s.split(' ')
+                    .map(|i| src.number(i))
+                    .collect::<Result<_, _>>()?,
+            )
         })
     }
 }
 
 impl Rule {
-    fn matches(&self, rules: &Rules, mut iter: &mut Chars) -> bool {
+    fn matches(&self, rules: &Rules, iter: &mut Chars) -> bool {
         match self {
             Rule::Atom(c) => match iter.next() {
                 Some(chr) => chr == *c,
                 None => false,
             },
-            Rule::Alternation(box (rule_1, rule_2)) => {
+            Rule::Alternation(alternatives) => {
+                let (rule_1, rule_2) = &**alternatives;
+
                 // first clone the iterator so we can try the second alternative from the current
                 // position - this is a shallow clone so it is cheap :)
                 let mut cloned_iter = iter.clone();
@@ -148,32 +143,27 @@ impl Rule {
                     *iter = cloned_iter;
                     true
                 } else {
-                    rule_2.matches(rules, &mut iter)
+                    rule_2.matches(rules, iter)
                 }
             }
             Rule::Sequence(rule_seq) => rule_seq
                 .iter()
-                .map(|rule_no| rules[rule_no].matches(rules, &mut iter))
-                .all(|b| b),
+                .all(|rule_no| rules[rule_no].matches(rules, iter)),
         }
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-    b.iter(|| part1(&input))
-}
-
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
 
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day19.rs"));
 }
@@ -1,553 +1,256 @@
-#![feature(test)]
-#![allow(dead_code, unused_variables)]
-extern crate test;
+use aoc_utils::grid::Grid;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness};
 
-use aoc::bit_ops::BitOps;
-
-use anyhow::Result;
-use std::str::FromStr;
-
-use std::collections::hash_map::Entry;
 use fnv::FnvHashMap;
 
-const INPUT_NUM: i32 = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day20.inp");
 
-fn main() -> Result<()> {
-    let input = get_input()?;
+/// Tiles are this many pixels across, and their edges fit in a `u16`.
+const TILE_SIZE: usize = 10;
 
-    let (part_1, part_2) = solve(&input)?;
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |tiles| part1(tiles),
+        |tiles| part2(tiles),
+    )
 }
 
-fn get_input() -> Result<Tiles> {
-    match INPUT_NUM {
-        0 => include_str!("../inputs/day20.inp"),
-        1 => include_str!("../test_inputs/day20.inp1"),
-        _ => panic!("Unknown input number: {:?}", INPUT_NUM),
-    }
-    .parse()
-}
-
-fn solve(tiles: &Tiles) -> Result<(usize, usize)> {
-    // map from edge to list of tile ids
-    let mut edge_map: FnvHashMap<usize, Vec<usize>> = Default::default();
-
-    // build the edge map from each edge to the list of IDs which can produce it
-    for tile in tiles.inner.iter() {
-        for side in [Side::North, Side::East, Side::South, Side::West] {
-            let edge = tile.get_edge(side);
+pub(crate) fn parse_input(in_str: &str) -> Result<Vec<Tile>, ParseError> {
+    let src = Source::new(in_str);
+    let tiles: Vec<Tile> = in_str
+        .trim_end()
+        .split("\n\n")
+        .map(|tile| Tile::parse(&src, tile))
+        .collect::<Result<_, _>>()?;
 
-            edge_map.entry(edge).or_default().push(tile.id);
-
-            edge_map
-                .entry(rev_n_bits(edge, 10))
-                .or_default()
-                .push(tile.id);
-        }
+    if side_length(tiles.len()).is_none() {
+        return Err(src.error(src.end(), "a square number of tiles"));
     }
+    Ok(tiles)
+}
 
-    // build a map from tile.id to the edges that they match
-    let side_map: FnvHashMap<usize, Vec<(Side, usize)>> = tiles
-        .inner
-        .iter()
-        .map(|tile| {
-            let matching_sides: Vec<(Side, usize)> =
-                [Side::North, Side::East, Side::South, Side::West]
-                    .into_iter()
-                    .map(|side| (side, tile.get_edge(side)))
-                    .filter(|(_, edge)| edge_map[edge].len() > 1)
-                    .collect();
-
-            (tile.id, matching_sides)
-        })
-        .collect();
-
-    let corner_ids: Vec<usize> = side_map
-        .iter()
-        .filter(|(_, matching)| matching.len() == 2)
-        .map(|(id, _)| *id)
-        .collect();
-
-    let part_1 = corner_ids.iter().product();
-
-    // make the first corner the top left one - edges going South and East
-    let top_left = corner_ids[1];
-    let (s_1, edge_1) = side_map[&top_left][0];
-    let (s_2, edge_2) = side_map[&top_left][1];
-
-    // current rotation
-    let rot = match s_1.rotations_to(&s_2) {
-        1 => s_1.rotations_to(&Side::East),
-        3 => s_2.rotations_to(&Side::East),
-        n => unreachable!("{} rotations should not be possible for a corner.", n),
-    };
-
-    // map from tile id to rotation / flip
-    let mut state_map: FnvHashMap<usize, (usize, Flip)> = Default::default();
-    state_map.insert(top_left, (rot, Flip::NoFlip));
-
-    let side_length = (tiles.inner.len() as f64).sqrt() as usize;
-
-    // build a mapping from (row, col), to the tile id at that position
-    let mut position_map: FnvHashMap<(usize, usize), usize> = Default::default();
-    position_map.insert((0, 0), top_left);
-
-    for row in 0..side_length - 1 {
-        for col in 0..side_length {
-            let id = *position_map.get(&(row, col)).unwrap();
-            let tile = tiles.get_by_id(&id).unwrap();
-            let (rot, flip) = *state_map.get(&id).unwrap();
-
-            // find east edge connection
-            let east_edge = tile.get_edge(Side::East.rotate_neg(rot));
-            let east_neighbour = edge_map[&east_edge]
-                .iter()
-                .find(|tile_id| **tile_id != id)
-                .and_then(|id| tiles.get_by_id(id));
-
-            // find south edge connection
-            let south_edge = tile.get_edge(Side::South.rotate_neg(rot));
-            let south_neighbour = edge_map[&south_edge]
-                .iter()
-                .find(|tile_id| **tile_id != id)
-                .and_then(|id| tiles.get_by_id(id));
-
-            println!("{:?}", (row, col, id, east_neighbour.map(|t|t.id), south_neighbour.map(|t|t.id)));
-
-            // if there is an east neighbour, put its info in the maps
-            if let Some(en) = east_neighbour {
-                position_map.insert((row, col + 1), en.id);
-
-                let (side, flipped) = en.find_connection_state(east_edge).unwrap();
-                let rotations = side.rotations_to(&Side::West);
-                let flip_type = if flipped ^ (rotations >= 2) { Flip::Horizontal } else { Flip::NoFlip };
-
-                if en.id == 2473 {
-                    println!("{:010b}", tile.get_edge(Side::East.rotate_neg(rot)));
-                    println!("{:010b}", en.get_edge(side));
-                    println!("{:010b}", east_edge);
-                    println!("{:010b}", rev_n_bits(east_edge, 10));
-                    dbg!(side, flip, flipped);
-                    dbg!(rotations);
-                    dbg!(flip_type);
-                }
-
-                state_map.insert(en.id, (rotations, flip_type));
-            }
+#[derive(Debug)]
+pub(crate) struct Tile {
+    id: u64,
+    image: Grid<bool>,
+}
 
-            // if there is a south neighbour, put its info in the maps
-            if let Some(sn) = south_neighbour {
-                position_map.insert((row + 1, col), sn.id);
-
-                let (side, flipped) = sn.find_connection_state(south_edge).unwrap();
-                let rotations = side.rotations_to(&Side::North);
-                let flip_type = if flipped ^ (rotations >= 2) { Flip::Vertical } else { Flip::NoFlip };
-
-                if sn.id == 2473 {
-                    println!("{:010b}", tile.get_edge(Side::South.rotate_neg(rot)));
-                    println!("{:010b}", sn.get_edge(side));
-                    println!("{:010b}", south_edge);
-                    println!("{:010b}", rev_n_bits(south_edge, 10));
-                    dbg!(side, flip, flipped);
-                    dbg!(rotations);
-                    dbg!(flip_type);
-                }
-                
-                state_map.insert(sn.id, (rotations, flip_type));
-            }
+impl Tile {
+    fn parse(src: &Source, s: &str) -> Result<Self, ParseError> {
+        let (header, image) = src.split_once(s, "\n")?;
+        let id = src.number(src.strip_suffix(src.strip_prefix(header, "Tile ")?, ":")?)?;
+        let image = Grid::parse_within(src, image, "\".\" or \"#\"", pixel)?;
+        if (image.width(), image.height()) != (TILE_SIZE, TILE_SIZE) {
+            return Err(src.error(s, "10 rows of 10 pixels"));
         }
-    }
 
-    //*state_map.get_mut(&2473).unwrap() = (2, Flip::Vertical);
-    //*state_map.get_mut(&1171).unwrap() = (3, Flip::NoFlip);
-
-    dbg!(&position_map);
-    dbg!(&state_map);
-
-    // build the final search grids
-    let mut rows: Vec<u128> = std::iter::repeat(0_u128).take(side_length * 8).collect();
-    let mut cols = rows.clone();
-    let mut monster_bits = rows.clone();
-
-    for row in 0..side_length {
-        for col in 0..side_length {
-            let id = position_map[&(row, col)];
-            let (rot, flip) = state_map[&id];
-
-            let to_change: Vec<(&mut u128, usize)> = rows.iter_mut().skip(row * 8).zip(tiles.get_by_id(&id).unwrap().iter_rows(rot)).collect();
-
-            match flip {
-                Flip::NoFlip => {
-                    for (row, cell_row) in to_change {
-                        *row <<= 8;
-                        *row |= cell_row as u128;
-                    }
-                }
-                Flip::Horizontal => {
-                    for (row, cell_row) in to_change.into_iter().rev() {
-                        *row <<= 8;
-                        *row |= cell_row as u128;
-                    }
-                }
-                Flip::Vertical => {
-                    for (row, cell_row) in to_change {
-                        *row <<= 8;
-                        *row |= rev_n_bits(cell_row, 8) as u128;
-                    }
-                }
-            }
-        }
-    }
-
-    for (i, col) in cols.iter_mut().enumerate() {
-        for bit in 0..side_length * 8 {
-            if rows[bit].test_bit(i as u32) {
-                col.set_bit(bit as u32);
-            }
-        }
+        Ok(Self { id, image })
     }
+}
 
-    // hardcode the monster variations
-    let monster          = 0b000000000000000000101000011000011000011101001001001001001000_u128;
-    let monster_rev      = 0b010000000000000000001110000110000110000100010010010010010010_u128;
-    let monster_flip     = 0b010010010010010010001000011000011000011100000000000000000010_u128;
-    let monster_flip_rev = 0b000100100100100100101110000110000110000101000000000000000000_u128;
-
-    let monsters = [
-        monster,
-        monster_rev,
-        monster_flip,
-        monster_flip_rev,
-    ];
-
-    for (j, &r) in rows.iter().enumerate() {
-        for i in (0..8 * side_length).rev() {
-            print!("{}", if r.test_bit(i as u32) { "#" } else { "." });
-            if i % 8 == 0 && i != 0 {
-                //print!("|");
-            }
-        }
-        println!("");
-        if j % 8 == 7 { //&& j < 21 {
-            //println!("--------------------------");
-        }
+fn pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
     }
+}
 
-    /*
-    println!();
-
-    for (j, &r) in cols.iter().enumerate() {
-        for i in (0..8 * side_length).rev() {
-            print!("{}", if r.test_bit(i as u32) { "#" } else { "." });
-            if i % 8 == 0 && i != 0 {
-                //print!("|");
-            }
-        }
-        println!("");
-        if j % 8 == 7 { //&& j < 21 {
-            //println!("--------------------------");
-        }
-    }
-    */
-
-    // perform the search
-    let mut count = 0;
-
-    for grid in [&rows, &cols] {
-        for w in grid.windows(3) {
-            let a = w[0];
-            let b = w[1];
-            let c = w[2];
-
-            for offset in 0..8 * side_length - 20 {
-                let pot_monster = ((a >> offset) & ((1 << 20) - 1)) << 40
-                                | ((b >> offset) & ((1 << 20) - 1)) << 20
-                                | ((c >> offset) & ((1 << 20) - 1));
-
-                if monsters.iter().find(|m| (pot_monster & **m) == **m).is_some() {
-                    count += 1;
-                    //println!("{:060b}", pot_monster);
-                    //println!("{:020b}", a & ((1 << 20) - 1));
-                    //println!("{:020b}", b & ((1 << 20) - 1));
-                    //println!("{:020b}", c & ((1 << 20) - 1));
-                }
-            }
-        }
-    }
+/// How many tiles there are along each side of the square image, if they make a square.
+fn side_length(tiles: usize) -> Option<usize> {
+    let side = (tiles as f64).sqrt().round() as usize;
+    (side * side == tiles).then_some(side)
+}
 
-    println!("{}", count);
-    let monster_bits: u32 = count * monster.count_ones();
-    let all_bits: u32 = rows.into_iter().map(u128::count_ones).sum();
+/// An edge's pixels as bits, the first pixel highest.
+fn read_edge<'a>(pixels: impl Iterator<Item = &'a bool>) -> u16 {
+    pixels.fold(0, |edge, &pixel| edge << 1 | u16::from(pixel))
+}
 
-    Ok((part_1, (all_bits - monster_bits) as usize))
+/// The same edge whichever way round it's read, so that it matches a flipped neighbour.
+fn either_way(edge: u16) -> u16 {
+    edge.min(edge.reverse_bits() >> (16 - TILE_SIZE))
 }
 
-// pre-compute west and east edges
-#[derive(Debug)]
-struct Tile {
-    id: usize,
-    data: Vec<usize>,
-    // precompute edges which would otherwise be expensive
-    west_edge: usize, // collected bit 0s from each row
-    east_edge: usize, // collected bit 9s from each row
+/// The edges of a tile turned one way, each read left to right or top to bottom.
+#[derive(Debug, Clone, Copy)]
+struct Edges {
+    top: u16,
+    bottom: u16,
+    left: u16,
+    right: u16,
 }
 
-impl FromStr for Tile {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut line_iter = s.lines();
-
-        let id: usize = line_iter
-            .next()
-            .and_then(|line| line.get(5..9))
-            .unwrap()
-            .parse()?;
-
-        let mut east_edge = 0;
-        let mut west_edge = 0;
-
-        // lowest bit is left hand side
-        let data: Vec<usize> = line_iter
-            .enumerate()
-            .map(|(line_no, line)| {
-                line.chars()
-                    .enumerate()
-                    .filter(|(_, c)| *c == '#')
-                    .fold(0, |mut acc, (n, _)| {
-                        if n == 0 {
-                            west_edge.set_bit(line_no as u32);
-                        }
-                        if n == 9 {
-                            east_edge.set_bit(line_no as u32);
-                        }
-                        acc.set_bit(n as u32);
-                        acc
-                    })
-            })
-            .collect();
+impl Edges {
+    fn of(image: &Grid<bool>) -> Self {
+        Self {
+            top: read_edge(image.row(0).iter()),
+            bottom: read_edge(image.row(image.height() - 1).iter()),
+            left: read_edge(image.column(0)),
+            right: read_edge(image.column(image.width() - 1)),
+        }
+    }
 
-        Ok(Self {
-            id,
-            data,
-            east_edge,
-            west_edge,
-        })
+    fn all(self) -> [u16; 4] {
+        [self.top, self.bottom, self.left, self.right]
     }
 }
 
-impl Tile {
-    fn get_edge(&self, side: Side) -> usize {
-        match side {
-            Side::North => self.data[0],
-            Side::South => self.data[9],
-            Side::East => self.east_edge,
-            Side::West => self.west_edge,
+/// How many tiles have each edge, read [`either_way`].
+fn edge_counts(tiles: &[Tile]) -> FnvHashMap<u16, usize> {
+    let mut counts = FnvHashMap::default();
+    for tile in tiles {
+        for edge in Edges::of(&tile.image).all() {
+            *counts.entry(either_way(edge)).or_default() += 1;
         }
     }
+    counts
+}
 
-    fn get_neighbour(
-        &self,
-        tiles: &Tiles,
-        side: &Side,
-        side_map: &FnvHashMap<usize, Vec<(Side, usize)>>,
-    ) -> Option<usize> {
-        side_map[&self.id]
+pub(crate) fn part1(tiles: &[Tile]) -> u64 {
+    let counts = edge_counts(tiles);
+    // a corner has two edges which no other tile shares
+    checked::product(
+        tiles
             .iter()
-            .find(|(s, _)| s == side)
-            .and_then(|(_, id)| tiles.inner.binary_search_by_key(id, |t| t.id).ok())
-    }
-
-    fn find_connection_state(&self, needle: usize) -> Option<(Side, bool)> {
-        let flipped_needle = rev_n_bits(needle, 10);
-
-        let mut options = vec![];
-        for side in [Side::North, Side::East, Side::South, Side::West] {
-            let edge = self.get_edge(side);
+            .filter(|tile| {
+                let edges = Edges::of(&tile.image).all();
+                edges
+                    .iter()
+                    .filter(|&&edge| counts[&either_way(edge)] == 1)
+                    .count()
+                    == 2
+            })
+            .map(|tile| tile.id),
+    )
+}
 
-            if needle == 0b0101011100 {
-                dbg!(side);
-                println!("edge = {:010b}", edge);
-                println!("needle = {:010b}", needle);
-                println!("flipped_needle = {:010b}", flipped_needle);
-                print!("\n");
-            }
+/// Lay the tiles out in a square so that neighbouring edges match, starting from a corner in the
+/// top left, and join them up without their borders.
+fn assemble(tiles: &[Tile]) -> Grid<bool> {
+    let side = side_length(tiles.len()).expect("the parser only allows a square of tiles");
+    let counts = edge_counts(tiles);
+    let unmatched = |edge: u16| counts[&either_way(edge)] == 1;
 
-            if edge == needle || edge == flipped_needle {
-                options.push(
-                    Some((side, edge == flipped_needle))
-                )
-                //return Some((side, edge == flipped_needle));
-            }
-        }
+    let turned: Vec<Vec<(Grid<bool>, Edges)>> = tiles
+        .iter()
+        .map(|tile| {
+            let orientations = tile.image.orientations().into_iter();
+            orientations.map(|image| {
+                let edges = Edges::of(&image);
+                (image, edges)
+            })
+        })
+        .map(Iterator::collect)
+        .collect();
 
-        if needle == 0b0101011100 {
-            dbg!(&options);
-        }
-        options.first().cloned().flatten()
-        //None
+    let mut used = vec![false; tiles.len()];
+    let mut placed: Vec<(Grid<bool>, Edges)> = Vec::with_capacity(tiles.len());
+    for at in 0..tiles.len() {
+        // match the tile to the left and the one above, or the outside of the image
+        let fits = |edges: &Edges| {
+            let left_fits = match at % side {
+                0 => unmatched(edges.left),
+                _ => placed[at - 1].1.right == edges.left,
+            };
+            let top_fits = match at / side {
+                0 => unmatched(edges.top),
+                _ => placed[at - side].1.bottom == edges.top,
+            };
+            left_fits && top_fits
+        };
+
+        let (i, orientation) = (0..tiles.len())
+            .filter(|&i| !used[i])
+            .find_map(|i| {
+                let orientation = turned[i].iter().position(|(_, edges)| fits(edges))?;
+                Some((i, orientation))
+            })
+            .expect("the tiles don't fit together");
+        used[i] = true;
+        placed.push(turned[i][orientation].clone());
     }
 
-    fn iter_rows(&self, rot: usize) -> Box<dyn Iterator<Item = usize> + '_> {
-        // individual arms have to be boxed as their types aren't the same
-        match rot % 4 {
-            0 => Box::new(
-                self.data
-                    .iter()
-                    .skip(1)
-                    .take(8)
-                    .map(|row| row >> 1 & 0b1111_1111),
-            ),
-
-            2 => Box::new(
-                self.data
-                    .iter()
-                    .skip(1)
-                    .take(8)
-                    .rev()
-                    .map(|row| rev_n_bits(row >> 1, 8)),
-            ),
-
-            1 => Box::new((1..=8).map(|bit| {
-                self.data
-                    .iter()
-                    .skip(1)
-                    .take(8)
-                    .enumerate()
-                    .fold(0, |mut acc, (i, x)| {
-                        if x.test_bit(bit) {
-                            acc.set_bit(i as u32);
-                        }
-                        acc
-                    })
-            })),
-
-            3 => Box::new((1..=8).rev().map(|bit| {
-                self.data
-                    .iter()
-                    .skip(1)
-                    .take(8)
-                    .enumerate()
-                    .fold(0, |mut acc, (i, x)| {
-                        if x.test_bit(bit) {
-                            acc.set_bit((7 - i) as u32);
-                        }
-                        acc
-                    })
-            })),
-
-            _ => unreachable!(),
+    let inner = TILE_SIZE - 2;
+    let mut pixels = Vec::with_capacity(placed.len() * inner * inner);
+    for tile_row in placed.chunks_exact(side) {
+        for row in 1..=inner {
+            for (image, _) in tile_row {
+                pixels.extend_from_slice(&image.row(row)[1..=inner]);
+            }
         }
     }
+    Grid::from_vec(pixels, side * inner)
 }
 
-#[derive(Debug)]
-struct Tiles {
-    inner: Vec<Tile>,
-}
-
-impl Tiles {
-    fn get_by_id(&self, id: &usize) -> Option<&Tile> {
-        self.inner
-            .binary_search_by_key(id, |t| t.id)
-            .ok()
-            .and_then(|idx| self.inner.get(idx))
-    }
-}
-
-impl FromStr for Tiles {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut tiles: Vec<Tile> = s.split("\n\n").map(str::parse).collect::<Result<_>>()?;
-
-        // sort by id so we can then use
-        // tiles.binary_search_by_key with tile.id
-        tiles.sort_by_key(|tile| tile.id);
-
-        Ok(Self { inner: tiles })
-    }
+/// Where the sea monster's body is, from the top left of the box around it.
+fn monster() -> Vec<(usize, usize)> {
+    MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            let body = line.char_indices().filter(|&(_, c)| c == '#');
+            body.map(move |(col, _)| (row, col))
+        })
+        .collect()
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Side {
-    North,
-    East,
-    South,
-    West,
+/// The positions of any sea monsters in `image`, by the top left of the box around them.
+fn find_monsters(image: &Grid<bool>, monster: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let height = monster.iter().map(|&(row, _)| row + 1).max().unwrap_or(0);
+    let width = monster.iter().map(|&(_, col)| col + 1).max().unwrap_or(0);
+    let rows = (image.height() + 1).saturating_sub(height);
+    let cols = (image.width() + 1).saturating_sub(width);
+    (0..rows)
+        .flat_map(|row| (0..cols).map(move |col| (row, col)))
+        .filter(|&(row, col)| {
+            let mut body = monster.iter();
+            body.all(|&(d_row, d_col)| image[(row + d_row, col + d_col)])
+        })
+        .collect()
 }
 
-impl Side {
-    fn get_sideno(&self) -> usize {
-        match self {
-            Side::North => 0,
-            Side::East => 1,
-            Side::South => 2,
-            Side::West => 3,
-        }
-    }
+pub(crate) fn part2(tiles: &[Tile]) -> usize {
+    let monster = monster();
+    // the monsters only show up with the image turned the right way
+    let (mut rough, found) = assemble(tiles)
+        .orientations()
+        .into_iter()
+        .map(|image| {
+            let found = find_monsters(&image, &monster);
+            (image, found)
+        })
+        .find(|(_, found)| !found.is_empty())
+        .expect("there are no sea monsters whichever way the image is turned");
 
-    fn from_sideno(sideno: usize) -> Self {
-        match sideno {
-            0 => Side::North,
-            1 => Side::East,
-            2 => Side::South,
-            3 => Side::West,
-            _ => panic!("Cannot create Side from sideno: {}.", sideno),
+    for (row, col) in found {
+        for &(d_row, d_col) in &monster {
+            rough[(row + d_row, col + d_col)] = false;
         }
     }
+    rough.cells().iter().filter(|&&pixel| pixel).count()
+}
 
-    fn rotations_to(&self, other: &Self) -> usize {
-        let s1 = self.get_sideno();
-        let s2 = other.get_sideno();
-
-        (s2 - s1) % 4
-    }
-
-    fn rotate_pos(&self, rotations: usize) -> Self {
-        let sideno = self.get_sideno();
-
-        let new_sideno = (sideno + rotations) % 4;
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-        Side::from_sideno(new_sideno)
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
     }
 
-    fn rotate_neg(&self, rotations: usize) -> Self {
-        let sideno = self.get_sideno();
-
-        let new_sideno = (sideno - rotations) % 4;
-
-        Side::from_sideno(new_sideno)
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
     }
-}
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Flip {
-    NoFlip,
-    Vertical,
-    Horizontal,
-}
-
-/// returns the reverse of the first `bits` in `n`
-fn rev_n_bits(n: usize, bits: u32) -> usize {
-    (0..bits).filter(|b| n.test_bit(*b)).fold(0, |mut acc, b| {
-        acc.set_bit(bits - 1 - b);
-        acc
-    })
-}
-
-#[bench]
-fn bench_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-
-    b.iter(|| solve(&input))
-}
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day20.rs"));
 }
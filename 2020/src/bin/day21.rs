@@ -1,33 +1,23 @@
-#![feature(test, try_trait)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use rustc_hash::{FxHashMap, FxHashSet};
 
-const INPUT_NUM: usize = 0;
-
 type Input = Vec<Food>;
 type PartInput = [Food];
 
-fn main() {
-    let input = get_input();
-
-    let (part_1, part_2) = solve(&input);
+pub(crate) const INPUT: &str = include_str!("../inputs/day21.inp");
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run_both(INPUT, parse_input, |input| solve(&input))
 }
 
-fn get_input() -> Input {
-    [
-        include_str!("../inputs/day21.inp"),
-        include_str!("../test_inputs/day21.inp1"),
-    ][INPUT_NUM]
-        .lines()
-        .map(|line| line.parse().unwrap())
-        .collect()
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    let src = Source::new(in_str);
+    in_str.lines().map(|line| src.parse(line)).collect()
 }
 
-fn solve<'a>(input: &'a PartInput) -> (usize, String) {
+pub(crate) fn solve<'a>(input: &'a PartInput) -> (usize, String) {
     // counts of safe ingredients
     let mut safe_ingredients: FxHashMap<&'a str, usize> = Default::default();
 
@@ -43,7 +33,7 @@ fn solve<'a>(input: &'a PartInput) -> (usize, String) {
     for food in input {
         // update the counts of the each ingredient
         for ingredient in food.ingredients.iter() {
-            *safe_ingredients.entry(&ingredient).or_insert(0) += 1;
+            *safe_ingredients.entry(ingredient).or_insert(0) += 1;
         }
 
         // update could for each allergen
@@ -86,7 +76,7 @@ fn solve<'a>(input: &'a PartInput) -> (usize, String) {
         safe_ingredients.remove(ingredient);
     }
 
-    let part_1 = safe_ingredients.iter().map(|(_, n)| n).sum::<usize>();
+    let part_1 = safe_ingredients.values().sum::<usize>();
 
     let mut known_sorted: Vec<(&'a str, &'a str)> = known.into_iter().collect();
     known_sorted.sort_unstable_by_key(|(a, _)| *a);
@@ -106,19 +96,18 @@ fn solve<'a>(input: &'a PartInput) -> (usize, String) {
 }
 
 #[derive(Debug)]
-struct Food {
+pub(crate) struct Food {
     ingredients: Vec<String>,
     allergens: Vec<String>,
 }
 
 impl std::str::FromStr for Food {
-    type Err = std::option::NoneError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let open_brac_pos = s.find('(')?;
-
-        let ingredient_str = s.get(0..open_brac_pos - 1)?;
-        let allergen_str = s.get(open_brac_pos + 10..s.len() - 1)?;
+        let src = Source::new(s);
+        let (ingredient_str, allergen_str) = src.split_once(s, " (contains ")?;
+        let allergen_str = src.strip_suffix(allergen_str, ")")?;
 
         let ingredients = ingredient_str.split(' ').map(String::from).collect();
         let allergens = allergen_str.split(", ").map(String::from).collect();
@@ -130,14 +119,19 @@ impl std::str::FromStr for Food {
     }
 }
 
-#[bench]
-fn bench_solution(b: &mut test::Bencher) {
-    let input = get_input();
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-    b.iter(|| solve(&input))
-}
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(&parse_input(in_str).unwrap());
+        part_1.to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(&parse_input(in_str).unwrap());
+        part_2.to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day21.rs"));
 }
@@ -1,49 +1,38 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use std::cmp::Ordering;
 use std::collections::{HashSet, VecDeque};
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day22.inp");
 
-fn main() {
-    let (player_1, player_2) = get_input();
+fn main() -> anyhow::Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |(player_1, player_2)| part1(player_1, player_2),
+        |(player_1, player_2)| part2(player_1, player_2),
+    )
+}
 
-    let part_1 = part1(&player_1, &player_2);
-    let part_2 = part2(&player_1, &player_2);
+pub(crate) fn parse_input(in_str: &str) -> Result<(Vec<usize>, Vec<usize>), ParseError> {
+    let src = Source::new(in_str);
+    let (plr1, plr2) = src.split_once(in_str, "\n\n")?;
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+    Ok((
+        parse_deck(&src, plr1, "Player 1:")?,
+        parse_deck(&src, plr2, "Player 2:")?,
+    ))
 }
 
-fn get_input() -> (Vec<usize>, Vec<usize>) {
-    let (plr1, plr2) = [
-        include_str!("../inputs/day22.inp"),
-        include_str!("../test_inputs/day22.inp1"),
-        include_str!("../test_inputs/day22.inp2"),
-    ][INPUT_NUM]
-        .split_once("\n\n")
-        .expect("Invalid input file.");
-
-    (
-        plr1.lines()
-            .skip(1)
-            .map(|line| {
-                line.parse()
-                    .expect("Found non numeric value in player data.")
-            })
-            .collect(),
-        plr2.lines()
-            .skip(1)
-            .map(|line| {
-                line.parse()
-                    .expect("Found non numeric value in player data.")
-            })
-            .collect(),
-    )
+fn parse_deck(src: &Source, s: &str, header: &str) -> Result<Vec<usize>, ParseError> {
+    let mut lines = s.lines();
+    src.strip_prefix(lines.next().unwrap_or(src.end()), header)?;
+
+    lines.map(|line| src.number(line)).collect()
 }
 
-fn part1(p1_deck: &[usize], p2_deck: &[usize]) -> usize {
+pub(crate) fn part1(p1_deck: &[usize], p2_deck: &[usize]) -> usize {
     let mut game = GameState::new(p1_deck, p2_deck);
 
     while !game.is_over() {
@@ -62,39 +51,58 @@ fn part1(p1_deck: &[usize], p2_deck: &[usize]) -> usize {
         }
     }
 
-    let winner = game.decide_winner();
-
-    winner
-        .iter()
-        .zip((1..=winner.len()).rev())
-        .fold(0, |acc, (a, b)| acc + (a * b))
+    score(game.decide_winner())
 }
 
-fn part2(p1_deck: &[usize], p2_deck: &[usize]) -> usize {
-    let mut game = GameState::new(p1_deck, p2_deck);
+pub(crate) fn part2(p1_deck: &[usize], p2_deck: &[usize]) -> usize {
+    let (_, winner) = recursive_combat(GameState::new(p1_deck, p2_deck));
 
+    score(&winner)
+}
+
+/// Play a game of recursive combat, returning whether player 1 won, and the winner's deck.
+fn recursive_combat(mut game: GameState) -> (bool, VecDeque<usize>) {
     let mut prev_states: HashSet<GameState> = Default::default();
-    let mut game_stack: Vec<GameState> = Default::default();
 
-    let winner = loop {
-        if prev_states.contains(&game) {
-            break &game.player_1;
+    loop {
+        // a repeated round would repeat forever, so player 1 wins instead
+        if !prev_states.insert(game.clone()) {
+            return (true, game.player_1);
         }
 
-        match game.draw_cards() {
-            (Some(p1), Some(p2)) => {
-                let (p1_cards, p2_cards) = game.num_cards();
-            }
-            (Some(_), None) => (), // player 2 win
-            (None, Some(_)) => (), // player 2 win
-            (None, None) => unreachable!("Both players cannot run out of cards."),
+        let (p1, p2) = game.draw_cards_unchecked();
+        let p1_wins = if game.player_1.len() >= p1 && game.player_2.len() >= p2 {
+            let p1_sub_deck: Vec<usize> = game.player_1.iter().take(p1).copied().collect();
+            let p2_sub_deck: Vec<usize> = game.player_2.iter().take(p2).copied().collect();
+            recursive_combat(GameState::new(&p1_sub_deck, &p2_sub_deck)).0
+        } else {
+            p1 > p2
+        };
+
+        if p1_wins {
+            game.player_1.push_back(p1);
+            game.player_1.push_back(p2);
+        } else {
+            game.player_2.push_back(p2);
+            game.player_2.push_back(p1);
         }
-    };
 
-    0
+        if game.player_2.is_empty() {
+            return (true, game.player_1);
+        }
+        if game.player_1.is_empty() {
+            return (false, game.player_2);
+        }
+    }
 }
 
-#[derive(Debug, Hash, Eq, PartialEq)]
+fn score(deck: &VecDeque<usize>) -> usize {
+    deck.iter()
+        .zip((1..=deck.len()).rev())
+        .fold(0, |acc, (a, b)| acc + (a * b))
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
 struct GameState {
     player_1: VecDeque<usize>,
     player_2: VecDeque<usize>,
@@ -112,10 +120,6 @@ impl GameState {
         self.player_1.is_empty() || self.player_2.is_empty()
     }
 
-    fn draw_cards(&mut self) -> (Option<usize>, Option<usize>) {
-        (self.player_1.pop_front(), self.player_2.pop_front())
-    }
-
     fn draw_cards_unchecked(&mut self) -> (usize, usize) {
         (
             self.player_1.pop_front().unwrap(),
@@ -123,10 +127,6 @@ impl GameState {
         )
     }
 
-    fn num_cards(&mut self) -> (usize, usize) {
-        (self.player_1.len(), self.player_2.len())
-    }
-
     fn decide_winner(&self) -> &VecDeque<usize> {
         if self.player_1.is_empty() {
             &self.player_2
@@ -136,21 +136,19 @@ impl GameState {
     }
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input();
-
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input();
+    fn solve_part1(in_str: &str) -> String {
+        let (player_1, player_2) = parse_input(in_str).unwrap();
+        part1(&player_1, &player_2).to_string()
+    }
 
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        let (player_1, player_2) = parse_input(in_str).unwrap();
+        part2(&player_1, &player_2).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day22.rs"));
 }
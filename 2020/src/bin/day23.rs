@@ -1,6 +1,3 @@
-#![feature(test)]
-extern crate test;
-
 fn main() {
     let input: Vec<usize> = "389125467"
         // "219748365"
@@ -15,7 +12,7 @@ fn main() {
     println!("Part 1: {:?}", ans1);
 }
 
-fn part1(input: &[usize]) -> u64 {
+pub(crate) fn part1(input: &[usize]) -> u64 {
     let board = game(input, 10, 100);
 
     let mut i = 1;
@@ -48,11 +45,7 @@ fn print_ll(ll: &[usize], num: usize) {
 
 fn populate_holding(cups: &[usize], holding: &mut [usize; 3], curr: usize) {
     for i in 0..3 {
-        let mut c = if i == 0 {
-            curr
-        } else {
-            holding[i - 1]
-        };
+        let c = if i == 0 { curr } else { holding[i - 1] };
 
         if cups[c] == 0 {
             holding[i] = cups[0];
@@ -63,7 +56,7 @@ fn populate_holding(cups: &[usize], holding: &mut [usize; 3], curr: usize) {
 }
 
 fn game(input: &[usize], size: usize, iterations: u32) -> Vec<usize> {
-    let mut cups: Vec<usize> = (1..=dbg!(size.max(dbg!(input.len() + 1)))).collect();
+    let mut cups: Vec<usize> = (1..=size.max(input.len() + 1)).collect();
 
     for (i, j) in std::iter::once(&0)
         .chain(input.iter())
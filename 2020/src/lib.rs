@@ -1,2 +0,0 @@
-mod utils;
-pub use utils::*;
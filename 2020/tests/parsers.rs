@@ -0,0 +1,70 @@
+//! Every day's parser is given junk, and has to turn it down with an error rather than panicking.
+//!
+//! The days are binaries, so each one is pulled in as a module. This runs without the test
+//! harness, so that their example tests aren't run again here.
+
+#![allow(dead_code)]
+
+use aoc_utils::{fuzz, input_path};
+
+#[path = "../src/bin/day01.rs"]
+mod day01;
+#[path = "../src/bin/day02.rs"]
+mod day02;
+#[path = "../src/bin/day03.rs"]
+mod day03;
+#[path = "../src/bin/day04.rs"]
+mod day04;
+#[path = "../src/bin/day05.rs"]
+mod day05;
+#[path = "../src/bin/day06.rs"]
+mod day06;
+#[path = "../src/bin/day07.rs"]
+mod day07;
+#[path = "../src/bin/day08.rs"]
+mod day08;
+#[path = "../src/bin/day09.rs"]
+mod day09;
+#[path = "../src/bin/day10.rs"]
+mod day10;
+#[path = "../src/bin/day11.rs"]
+mod day11;
+#[path = "../src/bin/day12.rs"]
+mod day12;
+#[path = "../src/bin/day13.rs"]
+mod day13;
+#[path = "../src/bin/day14.rs"]
+mod day14;
+#[path = "../src/bin/day15.rs"]
+mod day15;
+#[path = "../src/bin/day16.rs"]
+mod day16;
+#[path = "../src/bin/day17.rs"]
+mod day17;
+#[path = "../src/bin/day18.rs"]
+mod day18;
+#[path = "../src/bin/day19.rs"]
+mod day19;
+#[path = "../src/bin/day20.rs"]
+mod day20;
+#[path = "../src/bin/day21.rs"]
+mod day21;
+#[path = "../src/bin/day22.rs"]
+mod day22;
+
+// day 23 is given its cups directly, so has no parser
+
+macro_rules! check_days {
+    ($($day:ident),* $(,)?) => {$(
+        let examples = fuzz::examples(input_path!("src/test_inputs"), stringify!($day));
+        fuzz::check(stringify!($day), &examples, $day::parse_input);
+        println!("{}: ok", stringify!($day));
+    )*};
+}
+
+fn main() {
+    check_days!(
+        day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+        day14, day15, day16, day17, day18, day19, day20, day21, day22,
+    );
+}
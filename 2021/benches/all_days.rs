@@ -0,0 +1,148 @@
+//! Criterion benchmarks for every day, run with `cargo bench`.
+//!
+//! The days are binaries, so each one is pulled in as a module.
+
+// without the harness, a day's unit test module is built but its `#[test]`s aren't, which leaves
+// its imports unused
+#![allow(dead_code, unused_imports)]
+
+use aoc_utils::bench::{bench_day, bench_day_both, bench_parse};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+#[path = "../src/bin/day01.rs"]
+mod day01;
+#[path = "../src/bin/day02.rs"]
+mod day02;
+#[path = "../src/bin/day03.rs"]
+mod day03;
+#[path = "../src/bin/day04.rs"]
+mod day04;
+#[path = "../src/bin/day05.rs"]
+mod day05;
+#[path = "../src/bin/day06.rs"]
+mod day06;
+#[path = "../src/bin/day07.rs"]
+mod day07;
+#[path = "../src/bin/day08.rs"]
+mod day08;
+#[path = "../src/bin/day09.rs"]
+mod day09;
+#[path = "../src/bin/day10.rs"]
+mod day10;
+#[path = "../src/bin/day11.rs"]
+mod day11;
+#[path = "../src/bin/day12.rs"]
+mod day12;
+#[path = "../src/bin/day13.rs"]
+mod day13;
+#[path = "../src/bin/day14.rs"]
+mod day14;
+#[path = "../src/bin/day15.rs"]
+mod day15;
+#[path = "../src/bin/day16.rs"]
+mod day16;
+
+fn bench_day01(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day01",
+        day01::INPUT,
+        day01::parse_input,
+        |input| day01::part1(input),
+        |input| day01::part2(input),
+    );
+}
+
+fn bench_day02(c: &mut Criterion) {
+    let input = bench_parse(c, "day02", day02::INPUT, day02::parse_input);
+    c.bench_function("day02_solve", |b| {
+        b.iter(|| day02::solve(black_box(&input)))
+    });
+}
+
+fn bench_day03(c: &mut Criterion) {
+    bench_day(
+        c,
+        "day03",
+        day03::INPUT,
+        day03::parse_input,
+        |input| day03::part1::<{ day03::WIDTH }>(input),
+        |input| day03::part2::<{ day03::WIDTH }>(input),
+    );
+}
+
+fn bench_day04(c: &mut Criterion) {
+    bench_day_both(c, "day04", day04::INPUT, day04::parse_input, day04::solve);
+}
+
+fn bench_day05(c: &mut Criterion) {
+    bench_day_both(c, "day05", day05::INPUT, day05::parse_input, day05::solve);
+}
+
+fn bench_day06(c: &mut Criterion) {
+    bench_day_both(c, "day06", day06::INPUT, day06::parse_input, day06::solve);
+}
+
+fn bench_day07(c: &mut Criterion) {
+    bench_day_both(c, "day07", day07::INPUT, day07::parse_input, day07::solve);
+}
+
+fn bench_day08(c: &mut Criterion) {
+    bench_day_both(c, "day08", day08::INPUT, day08::parse_input, day08::solve);
+}
+
+fn bench_day09(c: &mut Criterion) {
+    bench_day_both(c, "day09", day09::INPUT, day09::parse_input, day09::solve);
+}
+
+fn bench_day10(c: &mut Criterion) {
+    bench_day_both(c, "day10", day10::INPUT, day10::parse_input, day10::solve);
+}
+
+fn bench_day11(c: &mut Criterion) {
+    bench_day_both(c, "day11", day11::INPUT, day11::parse_input, day11::solve);
+}
+
+fn bench_day12(c: &mut Criterion) {
+    bench_day_both(c, "day12", day12::INPUT, day12::parse_input, day12::solve);
+}
+
+fn bench_day13(c: &mut Criterion) {
+    bench_day_both(c, "day13", day13::INPUT, day13::parse_input, day13::solve);
+}
+
+fn bench_day14(c: &mut Criterion) {
+    bench_day_both(c, "day14", day14::INPUT, day14::parse_input, day14::solve);
+}
+
+fn bench_day15(c: &mut Criterion) {
+    bench_day_both(c, "day15", day15::INPUT, day15::parse_input, day15::solve);
+}
+
+fn bench_day16(c: &mut Criterion) {
+    let input = bench_parse(c, "day16", day16::INPUT, day16::parse_input);
+    c.bench_function("day16_solve", |b| {
+        b.iter(|| day16::solve(black_box(&input)))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_day01,
+    bench_day02,
+    bench_day03,
+    bench_day04,
+    bench_day05,
+    bench_day06,
+    bench_day07,
+    bench_day08,
+    bench_day09,
+    bench_day10,
+    bench_day11,
+    bench_day12,
+    bench_day13,
+    bench_day14,
+    bench_day15,
+    bench_day16
+);
+criterion_main!(benches);
@@ -1,61 +1,49 @@
-#![feature(test, array_windows)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use anyhow::Result;
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day01.inp");
 
 fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let part_1 = part1(input.as_slice());
-    let part_2 = part2(input.as_slice());
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1(input),
+        |input| part2(input),
+    )
 }
 
-fn get_input() -> Result<Vec<u32>, std::num::ParseIntError> {
-    [
-        include_str!("../inputs/day01.inp"),
-        include_str!("../test_inputs/day01.inp1"),
-    ][INPUT_NUM]
-        .lines()
-        .map(str::parse)
-        .collect()
+pub(crate) fn parse_input(in_str: &str) -> Result<Vec<u32>, ParseError> {
+    let src = Source::new(in_str);
+    in_str.lines().map(|line| src.number(line)).collect()
 }
 
 fn num_increasing(slice: &[u32]) -> usize {
-    slice.array_windows().filter(|[a, b]| a < b).count()
+    slice.windows(2).filter(|w| w[0] < w[1]).count()
 }
 
-fn part1(inp: &[u32]) -> usize {
+pub(crate) fn part1(inp: &[u32]) -> usize {
     num_increasing(inp)
 }
 
-fn part2(inp: &[u32]) -> usize {
+pub(crate) fn part2(inp: &[u32]) -> usize {
     let slice_sums: Vec<u32> = inp.windows(3).map(|w| w.iter().sum()).collect();
 
     num_increasing(slice_sums.as_slice())
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-    b.iter(|| part1(&input))
-}
-
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
+    fn solve_part1(in_str: &str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
 
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input().unwrap());
+    include!(concat!(env!("OUT_DIR"), "/examples/day01.rs"));
 }
@@ -1,32 +1,20 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use anyhow::Result;
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day02.inp");
 
 fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let (part_1, part_2) = solve(input.as_slice());
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+    harness::run_both(INPUT, parse_input, |input| solve(&input))
 }
 
-fn get_input() -> Result<Vec<Command>> {
-    [
-        include_str!("../inputs/day02.inp"),
-        include_str!("../test_inputs/day02.inp1"),
-    ][INPUT_NUM]
-        .lines()
-        .map(str::parse)
-        .collect()
+pub(crate) fn parse_input(in_str: &str) -> Result<Vec<Command>, ParseError> {
+    let src = Source::new(in_str);
+    in_str.lines().map(|line| src.parse(line)).collect()
 }
 
-fn solve(inp: &[Command]) -> (u32, u32) {
+pub(crate) fn solve(inp: &[Command]) -> (u32, u32) {
     let mut horiz: u32 = 0;
     let mut aim: u32 = 0;
     let mut depth: u32 = 0;
@@ -50,38 +38,44 @@ fn solve(inp: &[Command]) -> (u32, u32) {
 }
 
 #[derive(Debug, Copy, Clone)]
-enum Command {
+pub(crate) enum Command {
     Forward(u32),
     Down(u32),
     Up(u32),
 }
 
 impl std::str::FromStr for Command {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         use Command::*;
 
-        let (cmd, d) = s.split_once(' ').unwrap();
-        let dist: u32 = d.parse()?;
-        let c = cmd.chars().next().unwrap();
+        let src = Source::new(s);
+        let (cmd, d) = src.split_once(s, " ")?;
+        let dist: u32 = src.number(d)?;
 
-        Ok(match c {
-            'f' => Forward(dist),
-            'd' => Down(dist),
-            'u' => Up(dist),
-            _ => unreachable!("Only 'f','d' and 'u' are valid first letters of commands."),
+        Ok(match cmd {
+            "forward" => Forward(dist),
+            "down" => Down(dist),
+            "up" => Up(dist),
+            _ => return Err(src.error(cmd, "forward, down or up")),
         })
     }
 }
 
-#[bench]
-fn bench_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| solve(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(&parse_input(in_str).unwrap());
+        part_1.to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(&parse_input(in_str).unwrap());
+        part_2.to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input().unwrap());
+    include!(concat!(env!("OUT_DIR"), "/examples/day02.rs"));
 }
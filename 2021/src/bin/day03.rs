@@ -1,35 +1,31 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::bit_ops::BitOps;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use anyhow::Result;
-use aoc::bit_ops::BitOps;
 
-const INPUT_NUM: usize = 0;
-const WIDTH: usize = [12, 5][INPUT_NUM];
+pub(crate) const WIDTH: usize = 12;
 
-fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let part_1 = part1(input.as_slice());
-    let part_2 = part2(input.as_slice());
+pub(crate) const INPUT: &str = include_str!("../inputs/day03.inp");
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+fn main() -> Result<()> {
+    harness::run(
+        INPUT,
+        parse_input,
+        |input| part1::<WIDTH>(input),
+        |input| part2::<WIDTH>(input),
+    )
 }
 
-fn get_input() -> Result<Vec<u32>, std::num::ParseIntError> {
-    [
-        include_str!("../inputs/day03.inp"),
-        include_str!("../test_inputs/day03.inp1"),
-    ][INPUT_NUM]
+pub(crate) fn parse_input(in_str: &str) -> Result<Vec<u32>, ParseError> {
+    let src = Source::new(in_str);
+    in_str
         .lines()
-        .map(|line| u32::from_str_radix(line, 2))
+        .map(|line| u32::from_str_radix(line, 2).map_err(|_| src.error(line, "a binary number")))
         .collect()
 }
 
-fn part1(inp: &[u32]) -> u32 {
+pub(crate) fn part1<const WIDTH: usize>(inp: &[u32]) -> u32 {
     let mut counts = [0_u32; WIDTH];
 
     for x in inp.iter() {
@@ -55,7 +51,7 @@ fn part1(inp: &[u32]) -> u32 {
     gamma_rate * (!gamma_rate & ((1 << WIDTH) - 1))
 }
 
-fn rating(inp: &[u32], invert: bool) -> u32 {
+fn rating<const WIDTH: usize>(inp: &[u32], invert: bool) -> u32 {
     let mut remaining = inp.to_vec();
 
     for bit in (0..WIDTH as u32).rev() {
@@ -80,24 +76,21 @@ fn rating(inp: &[u32], invert: bool) -> u32 {
     remaining[0]
 }
 
-fn part2(inp: &[u32]) -> u32 {
-    rating(inp, false) * rating(inp, true)
+pub(crate) fn part2<const WIDTH: usize>(inp: &[u32]) -> u32 {
+    rating::<WIDTH>(inp, false) * rating::<WIDTH>(inp, true)
 }
 
-#[bench]
-fn bench_part1_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| part1(&input))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-#[bench]
-fn bench_part2_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
+    fn solve_part1(in_str: &str) -> String {
+        part1::<5>(&parse_input(in_str).unwrap()).to_string()
+    }
 
-    b.iter(|| part2(&input))
-}
+    fn solve_part2(in_str: &str) -> String {
+        part2::<5>(&parse_input(in_str).unwrap()).to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input().unwrap());
+    include!(concat!(env!("OUT_DIR"), "/examples/day03.rs"));
 }
@@ -1,44 +1,36 @@
-#![feature(test, array_chunks)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
-use anyhow::{anyhow, Result};
-
-const INPUT_NUM: usize = 0;
+use anyhow::Result;
 
 #[derive(Debug, Clone)]
-struct Bingo(Vec<u8>, Vec<Board>);
-
-fn main() -> Result<()> {
-    let input = get_input()?;
+pub(crate) struct Bingo(Vec<u8>, Vec<Board>);
 
-    let (part_1, part_2) = solve(input);
+pub(crate) const INPUT: &str = include_str!("../inputs/day04.inp");
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+fn main() -> Result<()> {
+    harness::run_both(INPUT, parse_input, solve)
 }
 
-fn get_input() -> Result<Bingo> {
-    let mut blocks = [
-        include_str!("../inputs/day04.inp"),
-        include_str!("../test_inputs/day04.inp1"),
-    ][INPUT_NUM]
-        .split("\n\n");
+pub(crate) fn parse_input(in_str: &str) -> Result<Bingo, ParseError> {
+    let src = Source::new(in_str);
+    let mut blocks = in_str.split("\n\n");
 
     let numbers = blocks
         .next()
-        .unwrap()
+        .unwrap_or(src.end())
         .split(',')
-        .map(str::parse)
+        .map(|num| src.number(num))
         .collect::<Result<_, _>>()?;
 
-    let boards = blocks.map(str::parse).collect::<Result<_>>()?;
+    let boards = blocks
+        .map(|board| src.parse(board))
+        .collect::<Result<_, _>>()?;
 
     Ok(Bingo(numbers, boards))
 }
 
-fn solve(inp: Bingo) -> (u32, u32) {
+pub(crate) fn solve(inp: Bingo) -> (u32, u32) {
     let Bingo(numbers, mut boards) = inp;
 
     let mut part_1 = None;
@@ -79,10 +71,7 @@ impl Board {
     }
 
     fn is_winning(&self) -> bool {
-        let winning_row = self
-            .numbers
-            .array_chunks::<5>()
-            .any(|chunk| *chunk == [0xFF; 5]);
+        let winning_row = self.numbers.chunks_exact(5).any(|chunk| chunk == [0xFF; 5]);
 
         let winning_col =
             (0..5).any(|i| self.numbers.iter().skip(i).step_by(5).all(|n| *n == 0xFF));
@@ -100,32 +89,36 @@ impl Board {
 }
 
 impl std::str::FromStr for Board {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
         let nums: Vec<u8> = s
             .split_ascii_whitespace()
-            .map(str::parse)
+            .map(|num| src.number(num))
             .collect::<Result<_, _>>()?;
 
         Ok(Self {
-            numbers: nums.try_into().map_err(|numbers: Vec<u8>| {
-                anyhow!(
-                    "Failed to parse board, found {} numbers where there should have been 25.",
-                    numbers.len()
-                )
-            })?,
+            numbers: nums
+                .try_into()
+                .map_err(|_| src.error(s, "a board of 25 numbers"))?,
         })
     }
 }
 
-#[bench]
-fn bench_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| solve(input.clone()))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input().unwrap());
+    include!(concat!(env!("OUT_DIR"), "/examples/day04.rs"));
 }
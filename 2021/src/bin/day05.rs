@@ -1,57 +1,67 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::interval::IntervalSet;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness};
 
 use anyhow::Result;
 use std::collections::HashMap;
+use std::ops::Range;
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day05.inp");
 
 fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let (part_1, part_2) = solve(input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+    harness::run_both(INPUT, parse_input, solve)
 }
 
-fn get_input() -> Result<Vec<Range2d>> {
-    [
-        include_str!("../inputs/day05.inp"),
-        include_str!("../test_inputs/day05.inp1"),
-    ][INPUT_NUM]
-        .lines()
-        .map(str::parse)
-        .collect()
+pub(crate) fn parse_input(in_str: &str) -> Result<Vec<Range2d>, ParseError> {
+    let src = Source::new(in_str);
+    in_str.lines().map(|line| src.parse(line)).collect()
 }
 
-fn solve(inp: Vec<Range2d>) -> (usize, usize) {
+pub(crate) fn solve(inp: Vec<Range2d>) -> (usize, usize) {
     let (diag, flat): (Vec<Range2d>, Vec<Range2d>) =
         inp.into_iter().partition(Range2d::is_diagonal);
 
-    let mut counts: HashMap<(u32, u32), u32> = Default::default();
+    let mut rows: HashMap<i64, Row> = Default::default();
 
-    for point in flat.into_iter().flat_map(Range2d::into_iter) {
-        *counts.entry(point).or_insert(0) += 1;
+    for (y, xs) in flat.into_iter().flat_map(Range2d::rows) {
+        rows.entry(y).or_default().cover(xs);
     }
 
-    let part_1 = counts.iter().filter(|(_, c)| **c > 1).count();
+    let part_1 = overlaps(&rows);
 
-    for point in diag.into_iter().flat_map(Range2d::into_iter) {
-        *counts.entry(point).or_insert(0) += 1;
+    for (y, xs) in diag.into_iter().flat_map(Range2d::rows) {
+        rows.entry(y).or_default().cover(xs);
     }
 
-    let part_2 = counts.iter().filter(|(_, c)| **c > 1).count();
+    let part_2 = overlaps(&rows);
 
     (part_1, part_2)
 }
 
+/// The points along a row which at least one line covers, and those which two or more do.
+#[derive(Debug, Default)]
+struct Row {
+    once: IntervalSet,
+    twice: IntervalSet,
+}
+
+impl Row {
+    fn cover(&mut self, xs: Range<i64>) {
+        let xs = IntervalSet::from(xs);
+        self.twice = self.twice.union(&self.once.intersection(&xs));
+        self.once = self.once.union(&xs);
+    }
+}
+
+/// How many points two or more lines cover.
+fn overlaps(rows: &HashMap<i64, Row>) -> usize {
+    checked::cast(checked::sum(rows.values().map(|row| row.twice.len())))
+}
+
 #[derive(Debug, Copy, Clone)]
-struct Range2d {
-    start: (u32, u32),
-    end: (u32, u32),
+pub(crate) struct Range2d {
+    start: (i64, i64),
+    end: (i64, i64),
 }
 
 impl Range2d {
@@ -59,47 +69,63 @@ impl Range2d {
         self.start.0 != self.end.0 && self.start.1 != self.end.1
     }
 
-    fn into_iter(self) -> impl Iterator<Item = (u32, u32)> {
-        use std::cmp::Ordering::*;
+    /// The `x`s the line covers in each row `y` it crosses, as `(y, xs)`.
+    fn rows(self) -> impl Iterator<Item = (i64, Range<i64>)> {
+        let ((x1, y1), (x2, y2)) = (self.start, self.end);
+        let (dx, dy) = ((x2 - x1).signum(), (y2 - y1).signum());
+        let horizontal = dy == 0;
+        let steps = if horizontal { 0 } else { (y2 - y1).abs() };
+
+        (0..=steps).map(move |i| {
+            let (x, y) = (x1 + i * dx, y1 + i * dy);
+            if horizontal {
+                (y, x1.min(x2)..x1.max(x2) + 1)
+            } else {
+                (y, x..x + 1)
+            }
+        })
+    }
+}
 
-        let x_iter: Box<dyn Iterator<Item = u32>> = match self.start.0.cmp(&self.end.0) {
-            Equal => Box::new(std::iter::repeat(self.start.0)),
-            Less => Box::new(self.start.0..=self.end.0),
-            Greater => Box::new((self.end.0..=self.start.0).rev()),
+impl std::str::FromStr for Range2d {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(line);
+        let (s, e) = src.split_once(line, " -> ")?;
+        let (x1, y1) = src.split_once(s, ",")?;
+        let (x2, y2) = src.split_once(e, ",")?;
+
+        let coord = |n| src.number::<u32>(n).map(i64::from);
+        let range = Self {
+            start: (coord(x1)?, coord(y1)?),
+            end: (coord(x2)?, coord(y2)?),
         };
 
-        let y_iter: Box<dyn Iterator<Item = u32>> = match self.start.1.cmp(&self.end.1) {
-            Equal => Box::new(std::iter::repeat(self.start.1)),
-            Less => Box::new(self.start.1..=self.end.1),
-            Greater => Box::new((self.end.1..=self.start.1).rev()),
-        };
+        // rows steps both coordinates together, so a slanted line has to be at 45 degrees
+        let dx = range.start.0.abs_diff(range.end.0);
+        let dy = range.start.1.abs_diff(range.end.1);
+        if (dx != 0 && dy != 0 && dx != dy) || (dx == 0 && dy == 0) {
+            return Err(src.error(line, "a horizontal, vertical or diagonal line"));
+        }
 
-        x_iter.zip(y_iter)
+        Ok(range)
     }
 }
 
-impl std::str::FromStr for Range2d {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (s, e) = s.split_once(" -> ").unwrap();
-        let (x1, y1) = s.split_once(',').unwrap();
-        let (x2, y2) = e.split_once(',').unwrap();
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-        Ok(Self {
-            start: (x1.parse()?, y1.parse()?),
-            end: (x2.parse()?, y2.parse()?),
-        })
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
     }
-}
 
-#[bench]
-fn bench_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| solve(input.clone()))
-}
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input().unwrap());
+    include!(concat!(env!("OUT_DIR"), "/examples/day05.rs"));
 }
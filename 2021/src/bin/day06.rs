@@ -1,34 +1,28 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness};
 
 use anyhow::Result;
 use std::collections::VecDeque;
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day06.inp");
 
 fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let (part_1, part_2) = solve(input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+    harness::run_both(INPUT, parse_input, solve)
 }
 
-fn get_input() -> Result<Vec<u32>, std::num::ParseIntError> {
-    [
-        include_str!("../inputs/day06.inp"),
-        include_str!("../test_inputs/day06.inp1"),
-    ][INPUT_NUM]
+pub(crate) fn parse_input(in_str: &str) -> Result<Vec<u32>, ParseError> {
+    let src = Source::new(in_str);
+    in_str
         .trim()
         .split(',')
-        .map(str::parse)
+        .map(|num| match src.number(num)? {
+            timer @ 0..=8 => Ok(timer),
+            _ => Err(src.error(num, "a timer from 0 to 8")),
+        })
         .collect()
 }
 
-fn solve(inp: Vec<u32>) -> (u64, u64) {
+pub(crate) fn solve(inp: Vec<u32>) -> (u64, u64) {
     let mut init_counts = [0u64; 9];
     for i in inp {
         init_counts[i as usize] += 1;
@@ -41,25 +35,32 @@ fn solve(inp: Vec<u32>) -> (u64, u64) {
     for day in 1..=256 {
         let birthing = lanternfish.pop_front().unwrap();
         lanternfish.push_back(birthing);
-        *lanternfish.get_mut(6).unwrap() += birthing;
+        let resetting = lanternfish.get_mut(6).unwrap();
+        *resetting = checked::add(*resetting, birthing);
 
         if day == 80 {
-            part_1 = lanternfish.iter().sum();
+            part_1 = checked::sum(lanternfish.iter().copied());
         }
     }
 
-    let part_2 = lanternfish.iter().sum();
+    let part_2 = checked::sum(lanternfish.iter().copied());
 
     (part_1, part_2)
 }
 
-#[bench]
-fn bench_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| solve(input.clone()))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input().unwrap());
+    include!(concat!(env!("OUT_DIR"), "/examples/day06.rs"));
 }
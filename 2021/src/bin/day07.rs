@@ -1,33 +1,24 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use anyhow::Result;
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day07.inp");
 
 fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let (part_1, part_2) = solve(input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+    harness::run_both(INPUT, parse_input, solve)
 }
 
-fn get_input() -> Result<Vec<i32>, std::num::ParseIntError> {
-    [
-        include_str!("../inputs/day07.inp"),
-        include_str!("../test_inputs/day07.inp1"),
-    ][INPUT_NUM]
+pub(crate) fn parse_input(in_str: &str) -> Result<Vec<i32>, ParseError> {
+    let src = Source::new(in_str);
+    in_str
         .trim()
         .split(',')
-        .map(str::parse)
+        .map(|num| src.number(num))
         .collect()
 }
 
-fn solve(inp: Vec<i32>) -> (i32, i32) {
+pub(crate) fn solve(inp: Vec<i32>) -> (i32, i32) {
     let mut part_1 = i32::MAX;
     let mut part_2 = i32::MAX;
 
@@ -48,13 +39,19 @@ fn solve(inp: Vec<i32>) -> (i32, i32) {
     (part_1, part_2)
 }
 
-#[bench]
-fn bench_solution(b: &mut test::Bencher) {
-    let input = get_input().unwrap();
-    b.iter(|| solve(input.clone()))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input().unwrap());
+    include!(concat!(env!("OUT_DIR"), "/examples/day07.rs"));
 }
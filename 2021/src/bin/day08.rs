@@ -1,41 +1,48 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
 use anyhow::Result;
 
-const INPUT_NUM: usize = 0;
-
 #[derive(Debug, Clone)]
-struct Line([&'static str; 10], [&'static str; 4]);
-
-fn main() -> Result<()> {
-    let input = get_input();
-
-    let (part_1, part_2) = solve(input);
+pub(crate) struct Line([&'static str; 10], [&'static str; 4]);
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+pub(crate) const INPUT: &str = include_str!("../inputs/day08.inp");
 
-    Ok(())
+fn main() -> Result<()> {
+    harness::run_both(INPUT, parse_input, solve)
 }
 
-fn get_input() -> Vec<Line> {
-    [
-        include_str!("../inputs/day08.inp"),
-        include_str!("../test_inputs/day08.inp1"),
-        include_str!("../test_inputs/day08.inp2"),
-    ][INPUT_NUM]
+pub(crate) fn parse_input(in_str: &'static str) -> Result<Vec<Line>, ParseError> {
+    let src = Source::new(in_str);
+    in_str
         .lines()
         .map(|line| {
-            let (left, right) = line.split_once(" | ").unwrap();
-            let l: Vec<_> = left.split(' ').collect();
-            let r: Vec<_> = right.split(' ').collect();
-            Line(l.try_into().unwrap(), r.try_into().unwrap())
+            let (left, right) = src.split_once(line, " | ")?;
+            Ok(Line(
+                parse_patterns(&src, left)?,
+                parse_patterns(&src, right)?,
+            ))
         })
         .collect()
 }
 
-fn solve(inp: Vec<Line>) -> (usize, usize) {
+fn parse_patterns<const N: usize>(
+    src: &Source,
+    s: &'static str,
+) -> Result<[&'static str; N], ParseError> {
+    let patterns: Vec<_> = s.split(' ').collect();
+    for pattern in &patterns {
+        if let Some(i) = pattern.bytes().position(|b| !(b'a'..=b'g').contains(&b)) {
+            return Err(src.error_at(pattern, i, "a segment from a to g"));
+        }
+    }
+
+    patterns
+        .try_into()
+        .map_err(|_| src.error(s, format!("{N} patterns")))
+}
+
+pub(crate) fn solve(inp: Vec<Line>) -> (usize, usize) {
     let solved: Vec<_> = inp.iter().map(Line::solve).collect();
 
     let mut part_1 = 0;
@@ -115,13 +122,19 @@ impl Line {
     }
 }
 
-#[bench]
-fn bench_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| solve(input.clone()))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day08.rs"));
 }
@@ -1,89 +1,69 @@
-#![feature(test, array_windows, binary_heap_into_iter_sorted)]
-extern crate test;
+use aoc_utils::grid::{Grid, Pos};
+use aoc_utils::harness;
+use aoc_utils::parse::ParseError;
 
 use std::collections::{BinaryHeap, VecDeque};
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day09.inp");
 
-fn main() {
-    let input = get_input();
-
-    let (part_1, part_2) = solve(input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run_both(INPUT, parse_input, solve)
 }
 
-fn get_input() -> Vec<&'static str> {
-    [
-        include_str!("../inputs/day09.inp"),
-        include_str!("../test_inputs/day09.inp1"),
-    ][INPUT_NUM]
-        .lines()
-        .collect()
+pub(crate) fn parse_input(in_str: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(in_str, "a height from 0 to 9", |c| {
+        c.to_digit(10).map(|height| height as u8)
+    })
 }
 
-fn solve(inp: Vec<&'static str>) -> (usize, usize) {
-    let top_border = vec![b'9'; inp[0].len() + 2];
-    let mut modified: Vec<Vec<u8>> = std::iter::once(top_border.clone())
-        .chain(inp.iter().map(|l| format!("9{}9", l).into_bytes()))
-        .chain(std::iter::once(top_border))
-        .collect();
-
-    let basins: Vec<_> = modified
-        .array_windows::<3>()
-        .enumerate()
-        .flat_map(|(y, rows)| {
-            let [top, mid, bot] = rows;
-            top.array_windows::<3>()
-                .enumerate()
-                .zip(mid.array_windows::<3>().zip(bot.array_windows::<3>()))
-                .filter(|((_, t), (m, b))| {
-                    let c = m[1];
-                    c < t[1] && c < b[1] && c < m[0] && c < m[2]
-                })
-                .map(move |((x, _), _)| (x + 1, y + 1))
-        })
+pub(crate) fn solve(mut heights: Grid<u8>) -> (usize, usize) {
+    let basins: Vec<_> = heights
+        .iter()
+        .filter(|&(pos, &height)| heights.neighbours4(pos).all(|n| height < heights[n]))
+        .map(|(pos, _)| pos)
         .collect();
 
     let mut part_1 = 0;
     let mut max_heap = BinaryHeap::new();
-    for (x, y) in basins {
-        part_1 += (modified[y][x] - b'0' + 1) as usize;
+    for low_point in basins {
+        part_1 += heights[low_point] as usize + 1;
 
-        let basin_size = floodfill(modified.as_mut_slice(), (x, y));
+        let basin_size = floodfill(&mut heights, low_point);
         max_heap.push(basin_size);
     }
-    let part_2 = max_heap.into_iter_sorted().take(3).product();
+    let part_2 = max_heap.into_sorted_vec().iter().rev().take(3).product();
 
     (part_1, part_2)
 }
 
-fn floodfill(caves: &mut [Vec<u8>], low_point: (usize, usize)) -> usize {
+fn floodfill(caves: &mut Grid<u8>, low_point: Pos) -> usize {
     let mut point_queue = VecDeque::from([low_point]);
 
     let mut count = 0;
-    while let Some((x, y)) = point_queue.pop_front() {
-        if caves[y][x] != b'9' {
+    while let Some(pos) = point_queue.pop_front() {
+        if caves[pos] != 9 {
             count += 1;
-            caves[y][x] = b'9';
-            point_queue.push_back((x - 1, y));
-            point_queue.push_back((x + 1, y));
-            point_queue.push_back((x, y - 1));
-            point_queue.push_back((x, y + 1));
+            caves[pos] = 9;
+            point_queue.extend(caves.neighbours4(pos));
         }
     }
 
     count
 }
 
-#[bench]
-fn bench_solution(b: &mut test::Bencher) {
-    let input = get_input();
-    b.iter(|| solve(input.clone()))
-}
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
+    }
+
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
+    }
 
-#[bench]
-fn bench_get_input(b: &mut test::Bencher) {
-    b.iter(|| get_input());
+    include!(concat!(env!("OUT_DIR"), "/examples/day09.rs"));
 }
@@ -1,27 +1,26 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day10.inp");
 
-fn main() {
-    let input = get_input();
-
-    let (part_1, part_2) = solve(input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+fn main() -> anyhow::Result<()> {
+    harness::run_both(INPUT, parse_input, solve)
 }
 
-fn get_input() -> Vec<&'static str> {
-    [
-        include_str!("../inputs/day10.inp"),
-        include_str!("../test_inputs/day10.inp1"),
-    ][INPUT_NUM]
+pub(crate) fn parse_input(in_str: &'static str) -> Result<Vec<&'static str>, ParseError> {
+    let src = Source::new(in_str);
+    in_str
         .lines()
+        .map(
+            |line| match line.bytes().position(|b| !b"()[]{}<>".contains(&b)) {
+                Some(i) => Err(src.error_at(line, i, "a bracket")),
+                None => Ok(line),
+            },
+        )
         .collect()
 }
 
-fn solve(inp: Vec<&'static str>) -> (u32, u64) {
+pub(crate) fn solve(inp: Vec<&'static str>) -> (u32, u64) {
     use ParseChunkError::*;
 
     fn score_corrupt_bracket(bracket: u8) -> u32 {
@@ -204,15 +203,21 @@ mod tests {
         let s = b"])}>";
         assert_eq!(score_incomplete_chunk(s), 294);
     }
+}
 
-    #[bench]
-    fn bench_solution(b: &mut test::Bencher) {
-        let input = get_input();
-        b.iter(|| solve(input.clone()))
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
     }
 
-    #[bench]
-    fn bench_get_input(b: &mut test::Bencher) {
-        b.iter(|| get_input());
+    fn solve_part2(in_str: &'static str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
     }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day10.rs"));
 }
@@ -1,105 +1,92 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::grid::{Grid, SURROUNDING};
+use aoc_utils::harness;
+use aoc_utils::parse::ParseError;
+use aoc_utils::record::{Palette, Recorder};
 
 use anyhow::Result;
 use std::collections::VecDeque;
 use std::fmt;
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day11.inp");
 
 fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let (part_1, part_2) = solve(input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+    harness::run_both(INPUT, parse_input, solve)
 }
 
-fn get_input() -> Result<Cavern> {
-    [
-        include_str!("../inputs/day11.inp"),
-        include_str!("../test_inputs/day11.inp1"),
-        include_str!("../test_inputs/day11.inp2"),
-    ][INPUT_NUM]
-        .parse()
+pub(crate) fn parse_input(in_str: &str) -> Result<Cavern, ParseError> {
+    in_str.parse()
 }
 
-fn solve(mut cavern: Cavern) -> (usize, usize) {
-    let part_1 = (0..100).map(|_| cavern.step()).sum();
+pub(crate) fn solve(mut cavern: Cavern) -> (usize, usize) {
+    let mut recorder = Recorder::requested("octopuses", Cavern::palette());
+    let mut step = |cavern: &mut Cavern| {
+        let flashed = cavern.step();
+        if let Some(recorder) = &mut recorder {
+            recorder.frame(&cavern.octopuses);
+        }
+        flashed
+    };
+
+    let part_1 = (0..100).map(|_| step(&mut cavern)).sum();
 
     let mut part_2 = 100;
     let num_octopuses = cavern.num_octopuses();
     loop {
         part_2 += 1;
-        let flashed = cavern.step();
+        let flashed = step(&mut cavern);
         if flashed == num_octopuses {
             break;
         }
     }
 
+    if let Some(recorder) = recorder {
+        recorder.finish();
+    }
+
     (part_1, part_2)
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Cavern {
-    octopuses: Vec<Vec<u8>>,
+pub(crate) struct Cavern {
+    octopuses: Grid<u8>,
 }
 
 impl Cavern {
     fn step(&mut self) -> usize {
         // First, the energy level of each octopus increases by 1.
         self.octopuses
+            .cells_mut()
             .iter_mut()
-            .flat_map(|line| line.iter_mut())
             .for_each(|energy| *energy += 1);
 
         // Then, any octopus with an energy level greater than 9 flashes.
-        let mut flash_queue: VecDeque<(usize, usize)> = self
+        let mut flash_queue: VecDeque<_> = self
             .octopuses
             .iter()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.iter()
-                    .enumerate()
-                    .filter(|&(_, energy)| *energy == 10)
-                    .map(move |(x, _)| (x, y))
-            })
+            .filter(|&(_, energy)| *energy == 10)
+            .map(|(pos, _)| pos)
             .collect();
 
         let mut flash_count = 0;
-        while let Some((x, y)) = flash_queue.pop_front() {
+        while let Some(pos) = flash_queue.pop_front() {
             flash_count += 1;
-            let offsets = [
-                y.checked_sub(1).zip(x.checked_sub(1)),
-                y.checked_sub(1).zip(Some(x)),
-                y.checked_sub(1).zip(Some(x + 1)),
-                Some(y).zip(x.checked_sub(1)),
-                Some(y).zip(Some(x + 1)),
-                Some(y + 1).zip(x.checked_sub(1)),
-                Some(y + 1).zip(Some(x)),
-                Some(y + 1).zip(Some(x + 1)),
-            ];
-
-            for (row, col) in offsets.into_iter().flatten() {
-                if let Some(energy) = self
-                    .octopuses
-                    .get_mut(row)
-                    .and_then(|line| line.get_mut(col))
-                {
-                    if *energy != 0 {
-                        *energy += 1;
-                    }
-
-                    if *energy == 10 {
-                        flash_queue.push_back((col, row));
-                    }
+
+            for delta in SURROUNDING {
+                let Some(neighbour) = self.octopuses.offset(pos, delta) else {
+                    continue;
+                };
+
+                let energy = &mut self.octopuses[neighbour];
+                if *energy != 0 {
+                    *energy += 1;
+                }
+
+                if *energy == 10 {
+                    flash_queue.push_back(neighbour);
                 }
 
                 // Finally, any octopus that flashed during this step has its energy level set to 0.
-                self.octopuses[y][x] = 0;
+                self.octopuses[pos] = 0;
             }
         }
 
@@ -107,22 +94,24 @@ impl Cavern {
     }
 
     fn num_octopuses(&self) -> usize {
-        self.octopuses[0].len() * self.octopuses.len()
+        self.octopuses.cells().len()
+    }
+
+    /// Those which have just flashed in white, and the rest bluer the less energy they have.
+    fn palette() -> Palette<u8> {
+        (1..=9).fold(Palette::new([255, 255, 255]), |palette, energy| {
+            palette.with(energy, [energy * 8, energy * 12, 40 + energy * 15])
+        })
     }
 }
 
 impl std::str::FromStr for Cavern {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let octopuses = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| c.to_digit(10).unwrap() as u8)
-                    .collect()
-            })
-            .collect();
+        let octopuses = Grid::parse(s, "an energy level from 0 to 9", |c| {
+            c.to_digit(10).map(|energy| energy as u8)
+        })?;
 
         Ok(Self { octopuses })
     }
@@ -130,29 +119,23 @@ impl std::str::FromStr for Cavern {
 
 impl fmt::Display for Cavern {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in self.octopuses.iter() {
-            for o in row.iter() {
-                write!(f, "{}", o)?;
-            }
-            writeln!(f)?;
-        }
-
-        Ok(())
+        write!(f, "{}", self.octopuses)
     }
 }
 
 #[cfg(test)]
-mod tests {
+mod examples {
     use super::*;
 
-    #[bench]
-    fn bench_solution(b: &mut test::Bencher) {
-        let input = get_input().unwrap();
-        b.iter(|| solve(input.clone()))
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
     }
 
-    #[bench]
-    fn bench_get_input(b: &mut test::Bencher) {
-        b.iter(|| get_input());
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
     }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day11.rs"));
 }
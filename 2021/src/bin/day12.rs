@@ -1,38 +1,68 @@
-#![feature(test, iter_intersperse)]
-extern crate test;
+use aoc_utils::graph::{Graph, NodeId};
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::trace;
+
+use std::fmt::{self, Display};
 
 use anyhow::Result;
-use std::collections::HashMap;
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day12.inp");
 
 fn main() -> Result<()> {
-    let input = get_input()?;
+    harness::run_both(INPUT, parse_input, solve)
+}
+
+/// The caves, joined both ways by the passages between them.
+pub(crate) type Caves = Graph<String>;
 
-    let (part_1, part_2) = solve(input);
+pub(crate) fn parse_input(in_str: &str) -> Result<Caves, ParseError> {
+    let src = Source::new(in_str);
+    let mut graph = Caves::undirected();
+
+    for line in in_str.lines() {
+        let (a, b) = src.split_once(line, "-")?;
+        for cave in [a, b] {
+            if cave.is_empty() || !cave.bytes().all(|c| c.is_ascii_alphabetic()) {
+                return Err(src.error(cave, "the name of a cave"));
+            }
+        }
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+        let a_index = graph.intern(a.to_string());
+        let b_index = graph.intern(b.to_string());
+
+        if !graph.neighbours(a_index).any(|x| x == b_index) {
+            graph.connect(a_index, b_index, ());
+        }
+    }
+
+    for cave in ["start", "end"] {
+        if graph.id(cave).is_none() {
+            return Err(src.error(src.end(), format!("a path from {cave}")));
+        }
+    }
 
-    Ok(())
+    Ok(graph)
 }
 
-fn get_input() -> Result<Graph> {
-    [
-        include_str!("../inputs/day12.inp"),
-        include_str!("../test_inputs/day12.inp1"),
-        include_str!("../test_inputs/day12.inp2"),
-        include_str!("../test_inputs/day12.inp3"),
-    ][INPUT_NUM]
-        .parse()
+fn is_small(graph: &Caves, node: NodeId) -> bool {
+    !graph[node].contains(char::is_uppercase)
 }
 
-fn solve(graph: Graph) -> (usize, usize) {
-    let start = graph.node_index("start").unwrap();
-    let end = graph.node_index("end").unwrap();
+pub(crate) fn solve(graph: Caves) -> (usize, usize) {
+    let start = graph.id("start").unwrap();
+    let end = graph.id("end").unwrap();
 
     let mut path_finder = DfsPathfinder::new(&graph);
-    path_finder.find_all_paths(start, end);
+    {
+        let _span = trace::span("paths");
+        path_finder.find_all_paths(start, end);
+    }
+    trace!(
+        "found",
+        single = path_finder.small_single_paths.len(),
+        two = path_finder.small_two_paths.len()
+    );
 
     let mut part_1 = 0;
     let mut two_path_count = 0;
@@ -41,11 +71,11 @@ fn solve(graph: Graph) -> (usize, usize) {
         .iter()
         .chain(path_finder.small_single_paths.iter())
     {
-        let mut counts = vec![0; graph.nodes.len()];
+        let mut counts = vec![0; graph.len()];
         let mut two = None;
         for &node in path.iter() {
             counts[node] += 1;
-            if counts[node] == 2 && graph.is_small(node).unwrap() {
+            if counts[node] == 2 && is_small(&graph, node) {
                 match two {
                     None => two = Some(node),
                     Some(_) => continue 'path_loop,
@@ -60,13 +90,14 @@ fn solve(graph: Graph) -> (usize, usize) {
     }
 
     let part_2 = part_1 + two_path_count;
+    trace!("counted", part_1, two_path_count);
 
     (part_1, part_2)
 }
 
 #[derive(Debug, Clone)]
 struct DfsPathfinder<'a> {
-    graph: &'a Graph,
+    graph: &'a Caves,
     start: usize,
     end: usize,
     small_single_paths: Vec<Vec<usize>>,
@@ -74,7 +105,7 @@ struct DfsPathfinder<'a> {
 }
 
 impl<'a> DfsPathfinder<'a> {
-    fn new(graph: &'a Graph) -> Self {
+    fn new(graph: &'a Caves) -> Self {
         Self {
             graph,
             small_single_paths: Default::default(),
@@ -89,7 +120,7 @@ impl<'a> DfsPathfinder<'a> {
         self.end = end;
 
         let mut path = vec![start];
-        let mut path_node_counts = vec![0; self.graph.nodes.len()];
+        let mut path_node_counts = vec![0; self.graph.len()];
 
         self.dfs(&mut path, path_node_counts.as_mut_slice(), false);
     }
@@ -104,6 +135,7 @@ impl<'a> DfsPathfinder<'a> {
 
         // check if we are the end node
         if node == self.end {
+            trace!("path", path = Route(self.graph, path), path_contains_two);
             if path_contains_two {
                 self.small_two_paths.push(path.clone());
             } else {
@@ -114,13 +146,13 @@ impl<'a> DfsPathfinder<'a> {
         }
 
         // otherwise visit all children
-        for &child in self.graph.succs(&node).into_iter().flatten() {
+        for child in self.graph.neighbours(node) {
             if child == self.start {
                 continue;
             }
 
             let count = path_node_counts[child];
-            if self.graph.is_small(child).unwrap() {
+            if is_small(self.graph, child) {
                 if path_contains_two {
                     if count >= 1 {
                         continue;
@@ -136,7 +168,7 @@ impl<'a> DfsPathfinder<'a> {
 
             self.dfs(path, path_node_counts, path_contains_two);
 
-            if self.graph.is_small(child).unwrap() && path_node_counts[child] == 2 {
+            if is_small(self.graph, child) && path_node_counts[child] == 2 {
                 path_contains_two = false;
             }
             path.pop();
@@ -145,94 +177,34 @@ impl<'a> DfsPathfinder<'a> {
     }
 }
 
-#[derive(Debug, Default, Clone)]
-struct Graph {
-    nodes: Vec<String>,
-    edges: HashMap<usize, Vec<usize>>,
-}
-
-impl Graph {
-    fn new() -> Self {
-        Default::default()
-    }
+/// `start,A,b,end`
+struct Route<'a>(&'a Caves, &'a [NodeId]);
 
-    fn node_index(&self, needle: &str) -> Option<usize> {
-        self.nodes.iter().position(|s| *s == needle)
-    }
-
-    fn add_node(&mut self, node: String) -> usize {
-        self.nodes.push(node);
-        self.nodes.len() - 1
-    }
-
-    fn get_or_insert_node(&mut self, node: &str) -> usize {
-        self.node_index(node)
-            .unwrap_or_else(|| self.add_node(node.to_string()))
-    }
-
-    fn add_edge(&mut self, start: usize, end: usize) {
-        let outbound: &mut Vec<_> = self.edges.entry(start).or_default();
-
-        if !outbound.iter().any(|&x| x == end) {
-            outbound.push(end);
-        }
-    }
-
-    fn succs<'a>(&'a self, node: &usize) -> Option<&'a [usize]> {
-        self.edges.get(node).map(Vec::as_slice)
-    }
-
-    fn is_big(&self, node: usize) -> Option<bool> {
-        self.nodes
-            .get(node)
-            .map(|s| s.find(char::is_uppercase).is_some())
-    }
-
-    fn is_small(&self, node: usize) -> Option<bool> {
-        self.is_big(node).map(std::ops::Not::not)
-    }
-
-    #[allow(dead_code)]
-    fn path_to_string(&self, path: &[usize]) -> String {
-        path.iter()
-            .map(|&n| self.nodes[n].as_str())
-            .intersperse(",")
-            .collect()
-    }
-}
-
-impl std::str::FromStr for Graph {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut graph = Graph::new();
-
-        for line in s.lines() {
-            let (a, b) = line.split_once('-').unwrap();
-
-            let a_index = graph.get_or_insert_node(a);
-            let b_index = graph.get_or_insert_node(b);
-
-            graph.add_edge(a_index, b_index);
-            graph.add_edge(b_index, a_index);
+impl Display for Route<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, &node) in self.1.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", self.0[node])?;
         }
-
-        Ok(graph)
+        Ok(())
     }
 }
 
 #[cfg(test)]
-mod tests {
+mod examples {
     use super::*;
 
-    #[bench]
-    fn bench_solution(b: &mut test::Bencher) {
-        let input = get_input().unwrap();
-        b.iter(|| solve(input.clone()))
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
     }
 
-    #[bench]
-    fn bench_get_input(b: &mut test::Bencher) {
-        b.iter(|| get_input());
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
     }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day12.rs"));
 }
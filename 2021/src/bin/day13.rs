@@ -1,31 +1,20 @@
-#![feature(test, iter_intersperse)]
-extern crate test;
+use aoc_utils::harness;
+use aoc_utils::parse::{ParseError, Source};
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use std::collections::HashSet;
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day13.inp");
 
 fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let (part_1, part_2) = solve(input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2:\n{}", part_2);
-
-    Ok(())
+    harness::run_both(INPUT, parse_input, solve)
 }
 
-fn get_input() -> Result<Input> {
-    [
-        include_str!("../inputs/day13.inp"),
-        include_str!("../test_inputs/day13.inp1"),
-    ][INPUT_NUM]
-        .parse()
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    in_str.parse()
 }
 
-fn solve(input: Input) -> (usize, String) {
+pub(crate) fn solve(input: Input) -> (usize, String) {
     let Input {
         paper: mut points,
         instrs,
@@ -64,29 +53,33 @@ fn solve(input: Input) -> (usize, String) {
     let part_2 = table
         .into_iter()
         .map(|row| String::from_utf8(row).unwrap())
-        .intersperse("\n".into())
-        .collect();
+        .collect::<Vec<_>>()
+        .join("\n");
 
     (part_1, part_2)
 }
 
 #[derive(Debug, Clone)]
-struct Input {
+pub(crate) struct Input {
     paper: Vec<Point>,
     instrs: Vec<Instruction>,
 }
 
 impl std::str::FromStr for Input {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
-        let (points, instructions) = s.split_once("\n\n").unwrap();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
+        let (points, instructions) = src.split_once(s, "\n\n")?;
 
-        let paper = points.lines().map(str::parse).collect::<Result<_>>()?;
+        let paper = points
+            .lines()
+            .map(|line| src.parse(line))
+            .collect::<Result<_, _>>()?;
         let instrs = instructions
             .lines()
-            .map(str::parse)
-            .collect::<Result<_>>()?;
+            .map(|line| src.parse(line))
+            .collect::<Result<_, _>>()?;
 
         Ok(Self { paper, instrs })
     }
@@ -109,14 +102,15 @@ impl Point {
 }
 
 impl std::str::FromStr for Point {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
-        let (x, y) = s.split_once(',').unwrap();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
+        let (x, y) = src.split_once(s, ",")?;
 
         Ok(Self {
-            x: x.parse()?,
-            y: y.parse()?,
+            x: src.number(x)?,
+            y: src.number(y)?,
         })
     }
 }
@@ -128,15 +122,16 @@ struct Instruction {
 }
 
 impl std::str::FromStr for Instruction {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
-        let val = s.rsplit_once(' ').unwrap().1;
-        let (axis, line) = val.split_once('=').unwrap();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
+        let val = src.strip_prefix(s, "fold along ")?;
+        let (axis, line) = src.split_once(val, "=")?;
 
         Ok(Self {
-            axis: axis.parse()?,
-            line: line.parse()?,
+            axis: src.parse(axis)?,
+            line: src.number(line)?,
         })
     }
 }
@@ -148,29 +143,30 @@ enum Axis {
 }
 
 impl std::str::FromStr for Axis {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "x" => Ok(Axis::X),
             "y" => Ok(Axis::Y),
-            _ => Err(anyhow!("Invalid axis: {:?}", s)),
+            _ => Err(Source::new(s).error(s, "x or y")),
         }
     }
 }
 
 #[cfg(test)]
-mod tests {
+mod examples {
     use super::*;
 
-    #[bench]
-    fn bench_solution(b: &mut test::Bencher) {
-        let input = get_input().unwrap();
-        b.iter(|| solve(input.clone()))
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
     }
 
-    #[bench]
-    fn bench_get_input(b: &mut test::Bencher) {
-        b.iter(|| get_input());
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
     }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day13.rs"));
 }
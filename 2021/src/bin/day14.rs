@@ -1,177 +1,138 @@
-#![feature(test, array_windows)]
-extern crate test;
+use aoc_utils::counter::DenseCounter;
+use aoc_utils::harness;
+use aoc_utils::memo::Memo;
+use aoc_utils::parse::{ParseError, Source};
 
 use anyhow::Result;
 use std::collections::HashMap;
 
-const INPUT_NUM: usize = 0;
+pub(crate) const INPUT: &str = include_str!("../inputs/day14.inp");
 
 fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let (part_1, part_2) = solve(input);
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+    harness::run_both(INPUT, parse_input, solve)
 }
 
-fn get_input() -> Result<Input> {
-    [
-        include_str!("../inputs/day14.inp"),
-        include_str!("../test_inputs/day14.inp1"),
-    ][INPUT_NUM]
-        .parse()
+pub(crate) fn parse_input(in_str: &str) -> Result<Input, ParseError> {
+    in_str.parse()
 }
 
-fn solve(Input { template, rules }: Input) -> (usize, usize) {
-    let mut polymeriser = Polymeriser::new(template.clone(), rules.clone(), 10);
+/// How many of each element there are, indexed by its letter.
+type Counter = DenseCounter<u8, { b'Z' as usize + 1 }>;
+
+pub(crate) fn solve(Input { template, rules }: Input) -> (usize, usize) {
+    let polymeriser = Polymeriser::new(template.clone(), rules.clone(), 10);
     let counts = polymeriser.polymerise();
-    let part_1 = counts.max() - counts.min();
+    let part_1 = spread(&counts);
 
-    let mut polymeriser = Polymeriser::new(template.clone(), rules.clone(), 40);
+    let polymeriser = Polymeriser::new(template.clone(), rules.clone(), 40);
     let counts = polymeriser.polymerise();
-    let part_2 = counts.max() - counts.min();
+    let part_2 = spread(&counts);
 
     (part_1, part_2)
 }
 
+/// The difference between the most and least common elements' counts.
+fn spread(counts: &Counter) -> usize {
+    counts.most_common().unwrap().0 - counts.least_common().unwrap().0
+}
+
 #[derive(Debug, Clone)]
 struct Polymeriser {
     template: Vec<u8>,
     rules: HashMap<(u8, u8), u8>,
-    step_cache: HashMap<(u8, u8, usize), Counter>,
     max_step: usize,
 }
 
+/// The elements inserted between a pair by the time it's been through every step, from the
+/// step it's at.
+type StepCache = Memo<(u8, u8, usize), Counter>;
+
 impl Polymeriser {
     fn new(template: Vec<u8>, rules: HashMap<(u8, u8), u8>, max_step: usize) -> Self {
         Self {
             template,
             rules,
             max_step,
-            step_cache: Default::default(),
         }
     }
 
-    fn count(&mut self, left: u8, right: u8, mut step: usize) -> Counter {
-        if let Some(cached_result) = self.step_cache.get(&(left, right, step)) {
-            return cached_result.clone();
-        }
-
+    fn count(&self, cache: &mut StepCache, left: u8, right: u8, step: usize) -> Counter {
         if step == self.max_step {
-            Counter::empty()
-        } else {
-            step += 1;
+            return Counter::new();
+        }
 
+        cache.get((left, right, step), |cache, _| {
             let next = *self.rules.get(&(left, right)).unwrap();
-            let mut new_counter = Counter::new(next);
-            new_counter.update(self.count(left, next, step));
-            new_counter.update(self.count(next, right, step));
-
-            self.step_cache
-                .insert((left, right, step - 1), new_counter.clone());
-
+            let mut new_counter = Counter::new();
+            new_counter.add(next);
+            new_counter.merge(&self.count(cache, left, next, step + 1));
+            new_counter.merge(&self.count(cache, next, right, step + 1));
             new_counter
-        }
-    }
-
-    fn polymerise(&mut self) -> Counter {
-        let mut counter = Counter::from(self.template.as_slice());
-        for &[left, right] in self.template.clone().array_windows() {
-            counter.update(self.count(left, right, 0));
-        }
-
-        counter
-    }
-}
-
-#[derive(Debug, Default, Clone)]
-struct Counter {
-    counts: HashMap<u8, usize>,
-}
-
-impl Counter {
-    fn empty() -> Self {
-        Default::default()
-    }
-
-    fn new(elem: u8) -> Self {
-        let mut counter = Self::empty();
-
-        counter.counts.insert(elem, 1);
-
-        counter
+        })
     }
 
-    fn from(elems: &[u8]) -> Self {
-        let mut counter = Self::empty();
-
-        for &elem in elems {
-            *counter.counts.entry(elem).or_default() += 1;
+    fn polymerise(&self) -> Counter {
+        let mut cache = StepCache::new();
+        let mut counter: Counter = self.template.iter().copied().collect();
+        for pair in self.template.windows(2) {
+            let (left, right) = (pair[0], pair[1]);
+            counter.merge(&self.count(&mut cache, left, right, 0));
         }
 
         counter
     }
-
-    fn update(&mut self, other: Self) {
-        for (elem, count) in other.counts {
-            *self.counts.entry(elem).or_default() += count;
-        }
-    }
-
-    fn max(&self) -> usize {
-        self.counts.iter().map(|(_, &count)| count).max().unwrap()
-    }
-
-    fn min(&self) -> usize {
-        self.counts.iter().map(|(_, &count)| count).min().unwrap()
-    }
 }
 
 #[derive(Debug, Clone)]
-struct Input {
+pub(crate) struct Input {
     template: Vec<u8>,
     rules: HashMap<(u8, u8), u8>,
 }
 
 impl std::str::FromStr for Input {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
-        let (temp, pairs) = s.split_once("\n\n").unwrap();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
+        let (temp, pairs) = src.split_once(s, "\n\n")?;
 
         let template = temp.trim().to_string().into_bytes();
+        if template.is_empty() || !template.iter().all(u8::is_ascii_uppercase) {
+            return Err(src.error(temp, "a polymer template"));
+        }
+
         let rules = pairs
             .lines()
             .map(|line| {
-                let (pair, result) = line.split_once(" -> ").unwrap();
-                let mut pair_bytes = pair.bytes();
-                let left = pair_bytes.next().unwrap();
-                let right = pair_bytes.next().unwrap();
-                let dest = result.bytes().next().unwrap();
-
-                ((left, right), dest)
+                let (pair, result) = src.split_once(line, " -> ")?;
+                let &[left @ b'A'..=b'Z', right @ b'A'..=b'Z'] = pair.as_bytes() else {
+                    return Err(src.error(pair, "a pair of elements"));
+                };
+                let &[dest @ b'A'..=b'Z'] = result.as_bytes() else {
+                    return Err(src.error(result, "a single element"));
+                };
+
+                Ok(((left, right), dest))
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         Ok(Self { template, rules })
     }
 }
 
 #[cfg(test)]
-mod tests {
+mod examples {
     use super::*;
 
-    #[bench]
-    fn bench_solution(b: &mut test::Bencher) {
-        let input = get_input().unwrap();
-        b.iter(|| solve(input.clone()))
+    fn solve_part1(in_str: &str) -> String {
+        let (part_1, _) = solve(parse_input(in_str).unwrap());
+        part_1.to_string()
     }
 
-    #[bench]
-    fn bench_get_input(b: &mut test::Bencher) {
-        b.iter(|| get_input());
+    fn solve_part2(in_str: &str) -> String {
+        let (_, part_2) = solve(parse_input(in_str).unwrap());
+        part_2.to_string()
     }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day14.rs"));
 }
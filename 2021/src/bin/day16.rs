@@ -1,30 +1,42 @@
-#![feature(test)]
-extern crate test;
+use aoc_utils::bit_ops::BitOps;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness};
 
 use anyhow::Result;
-use nom::IResult;
 use nom::bits::complete::take;
-use aoc::bit_ops::BitOps;
-
-fn main() -> Result<()> {
-    let input = get_input()?;
-
-    let (part_1, part_2) = solve(&input);
+use nom::IResult;
 
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+pub(crate) const INPUT: &str = include_str!("../inputs/day16.inp");
 
-    Ok(())
+fn main() -> Result<()> {
+    harness::run_both(INPUT, parse_input, |input| solve(&input))
 }
 
-fn get_input() -> Result<Vec<u8>, hex::FromHexError> {
-    hex::decode(include_str!("../inputs/day16.inp").trim())
-}
+pub(crate) fn parse_input(in_str: &str) -> Result<Packet, ParseError> {
+    let src = Source::new(in_str);
+    let hex_str = in_str.trim();
 
-fn solve(input: &[u8]) -> (usize, i64) {
-    let (_, packet) = Packet::parse((input, 0)).unwrap();
+    let data = hex::decode(hex_str).map_err(|e| match e {
+        hex::FromHexError::InvalidHexCharacter { index, .. } => {
+            src.error_at(hex_str, index, "a hex digit")
+        }
+        _ => src.error(src.end(), "an even number of hex digits"),
+    })?;
+
+    match Packet::parse((data.as_slice(), 0)) {
+        Ok((_, packet)) => Ok(packet),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            // two hex digits to a byte, four bits to a digit
+            let (rest, bit) = e.input;
+            let digit = (data.len() - rest.len()) * 2 + bit / 4;
+            Err(src.error_at(hex_str, digit, "the rest of the packet"))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(src.error(src.end(), "the rest of the packet")),
+    }
+}
 
-    let part_1 = version_sum(&packet);
+pub(crate) fn solve(packet: &Packet) -> (usize, i64) {
+    let part_1 = version_sum(packet);
     let part_2 = packet.eval();
 
     (part_1, part_2)
@@ -32,14 +44,15 @@ fn solve(input: &[u8]) -> (usize, i64) {
 
 fn version_sum(packet: &Packet) -> usize {
     use PacketContents::*;
-    packet.version as usize + match &packet.contents {
-        Literal(_) => 0,
-        Operator(sub_packets) => sub_packets.iter().map(version_sum).sum(),
-    }
+    packet.version as usize
+        + match &packet.contents {
+            Literal(_) => 0,
+            Operator(sub_packets) => sub_packets.iter().map(version_sum).sum(),
+        }
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct Packet {
+pub(crate) struct Packet {
     version: u8,
     type_id: u8,
     contents: PacketContents,
@@ -50,41 +63,74 @@ impl Packet {
         let (input, version) = take(3_usize)(input)?;
         let (input, (type_id, contents)) = PacketContents::parse(input)?;
 
-        Ok((input, Self { version, type_id, contents }))
+        Ok((
+            input,
+            Self {
+                version,
+                type_id,
+                contents,
+            },
+        ))
     }
-    
+
     fn eval(&self) -> i64 {
         match self.type_id {
-            0 => self.contents.subpackets().unwrap().iter().map(Packet::eval).sum(),
-            1 => self.contents.subpackets().unwrap().iter().map(Packet::eval).product(),
-            2 => self.contents.subpackets().unwrap().iter().map(Packet::eval).min().unwrap(),
-            3 => self.contents.subpackets().unwrap().iter().map(Packet::eval).max().unwrap(),
+            0 => checked::sum(self.contents.subpackets().unwrap().iter().map(Packet::eval)),
+            1 => checked::product(self.contents.subpackets().unwrap().iter().map(Packet::eval)),
+            2 => self
+                .contents
+                .subpackets()
+                .unwrap()
+                .iter()
+                .map(Packet::eval)
+                .min()
+                .unwrap(),
+            3 => self
+                .contents
+                .subpackets()
+                .unwrap()
+                .iter()
+                .map(Packet::eval)
+                .max()
+                .unwrap(),
             4 => self.contents.literal().unwrap() as i64,
-            5 => {
-                match self.contents.subpackets().unwrap() {
-                    [a, b] => if a.eval() > b.eval() { 1 } else { 0 },
-                    _ => unreachable!("Greater than packets always have exactly two sub-packets"),
+            5 => match self.contents.subpackets().unwrap() {
+                [a, b] => {
+                    if a.eval() > b.eval() {
+                        1
+                    } else {
+                        0
+                    }
                 }
-            }
-            6 => {
-                match self.contents.subpackets().unwrap() {
-                    [a, b] => if a.eval() < b.eval() { 1 } else { 0 },
-                    _ => unreachable!("Less than packets always have exactly two sub-packets"),
+                _ => unreachable!("Greater than packets always have exactly two sub-packets"),
+            },
+            6 => match self.contents.subpackets().unwrap() {
+                [a, b] => {
+                    if a.eval() < b.eval() {
+                        1
+                    } else {
+                        0
+                    }
                 }
-            }
-            7 => {
-                match self.contents.subpackets().unwrap() {
-                    [a, b] => if a.eval() == b.eval() { 1 } else { 0 },
-                    _ => unreachable!("Equal to packets always have exactly two sub-packets"),
+                _ => unreachable!("Less than packets always have exactly two sub-packets"),
+            },
+            7 => match self.contents.subpackets().unwrap() {
+                [a, b] => {
+                    if a.eval() == b.eval() {
+                        1
+                    } else {
+                        0
+                    }
                 }
-            }
+                _ => unreachable!("Equal to packets always have exactly two sub-packets"),
+            },
             _ => unimplemented!(),
         }
     }
 }
 
 #[derive(Debug, Eq, PartialEq)]
-enum PacketContents {
+pub(crate) enum PacketContents {
     Literal(u64),
     Operator(Vec<Packet>),
 }
@@ -112,7 +158,7 @@ impl PacketContents {
         (end * 8 + offset2) - (start * 8 + offset1)
     }
 
-    fn read_operator(input: (&[u8], usize), ) -> IResult<(&[u8], usize), Self> {
+    fn read_operator(input: (&[u8], usize)) -> IResult<(&[u8], usize), Self> {
         use LengthTypeId::*;
 
         let (mut input, length_type_id) = LengthTypeId::parse(input)?;
@@ -155,7 +201,7 @@ impl PacketContents {
             }
         })
     }
-    
+
     fn subpackets(&self) -> Option<&[Packet]> {
         use PacketContents::*;
 
@@ -200,8 +246,8 @@ impl LengthTypeId {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
     use super::PacketContents::*;
+    use super::*;
 
     #[test]
     fn test_parse_structure_1() {
@@ -209,11 +255,14 @@ mod tests {
         let input = (data.as_slice(), 0);
         let parsed = Packet::parse(input).unwrap().1;
 
-        assert_eq!(parsed, Packet {
-            version: 6,
-            type_id: 4,
-            contents: Literal(2021),
-        });
+        assert_eq!(
+            parsed,
+            Packet {
+                version: 6,
+                type_id: 4,
+                contents: Literal(2021),
+            }
+        );
     }
 
     #[test]
@@ -222,22 +271,25 @@ mod tests {
         let input = (data.as_slice(), 0);
         let parsed = Packet::parse(input).unwrap().1;
 
-        assert_eq!(parsed, Packet {
-            version: 1,
-            type_id: 6,
-            contents: Operator(vec![
-                Packet {
-                    version: 6,
-                    type_id: 4,
-                    contents: Literal(10),
-                },
-                Packet {
-                    version: 2,
-                    type_id: 4,
-                    contents: Literal(20),
-                },
-            ]),
-        });
+        assert_eq!(
+            parsed,
+            Packet {
+                version: 1,
+                type_id: 6,
+                contents: Operator(vec![
+                    Packet {
+                        version: 6,
+                        type_id: 4,
+                        contents: Literal(10),
+                    },
+                    Packet {
+                        version: 2,
+                        type_id: 4,
+                        contents: Literal(20),
+                    },
+                ]),
+            }
+        );
     }
 
     #[test]
@@ -246,27 +298,30 @@ mod tests {
         let input = (data.as_slice(), 0);
         let parsed = Packet::parse(input).unwrap().1;
 
-        assert_eq!(parsed, Packet {
-            version: 7,
-            type_id: 3,
-            contents: Operator(vec![
-                Packet {
-                    version: 2,
-                    type_id: 4,
-                    contents: Literal(1),
-                },
-                Packet {
-                    version: 4,
-                    type_id: 4,
-                    contents: Literal(2),
-                },
-                Packet {
-                    version: 1,
-                    type_id: 4,
-                    contents: Literal(3),
-                },
-            ]),
-        });
+        assert_eq!(
+            parsed,
+            Packet {
+                version: 7,
+                type_id: 3,
+                contents: Operator(vec![
+                    Packet {
+                        version: 2,
+                        type_id: 4,
+                        contents: Literal(1),
+                    },
+                    Packet {
+                        version: 4,
+                        type_id: 4,
+                        contents: Literal(2),
+                    },
+                    Packet {
+                        version: 1,
+                        type_id: 4,
+                        contents: Literal(3),
+                    },
+                ]),
+            }
+        );
     }
 
     #[test]
@@ -376,15 +431,4 @@ mod tests {
 
         assert_eq!(packet.eval(), 1);
     }
-
-    #[bench]
-    fn bench_solution(b: &mut test::Bencher) {
-        let input = get_input().unwrap();
-        b.iter(|| solve(&input))
-    }
-
-    #[bench]
-    fn bench_get_input(b: &mut test::Bencher) {
-        b.iter(|| get_input());
-    }
 }
@@ -0,0 +1,269 @@
+//! Criterion benchmarks for every day, run with `cargo bench`.
+//!
+//! The days are binaries, so each one is pulled in as a module.
+
+#![allow(dead_code)]
+
+use aoc_utils::bench::{bench_day, bench_parse};
+use aoc_utils::input_or_skip;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+#[path = "../src/bin/day01.rs"]
+mod day01;
+#[path = "../src/bin/day02.rs"]
+mod day02;
+#[path = "../src/bin/day03.rs"]
+mod day03;
+#[path = "../src/bin/day04.rs"]
+mod day04;
+#[path = "../src/bin/day05.rs"]
+mod day05;
+#[path = "../src/bin/day06.rs"]
+mod day06;
+#[path = "../src/bin/day07.rs"]
+mod day07;
+#[path = "../src/bin/day08.rs"]
+mod day08;
+#[path = "../src/bin/day09.rs"]
+mod day09;
+#[path = "../src/bin/day10.rs"]
+mod day10;
+#[path = "../src/bin/day11.rs"]
+mod day11;
+#[path = "../src/bin/day12.rs"]
+mod day12;
+#[path = "../src/bin/day13.rs"]
+mod day13;
+#[path = "../src/bin/day14.rs"]
+mod day14;
+#[path = "../src/bin/day15.rs"]
+mod day15;
+#[path = "../src/bin/day16.rs"]
+mod day16;
+#[path = "../src/bin/day17.rs"]
+mod day17;
+
+fn bench_day01(c: &mut Criterion) {
+    let input = input_or_skip!(day01::INPUT_PATH);
+    bench_day(
+        c,
+        "day01",
+        input,
+        day01::parse_input,
+        |inp| day01::part1(inp),
+        |inp| day01::part2(inp),
+    );
+}
+
+fn bench_day02(c: &mut Criterion) {
+    let input = input_or_skip!(day02::INPUT_PATH);
+    bench_day(
+        c,
+        "day02",
+        input,
+        day02::parse_input,
+        |inp| day02::part1(inp),
+        |inp| day02::part2(inp),
+    );
+}
+
+fn bench_day03(c: &mut Criterion) {
+    let input = input_or_skip!(day03::INPUT_PATH);
+    bench_day(
+        c,
+        "day03",
+        input,
+        day03::parse_input,
+        day03::part1,
+        day03::part2,
+    );
+}
+
+fn bench_day04(c: &mut Criterion) {
+    let input = input_or_skip!(day04::INPUT_PATH);
+    bench_day(
+        c,
+        "day04",
+        input,
+        day04::parse_input,
+        |inp| day04::part1(inp),
+        |inp| day04::part2(inp),
+    );
+}
+
+fn bench_day05(c: &mut Criterion) {
+    let input = input_or_skip!(day05::INPUT_PATH);
+    bench_day(
+        c,
+        "day05",
+        input,
+        day05::parse_input,
+        day05::part1,
+        day05::part2,
+    );
+}
+
+fn bench_day06(c: &mut Criterion) {
+    let input = input_or_skip!(day06::INPUT_PATH);
+    bench_day(
+        c,
+        "day06",
+        input,
+        day06::parse_input,
+        |inp| day06::part1(inp),
+        |inp| day06::part2(inp),
+    );
+}
+
+fn bench_day07(c: &mut Criterion) {
+    let input = input_or_skip!(day07::INPUT_PATH);
+    bench_day(
+        c,
+        "day07",
+        input,
+        day07::parse_input,
+        |inp| day07::part1(inp),
+        |inp| day07::part2(inp),
+    );
+}
+
+fn bench_day08(c: &mut Criterion) {
+    let input = input_or_skip!(day08::INPUT_PATH);
+    bench_day(
+        c,
+        "day08",
+        input,
+        day08::parse_input,
+        day08::part1,
+        day08::part2,
+    );
+}
+
+fn bench_day09(c: &mut Criterion) {
+    let input = input_or_skip!(day09::INPUT_PATH);
+    bench_day(
+        c,
+        "day09",
+        input,
+        day09::parse_input,
+        |inp| day09::part1(inp),
+        |inp| day09::part2(inp),
+    );
+}
+
+fn bench_day10(c: &mut Criterion) {
+    let input = input_or_skip!(day10::INPUT_PATH);
+    let inp = bench_parse(c, "day10", input, day10::parse_input);
+    c.bench_function("day10_solve", |b| {
+        b.iter(|| {
+            let (path, p1) = day10::part1(black_box(&inp));
+            (p1, day10::part2(black_box(&inp), path))
+        })
+    });
+}
+
+fn bench_day11(c: &mut Criterion) {
+    let input = input_or_skip!(day11::INPUT_PATH);
+    bench_day(
+        c,
+        "day11",
+        input,
+        day11::parse_input,
+        |(inp1, _)| day11::solve(inp1),
+        |(_, inp2)| day11::solve(inp2),
+    );
+}
+
+fn bench_day12(c: &mut Criterion) {
+    let input = input_or_skip!(day12::INPUT_PATH);
+    bench_day(
+        c,
+        "day12",
+        input,
+        day12::parse_input,
+        |inp| day12::part1(inp),
+        |inp| day12::part2(inp),
+    );
+}
+
+fn bench_day13(c: &mut Criterion) {
+    let input = input_or_skip!(day13::INPUT_PATH);
+    bench_day(
+        c,
+        "day13",
+        input,
+        day13::parse_input,
+        |inp| day13::solve(inp, 0),
+        |inp| day13::solve(inp, 1),
+    );
+}
+
+fn bench_day14(c: &mut Criterion) {
+    let input = input_or_skip!(day14::INPUT_PATH);
+    bench_day(
+        c,
+        "day14",
+        input,
+        day14::parse_input,
+        day14::part1,
+        day14::part2,
+    );
+}
+
+fn bench_day15(c: &mut Criterion) {
+    let input = input_or_skip!(day15::INPUT_PATH);
+    bench_day(
+        c,
+        "day15",
+        input,
+        day15::parse_input,
+        |inp| day15::part1(inp),
+        |inp| day15::part2(inp),
+    );
+}
+
+fn bench_day16(c: &mut Criterion) {
+    let input = input_or_skip!(day16::INPUT_PATH);
+    bench_day(
+        c,
+        "day16",
+        input,
+        day16::parse_input,
+        day16::part1,
+        day16::part2,
+    );
+}
+
+fn bench_day17(c: &mut Criterion) {
+    let input = input_or_skip!(day17::INPUT_PATH);
+    bench_day(
+        c,
+        "day17",
+        input,
+        day17::parse_input,
+        day17::part1,
+        day17::part2,
+    );
+}
+
+criterion_group!(
+    benches,
+    bench_day01,
+    bench_day02,
+    bench_day03,
+    bench_day04,
+    bench_day05,
+    bench_day06,
+    bench_day07,
+    bench_day08,
+    bench_day09,
+    bench_day10,
+    bench_day11,
+    bench_day12,
+    bench_day13,
+    bench_day14,
+    bench_day15,
+    bench_day16,
+    bench_day17
+);
+criterion_main!(benches);
@@ -1,45 +1,52 @@
-fn main() {
-    let inp = parse_input();
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day01.txt");
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
+
+    harness::run(input, parse_input, |inp| part1(inp), |inp| part2(inp))
 }
 
-fn parse_input() -> Vec<&'static str> {
-    include_str!("../../inputs/day01.txt").lines().collect()
+pub(crate) fn parse_input(inp: &'static str) -> Result<Vec<&'static str>, ParseError> {
+    let src = Source::new(inp);
+    inp.lines()
+        .map(
+            |line| match line.bytes().position(|b| !b.is_ascii_alphanumeric()) {
+                Some(i) => Err(src.error_at(line, i, "a digit or letter")),
+                None => Ok(line),
+            },
+        )
+        .collect()
 }
 
-fn part1(inp: &[&str]) -> u32 {
-    inp.iter()
-        .map(|line| {
-            let first = line.chars().find_map(|c| c.to_digit(10)).unwrap();
-            let last = line.chars().rev().find_map(|c| c.to_digit(10)).unwrap();
-            first * 10 + last
-        })
-        .sum()
+pub(crate) fn part1(inp: &[&str]) -> u32 {
+    checked::sum(inp.iter().map(|line| {
+        let first = line.chars().find_map(|c| c.to_digit(10)).unwrap();
+        let last = line.chars().rev().find_map(|c| c.to_digit(10)).unwrap();
+        first * 10 + last
+    }))
 }
 
-fn part2(inp: &[&str]) -> u32 {
-    inp.iter()
-        .map(|line| {
-            let bytes = line.as_bytes();
-            let mut first = None;
-            let mut last = None;
-            for (i, c) in bytes.iter().enumerate() {
-                let num = if c.is_ascii_digit() {
-                    Some((c - b'0') as u32)
-                } else {
-                    get_number_prefix(&bytes[i..])
-                };
-                first = first.or(num);
-                last = num.or(last);
-            }
-            first.unwrap() * 10 + last.unwrap()
-        })
-        .sum()
+pub(crate) fn part2(inp: &[&str]) -> u32 {
+    checked::sum(inp.iter().map(|line| {
+        let bytes = line.as_bytes();
+        let mut first = None;
+        let mut last = None;
+        for (i, c) in bytes.iter().enumerate() {
+            let num = if c.is_ascii_digit() {
+                Some((c - b'0') as u32)
+            } else {
+                get_number_prefix(&bytes[i..])
+            };
+            first = first.or(num);
+            last = num.or(last);
+        }
+        first.unwrap() * 10 + last.unwrap()
+    }))
 }
 
 fn get_number_prefix(num: &[u8]) -> Option<u32> {
@@ -59,3 +66,18 @@ fn get_number_prefix(num: &[u8]) -> Option<u32> {
         .into_iter()
         .find_map(|(prefix, n)| num.starts_with(prefix).then_some(n))
 }
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day01.rs"));
+}
@@ -1,16 +1,19 @@
-fn main() {
-    let inp = parse_input();
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day02.txt");
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
+
+    harness::run(input, parse_input, |inp| part1(inp), |inp| part2(inp))
 }
 
-fn parse_input() -> Vec<Game> {
-    include_str!("../../inputs/day02.txt")
-        .lines()
+pub(crate) fn parse_input(inp: &'static str) -> Result<Vec<Game>, ParseError> {
+    let src = Source::new(inp);
+    inp.lines()
         .map(|line| {
             let mut game = Game {
                 red: 0,
@@ -18,44 +21,57 @@ fn parse_input() -> Vec<Game> {
                 blue: 0,
             };
 
-            let rounds = line.split_once(": ").expect("Line in invalid format").1;
+            let rounds = src.split_once(line, ": ")?.1;
             for block in rounds.split("; ") {
                 for balls in block.split(", ") {
-                    let (count, colour) = balls.split_once(' ').expect("Balls in invalid format.");
-                    let count = count.parse().expect("Failed to parse count");
+                    let (count, colour) = src.split_once(balls, " ")?;
+                    let count = src.number(count)?;
                     match colour {
                         "red" => game.red = game.red.max(count),
                         "green" => game.green = game.green.max(count),
                         "blue" => game.blue = game.blue.max(count),
-                        x => unreachable!("A secret fourth colour appearerd! - {x:?}"),
+                        _ => return Err(src.error(colour, "red, green or blue")),
                     }
                 }
             }
 
-            game
+            Ok(game)
         })
         .collect()
 }
 
 #[derive(Debug)]
-struct Game {
+pub(crate) struct Game {
     red: u32,
     green: u32,
     blue: u32,
 }
 
-fn part1(inp: &[Game]) -> usize {
-    inp.iter()
-        .enumerate()
-        .filter_map(|(i, game)| {
-            let can_fit = game.red <= 12 && game.green <= 13 && game.blue <= 14;
-            can_fit.then_some(i + 1)
-        })
-        .sum()
+pub(crate) fn part1(inp: &[Game]) -> usize {
+    checked::sum(inp.iter().enumerate().filter_map(|(i, game)| {
+        let can_fit = game.red <= 12 && game.green <= 13 && game.blue <= 14;
+        can_fit.then_some(i + 1)
+    }))
+}
+
+pub(crate) fn part2(inp: &[Game]) -> u32 {
+    checked::sum(
+        inp.iter()
+            .map(|game| checked::product([game.red, game.blue, game.green])),
+    )
 }
 
-fn part2(inp: &[Game]) -> u32 {
-    inp.iter()
-        .map(|game| game.red * game.blue * game.green)
-        .sum()
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day02.rs"));
 }
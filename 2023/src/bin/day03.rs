@@ -1,37 +1,48 @@
 use std::{collections::BTreeMap, str::FromStr};
 
-use anyhow::Context;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 
-fn main() {
-    let inp = parse_input();
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day03.txt");
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+    harness::run(input, parse_input, part1, part2)
 }
 
-fn parse_input() -> Engine {
-    include_str!("../../inputs/day03.txt")
-        .parse()
-        .expect("Failed to parse input file.")
+pub(crate) fn parse_input(inp: &'static str) -> Result<Engine, ParseError> {
+    inp.parse()
 }
 
 #[derive(Debug, Default)]
-struct Engine {
+pub(crate) struct Engine {
     numbers: Vec<u32>,
     gear_adjacents: BTreeMap<(usize, usize), Vec<u32>>,
 }
 
 impl FromStr for Engine {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
         let mut numbers = Vec::new();
         let mut gear_adjacents: BTreeMap<_, Vec<u32>> = BTreeMap::new();
         let lines: Vec<_> = s.lines().collect();
 
+        // neighbours are found by indexing, so the schematic must be a rectangle of ascii
+        let width = lines.first().map_or(0, |line| line.len());
+        for line in &lines {
+            if let Some(i) = line.bytes().position(|b| !b.is_ascii_graphic()) {
+                return Err(src.error_at(line, i, "a digit, \".\" or a symbol"));
+            }
+            if line.len() != width {
+                return Err(src.error(line, format!("a row {width} wide")));
+            }
+        }
+
         let detect_neighbours = |i: usize, j: usize, num_start: usize| {
             let above = i.saturating_sub(1);
             let below = (i + 1).min(lines.len() - 1);
@@ -56,7 +67,7 @@ impl FromStr for Engine {
         let mut num = String::new();
         let mut num_start = 0;
         for (i, line) in lines.iter().enumerate() {
-            for (j, c) in line.chars().enumerate() {
+            for (j, c) in line.char_indices() {
                 if c.is_ascii_digit() {
                     if num.is_empty() {
                         num_start = j;
@@ -66,9 +77,7 @@ impl FromStr for Engine {
                 }
 
                 if !num.is_empty() {
-                    let n = num
-                        .parse()
-                        .with_context(|| format!("Line {i}, col {j}: num={num:?}"))?;
+                    let n = src.number(&line[num_start..j])?;
                     num.clear();
 
                     let (is_symbol, gears) = detect_neighbours(i, j, num_start);
@@ -84,9 +93,7 @@ impl FromStr for Engine {
             }
 
             if !num.is_empty() {
-                let n = num
-                    .parse()
-                    .with_context(|| format!("Line {i}, col {}: num={num:?}", line.len()))?;
+                let n = src.number(&line[num_start..])?;
 
                 let (is_symbol, gears) = detect_neighbours(i, line.len() - 1, num_start);
                 if is_symbol {
@@ -106,14 +113,30 @@ impl FromStr for Engine {
     }
 }
 
-fn part1(inp: &Engine) -> u32 {
-    inp.numbers.iter().sum()
+pub(crate) fn part1(inp: &Engine) -> u32 {
+    checked::sum(inp.numbers.iter().copied())
+}
+
+pub(crate) fn part2(inp: &Engine) -> u32 {
+    checked::sum(
+        inp.gear_adjacents
+            .values()
+            .filter(|adjacents| adjacents.len() == 2)
+            .map(|adjacents| checked::product(adjacents.iter().copied())),
+    )
 }
 
-fn part2(inp: &Engine) -> u32 {
-    inp.gear_adjacents
-        .values()
-        .filter(|adjacents| adjacents.len() == 2)
-        .map(|adjacents| adjacents.iter().product::<u32>())
-        .sum()
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day03.rs"));
 }
@@ -1,55 +1,90 @@
-fn main() {
-    let inp = parse_input();
+use aoc_utils::bit_ops::BitOps;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day04.txt");
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
-}
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-fn parse_input() -> Vec<(u128, u128)> {
-    include_str!("../../inputs/day04.txt")
-        .lines()
-        .map(|line| {
-            let all_numbers = line.split_once(':').expect("Failed to split on ':'").1;
+    harness::run(input, parse_input, |inp| part1(inp), |inp| part2(inp))
+}
 
-            let (winners, my_nums) = all_numbers.split_once('|').expect("Failed to split on '|'");
-            let mut win = 0;
-            let mut mine = 0;
+/// A card's numbers, as the bits of a `u128`.
+type Numbers = u128;
 
-            for num in winners.split_ascii_whitespace() {
-                let n: u32 = num.parse().expect("Failed to parse winner");
-                win |= 1 << n;
+pub(crate) fn parse_input(inp: &'static str) -> Result<Vec<(Numbers, Numbers)>, ParseError> {
+    let src = Source::new(inp);
+    let parse_set = |nums: &str| {
+        let mut set: Numbers = 0;
+        for num in nums.split_ascii_whitespace() {
+            match src.number::<u32>(num)? {
+                n if n < Numbers::BITS => set.set_bit(n),
+                _ => return Err(src.error(num, format!("a number below {}", Numbers::BITS))),
             }
+        }
+        Ok(set)
+    };
 
-            for num in my_nums.split_ascii_whitespace() {
-                let n: u32 = num.parse().expect("Failed to parse my number");
-                mine |= 1 << n;
-            }
+    inp.lines()
+        .map(|line| {
+            let all_numbers = src.split_once(line, ":")?.1;
+            let (winners, my_nums) = src.split_once(all_numbers, "|")?;
 
-            (win, mine)
+            Ok((parse_set(winners)?, parse_set(my_nums)?))
         })
         .collect()
 }
 
-fn part1(inp: &[(u128, u128)]) -> u32 {
-    inp.iter()
-        .filter_map(|(win, mine)| (win & mine).count_ones().checked_sub(1).map(|n| 1 << n))
-        .sum()
+pub(crate) fn part1(inp: &[(Numbers, Numbers)]) -> u32 {
+    checked::sum(inp.iter().filter_map(|(win, mine)| {
+        let doublings = (win & mine).count_ones().checked_sub(1)?;
+        Some(
+            1u32.checked_shl(doublings)
+                .expect("a card with over 32 matches is worth more than a u32"),
+        )
+    }))
 }
 
-fn part2(inp: &[(u128, u128)]) -> u32 {
-    inp.iter()
-        .enumerate()
-        .fold(vec![1; inp.len()], |mut n_copies, (i, (win, mine))| {
-            let matches = (win & mine).count_ones() as usize;
-            let copies = n_copies[i];
-            for card_count in n_copies.iter_mut().skip(i + 1).take(matches) {
-                *card_count += copies;
-            }
-            n_copies
-        })
-        .into_iter()
-        .sum()
+pub(crate) fn part2(inp: &[(Numbers, Numbers)]) -> u32 {
+    let n_copies =
+        inp.iter()
+            .enumerate()
+            .fold(vec![1; inp.len()], |mut n_copies, (i, (win, mine))| {
+                let matches = (win & mine).count_ones() as usize;
+                let copies = n_copies[i];
+                for card_count in n_copies.iter_mut().skip(i + 1).take(matches) {
+                    *card_count = checked::add(*card_count, copies);
+                }
+                n_copies
+            });
+    checked::sum(n_copies)
+}
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day04.rs"));
+
+    #[test]
+    fn lots_of_matches() {
+        let card = |n: u32| {
+            let nums: Vec<_> = (1..=n).map(|num| num.to_string()).collect();
+            format!("Card 1: {0} | {0}\n", nums.join(" ")).leak()
+        };
+        assert_eq!(part1(&parse_input(card(32)).unwrap()), 1 << 31);
+        assert!(std::panic::catch_unwind(|| part1(&parse_input(card(33)).unwrap())).is_err());
+        assert!(parse_input("Card 1: 128 | 1\n").is_err());
+    }
 }
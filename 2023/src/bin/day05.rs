@@ -1,20 +1,21 @@
-use anyhow::anyhow;
-use std::{mem, str::FromStr};
+use aoc_utils::harness::{self, Maybe};
+use aoc_utils::interval::{IntervalSet, RangeMap};
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{input_path, inputs};
+use std::str::FromStr;
 
-fn main() {
-    let inp = parse_input();
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day05.txt");
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+    harness::run(input, parse_input, part1, |inp| Maybe(part2(inp)))
 }
 
-fn parse_input() -> Almanac {
-    include_str!("../../inputs/day05.txt")
-        .parse()
-        .expect("Failed to parse input.")
+pub(crate) fn parse_input(inp: &'static str) -> Result<Almanac, ParseError> {
+    inp.parse()
 }
 
 #[derive(Debug)]
@@ -24,108 +25,124 @@ struct Mapping {
     len: u32,
 }
 
-impl Mapping {
-    #[allow(clippy::unnecessary_lazy_evaluations)]
-    fn map_backward(&self, inp: u32) -> Option<u32> {
-        (self.dst..self.dst + self.len)
-            .contains(&inp)
-            .then(|| inp - self.dst + self.src)
-    }
-}
-
 impl FromStr for Mapping {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (dst, src_len) = s
-            .split_once(' ')
-            .ok_or_else(|| anyhow!("Failed to split mapping."))?;
-
-        let (src, len) = src_len
-            .split_once(' ')
-            .ok_or_else(|| anyhow!("Failed to split mapping."))?;
+        let source = Source::new(s);
+        let (dst, src_len) = source.split_once(s, " ")?;
+        let (src, len) = source.split_once(src_len, " ")?;
 
         Ok(Self {
-            src: src.parse()?,
-            dst: dst.parse()?,
-            len: len.parse()?,
+            src: source.number(src)?,
+            dst: source.number(dst)?,
+            len: source.number(len)?,
         })
     }
 }
 
 #[derive(Debug)]
-struct Almanac {
-    seeds: Vec<u32>,
-    maps: Vec<Vec<Mapping>>,
+pub(crate) struct Almanac {
+    seeds: Vec<i64>,
+    maps: Vec<RangeMap>,
 }
 
 impl FromStr for Almanac {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines: Vec<_> = s.lines().collect();
+        let src = Source::new(s);
+        let mut blocks = s.trim_end().split("\n\n");
 
-        let seeds = lines[0]
+        let seeds_line = blocks.next().unwrap_or(src.end());
+        let seeds: Vec<_> = src
+            .strip_prefix(seeds_line, "seeds:")?
             .split_ascii_whitespace()
-            .skip(1)
-            .map(str::parse)
+            .map(|seed| src.number::<u32>(seed).map(i64::from))
             .collect::<Result<_, _>>()?;
-
-        let mut maps = vec![];
-        let mut curr_map = vec![];
-
-        let mut i = 3;
-        while i < lines.len() {
-            let line = lines[i];
-            i += 1;
-
-            if line.is_empty() {
-                maps.push(mem::take(&mut curr_map));
-                i += 1;
-                continue;
-            }
-
-            curr_map.push(line.parse()?);
+        if seeds.is_empty() {
+            return Err(src.error(seeds_line, "some seeds"));
         }
 
-        if !curr_map.is_empty() {
-            maps.push(curr_map);
-        }
+        let maps = blocks
+            .map(|block| {
+                let mut lines = block.lines();
+                src.strip_suffix(lines.next().unwrap_or(block), " map:")?;
+                lines
+                    .map(|line| {
+                        let mapping: Mapping = src.parse(line)?;
+                        let (src, dst) = (i64::from(mapping.src), i64::from(mapping.dst));
+                        Ok((src..src + i64::from(mapping.len), dst - src))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
 
         Ok(Self { seeds, maps })
     }
 }
 
 impl Almanac {
-    fn location_to_seed(&self, location: u32) -> u32 {
-        self.maps.iter().rev().fold(location, |num, map| {
-            map.iter()
-                .find_map(|mapping| mapping.map_backward(num))
-                .unwrap_or(num)
-        })
+    /// The lowest location any of `seeds` ends up at, following every map at once.
+    fn lowest_location(&self, seeds: IntervalSet) -> Option<i64> {
+        let seed_to_location = self
+            .maps
+            .iter()
+            .fold(RangeMap::new(), |combined, map| combined.then(map));
+        seed_to_location.apply(&seeds).min()
     }
 
-    fn in_seed_range(&self, seed: u32) -> bool {
-        self.seeds
-            .chunks(2)
-            .any(|chunk| (chunk[0]..chunk[0] + chunk[1]).contains(&seed))
+    /// The seeds read as pairs of a start and a length, if they pair up.
+    fn seed_ranges(&self) -> Option<IntervalSet> {
+        let pairs = self.seeds.chunks_exact(2);
+        if !pairs.remainder().is_empty() {
+            return None;
+        }
+        Some(pairs.map(|pair| pair[0]..pair[0] + pair[1]).collect())
     }
 }
 
-fn part1(inp: &Almanac) -> u32 {
-    (0..u32::MAX)
-        .find(|&location| {
-            let seed = inp.location_to_seed(location);
-            inp.seeds.contains(&seed)
-        })
-        .unwrap()
+pub(crate) fn part1(inp: &Almanac) -> i64 {
+    let seeds = inp.seeds.iter().map(|&seed| seed..seed + 1).collect();
+    inp.lowest_location(seeds)
+        .expect("the parser turns down an almanac without seeds")
 }
 
-fn part2(inp: &Almanac) -> u32 {
-    (0..u32::MAX)
-        .find(|&location| {
-            let seed = inp.location_to_seed(location);
-            inp.in_seed_range(seed)
-        })
-        .unwrap()
+/// With the seeds as ranges, which there's no answer for if they don't pair up or are all empty.
+pub(crate) fn part2(inp: &Almanac) -> Option<i64> {
+    inp.lowest_location(inp.seed_ranges()?)
+}
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).unwrap().to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day05.rs"));
+
+    #[test]
+    fn only_part_2_pairs_up_seeds() {
+        let almanac =
+            |seeds| parse_input(format!("seeds:{seeds}\n\nseed-to-soil map:\n50 98 2\n").leak());
+        assert!(almanac("").is_err());
+
+        let odd = almanac(" 79 14 55").unwrap();
+        assert_eq!(part1(&odd), 14);
+        assert_eq!(part2(&odd), None);
+
+        let zero = almanac(" 79 0").unwrap();
+        assert_eq!(part1(&zero), 0);
+        assert_eq!(part2(&zero), None);
+
+        let pair = almanac(" 99 2").unwrap();
+        assert_eq!(part1(&pair), 2);
+        assert_eq!(part2(&pair), Some(51));
+    }
 }
@@ -1,50 +1,68 @@
-use anyhow::anyhow;
-use std::{mem, str::FromStr};
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 
-fn main() {
-    let inp = parse_input();
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day06.txt");
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+    harness::run(input, parse_input, |inp| part1(inp), |inp| part2(inp))
 }
 
-fn parse_input() -> Vec<(u32, u32)> {
-    let (l1, l2) = include_str!("../../inputs/day06.txt")
-        .trim()
-        .split_once('\n')
-        .expect("Failed to get lines");
-
-    let times = l1
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(str::parse)
-        .map(Result::unwrap);
-
-    let dists = l2
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(str::parse)
-        .map(Result::unwrap);
-
-    times.zip(dists).collect()
+pub(crate) fn parse_input(inp: &'static str) -> Result<Vec<(u32, u32)>, ParseError> {
+    let src = Source::new(inp);
+    let (l1, l2) = src.split_once(inp.trim(), "\n")?;
+
+    let parse_row = |line: &str, header: &str| -> Result<Vec<u32>, ParseError> {
+        src.strip_prefix(line, header)?
+            .split_ascii_whitespace()
+            .map(|num| src.number(num))
+            .collect()
+    };
+    let times = parse_row(l1, "Time:")?;
+    let dists = parse_row(l2, "Distance:")?;
+
+    if times.len() != dists.len() {
+        return Err(src.error(l2, format!("{} distances, one for each time", times.len())));
+    }
+
+    Ok(times.into_iter().zip(dists).collect())
 }
 
-fn part1(inp: &[(u32, u32)]) -> u32 {
-    inp.iter()
-        .map(|&(time, record)| (0..time).filter(|t| t * (time - t) > record).count() as u32)
-        .product()
+pub(crate) fn part1(inp: &[(u32, u32)]) -> u32 {
+    checked::product(inp.iter().map(|&(time, record)| {
+        checked::cast((0..time).filter(|t| t * (time - t) > record).count())
+    }))
 }
 
-fn part2(inp: &[(u32, u32)]) -> u32 {
+pub(crate) fn part2(inp: &[(u32, u32)]) -> u32 {
     let (time, record) = inp.iter().fold((0, 0), |(at, ad), (t, d)| {
         (
-            at * 10_u64.pow(t.ilog10() + 1) + *t as u64,
-            ad * 10_u64.pow(d.ilog10() + 1) + *d as u64,
+            checked::add(checked::mul(at, 10_u64.pow(t.ilog10() + 1)), *t as u64),
+            checked::add(checked::mul(ad, 10_u64.pow(d.ilog10() + 1)), *d as u64),
         )
     });
 
-    (0..time).filter(|t| t * (time - t) > record).count() as u32
+    checked::cast(
+        (0..time)
+            .filter(|&t| checked::mul(t, time - t) > record)
+            .count(),
+    )
+}
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day06.rs"));
 }
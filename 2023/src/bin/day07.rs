@@ -1,30 +1,29 @@
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 use std::str::FromStr;
 
-fn main() {
-    let inp = parse_input();
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day07.txt");
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+    harness::run(input, parse_input, |inp| part1(inp), |inp| part2(inp))
 }
 
-fn parse_input() -> Vec<(Hand, u32)> {
-    include_str!("../../inputs/day07.txt")
-        .lines()
+pub(crate) fn parse_input(inp: &'static str) -> Result<Vec<(Hand, u32)>, ParseError> {
+    let src = Source::new(inp);
+    inp.lines()
         .map(|line| {
-            let (hand, bid) = line.split_once(' ').expect("Line in invalid format");
-            (
-                hand.parse().expect("Failed to parse hand"),
-                bid.parse().expect("Failed to parse bid"),
-            )
+            let (hand, bid) = src.split_once(line, " ")?;
+            Ok((src.parse(hand)?, src.number(bid)?))
         })
         .collect()
 }
 
 #[derive(Debug, Clone)]
-struct Hand {
+pub(crate) struct Hand {
     cards: [u8; 5],
 }
 
@@ -95,33 +94,46 @@ impl Hand {
 }
 
 impl FromStr for Hand {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cards: [u8; 5] = <[u8; 5]>::try_from(s.as_bytes())?.map(|c| match c {
-            b'2'..=b'9' => c - b'0',
-            b'T' => 10,
-            b'J' => 11,
-            b'Q' => 12,
-            b'K' => 13,
-            b'A' => 14,
-            _ => unreachable!("These are the only valid cards."),
-        });
+        let src = Source::new(s);
+        let hand = <[u8; 5]>::try_from(s.as_bytes()).map_err(|_| src.error(s, "5 cards"))?;
+
+        let mut cards = [0; 5];
+        for (i, (card, c)) in cards.iter_mut().zip(hand).enumerate() {
+            *card = match c {
+                b'2'..=b'9' => c - b'0',
+                b'T' => 10,
+                b'J' => 11,
+                b'Q' => 12,
+                b'K' => 13,
+                b'A' => 14,
+                _ => return Err(src.error_at(s, i, "a card from 2 to A")),
+            };
+        }
 
         Ok(Hand { cards })
     }
 }
 
-fn part1(inp: &[(Hand, u32)]) -> u32 {
+/// The total of each hand's bid times its rank, with `ranked` weakest first.
+fn winnings(ranked: &[(Hand, u32)]) -> u32 {
+    checked::sum(
+        ranked
+            .iter()
+            .enumerate()
+            .map(|(i, (_hand, bid))| checked::mul(checked::cast::<_, u32>(i) + 1, *bid)),
+    )
+}
+
+pub(crate) fn part1(inp: &[(Hand, u32)]) -> u32 {
     let mut inputs = inp.to_vec();
     inputs.sort_by_cached_key(|(hand, _)| (hand.kind(), hand.cards));
-    inputs
-        .iter()
-        .enumerate()
-        .fold(0, |acc, (i, (_hand, bid))| acc + (i as u32 + 1) * bid)
+    winnings(&inputs)
 }
 
-fn part2(inp: &[(Hand, u32)]) -> u32 {
+pub(crate) fn part2(inp: &[(Hand, u32)]) -> u32 {
     let mut inputs = inp.to_vec();
     inputs.sort_by_cached_key(|(hand, _)| {
         (
@@ -129,8 +141,20 @@ fn part2(inp: &[(Hand, u32)]) -> u32 {
             hand.cards.map(|c| if c == 11 { 0 } else { c }),
         )
     });
-    inputs
-        .iter()
-        .enumerate()
-        .fold(0, |acc, (i, (_hand, bid))| acc + (i as u32 + 1) * bid)
+    winnings(&inputs)
+}
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day07.rs"));
 }
@@ -1,75 +1,93 @@
+use aoc_utils::graph::{Graph, NodeId};
+use aoc_utils::number_theory::lcm;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 use std::collections::BTreeMap;
-use std::mem;
 
-fn main() {
-    let inp = parse_input();
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day08.txt");
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+    harness::run(input, parse_input, part1, part2)
 }
 
-fn parse_input() -> Network {
-    let lines: Vec<_> = include_str!("../../inputs/day08.txt").lines().collect();
+pub(crate) fn parse_input(inp: &'static str) -> Result<Network, ParseError> {
+    let src = Source::new(inp);
+    let mut lines = inp.lines();
 
-    let directions = lines[0];
+    let directions = lines.next().unwrap_or(src.end());
+    if directions.is_empty() {
+        return Err(src.error(directions, "some directions"));
+    }
+    if let Some(i) = directions.bytes().position(|b| b != b'L' && b != b'R') {
+        return Err(src.error_at(directions, i, "L or R"));
+    }
+    let blank = lines.next().unwrap_or(src.end());
+    if !blank.is_empty() {
+        return Err(src.error(blank, "a blank line"));
+    }
 
     // build the map in two passes, first find all keys
-    let name_to_index: BTreeMap<&str, usize> = lines
-        .iter()
-        .skip(2)
-        .map(|line| line.split_once(' ').unwrap().0)
-        .enumerate()
-        .map(|(a, b)| (b, a))
-        .collect();
-
-    let index_to_name = lines
-        .iter()
-        .skip(2)
-        .map(|line| line.split_once(' ').unwrap().0)
-        .collect();
-
-    // now build the map
-    let map = lines
-        .iter()
-        .skip(2)
+    let nodes: Vec<(&str, &str, &str)> = lines
         .map(|line| {
-            let (l, r) = line[7..15].split_once(", ").unwrap();
-            (name_to_index[l], name_to_index[r])
+            let (name, next) = src.split_once(line, " = (")?;
+            let (l, r) = src.split_once(src.strip_suffix(next, ")")?, ", ")?;
+            Ok((name, l, r))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
+
+    let mut map = Graph::directed();
+    for &(name, _, _) in &nodes {
+        if map.id(name).is_some() {
+            return Err(src.error(name, "a node not already in the map"));
+        }
+        map.intern(name);
+    }
 
-    Network {
-        directions,
-        index_to_name,
-        name_to_index,
-        map,
+    // now add the paths, left before right
+    for &(name, l, r) in &nodes {
+        let node = map.intern(name);
+        for next in [l, r] {
+            let next = map
+                .id(next)
+                .ok_or_else(|| src.error(next, "the name of a node"))?;
+            map.connect(node, next, ());
+        }
     }
+
+    Ok(Network { directions, map })
 }
 
 #[derive(Debug)]
-struct Network {
+pub(crate) struct Network {
     directions: &'static str,
-    index_to_name: Vec<&'static str>,
-    name_to_index: BTreeMap<&'static str, usize>,
-    map: Vec<(usize, usize)>,
+    map: Graph<&'static str>,
 }
 
-fn part1(inp: &Network) -> u32 {
-    let Network {
-        directions,
-        index_to_name: _,
-        name_to_index,
-        map,
-    } = inp;
+impl Network {
+    /// Where going `dir` from `pos` leads.
+    fn step(&self, pos: NodeId, dir: u8) -> NodeId {
+        let [(l, ()), (r, ())] = self.map.edges(pos) else {
+            unreachable!("every node has a left and a right");
+        };
+        if dir == b'L' {
+            *l
+        } else {
+            *r
+        }
+    }
+}
+
+pub(crate) fn part1(inp: &Network) -> u32 {
     let mut count = 0;
-    let mut pos = name_to_index["AAA"];
-    let end = name_to_index["ZZZ"];
-    for dir in directions.bytes().cycle() {
+    let mut pos = inp.map.id("AAA").unwrap();
+    let end = inp.map.id("ZZZ").unwrap();
+    for dir in inp.directions.bytes().cycle() {
         count += 1;
-        pos = if dir == b'L' { map[pos].0 } else { map[pos].1 };
+        pos = inp.step(pos, dir);
         if pos == end {
             break;
         }
@@ -78,15 +96,14 @@ fn part1(inp: &Network) -> u32 {
     count
 }
 
-fn find_orbit(start: usize, directions: &str, map: &[(usize, usize)]) -> usize {
+fn find_orbit(start: NodeId, inp: &Network) -> usize {
     let mut pos = start;
     let mut history = BTreeMap::new();
     let mut count = 0;
 
-    for (i, dir) in directions.bytes().enumerate().cycle() {
+    for (i, dir) in inp.directions.bytes().enumerate().cycle() {
         count += 1;
-        let (l, r) = map[pos];
-        pos = if dir == b'L' { l } else { r };
+        pos = inp.step(pos, dir);
 
         if let Some(offset) = history.insert((i, pos), count) {
             return count - offset;
@@ -96,39 +113,26 @@ fn find_orbit(start: usize, directions: &str, map: &[(usize, usize)]) -> usize {
     unreachable!(".cycle() creates infinite iterators");
 }
 
-fn part2(inp: &Network) -> u64 {
-    let Network {
-        directions,
-        index_to_name,
-        name_to_index,
-        map,
-    } = inp;
-
-    index_to_name
-        .iter()
-        .filter(|name| name.ends_with('A'))
-        .map(|name| find_orbit(name_to_index[name], directions, map) as u64)
-        .fold(1, lcm)
+pub(crate) fn part2(inp: &Network) -> u64 {
+    inp.map
+        .nodes()
+        .filter(|&node| inp.map[node].ends_with('A'))
+        .map(|node| checked::cast(find_orbit(node, inp)))
+        .try_fold(1, lcm)
+        .expect("the lcm of the orbits fits in a u64")
 }
 
-fn gcd(mut a: u64, mut b: u64) -> u64 {
-    if a == b {
-        return a;
-    }
-    if b > a {
-        mem::swap(&mut a, &mut b);
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
     }
-    loop {
-        if b == 0 {
-            break a;
-        }
 
-        let rmdr = a % b;
-        a = b;
-        b = rmdr;
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
     }
-}
 
-fn lcm(a: u64, b: u64) -> u64 {
-    a * b / gcd(a, b)
+    include!(concat!(env!("OUT_DIR"), "/examples/day08.rs"));
 }
@@ -1,48 +1,63 @@
-use std::str::FromStr;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 
-fn main() {
-    let inp = parse_input();
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day09.txt");
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+    harness::run(input, parse_input, |inp| part1(inp), |inp| part2(inp))
 }
 
-fn parse_input() -> Vec<Vec<i64>> {
-    include_str!("../../inputs/day09.txt")
-        .lines()
+pub(crate) fn parse_input(inp: &'static str) -> Result<Vec<Vec<i64>>, ParseError> {
+    let src = Source::new(inp);
+    inp.lines()
         .map(|line| {
             line.split_ascii_whitespace()
-                .map(str::parse)
-                .collect::<Result<_, _>>()
-                .expect("Failed to parse line")
+                .map(|num| src.number(num))
+                .collect()
         })
         .collect()
 }
 
 fn yeet(dataset: &[Vec<i64>], accumulator: fn(i64, Vec<i64>) -> i64) -> i64 {
-    dataset
-        .iter()
-        .map(|history| {
-            let mut compendium = vec![history.to_owned()];
-            while !compendium.last().unwrap().iter().all(|&x| x == 0) {
-                let prev = compendium.last().unwrap();
-                let next = prev.windows(2).map(|pair| pair[1] - pair[0]).collect();
-                _ = prev;
-                compendium.push(next);
-            }
-
-            compendium.into_iter().rev().fold(0i64, accumulator)
-        })
-        .sum::<i64>()
+    checked::sum(dataset.iter().map(|history| {
+        let mut compendium = vec![history.to_owned()];
+        while !compendium.last().unwrap().iter().all(|&x| x == 0) {
+            let prev = compendium.last().unwrap();
+            let next = prev
+                .windows(2)
+                .map(|pair| checked::sub(pair[1], pair[0]))
+                .collect();
+            _ = prev;
+            compendium.push(next);
+        }
+
+        compendium.into_iter().rev().fold(0i64, accumulator)
+    }))
+}
+
+pub(crate) fn part1(inp: &[Vec<i64>]) -> i64 {
+    yeet(inp, |acc, row| checked::add(acc, *row.last().unwrap()))
 }
 
-fn part1(inp: &[Vec<i64>]) -> i64 {
-    yeet(inp, |acc, row| acc + row.last().unwrap())
+pub(crate) fn part2(inp: &[Vec<i64>]) -> i64 {
+    yeet(inp, |acc, row| checked::sub(*row.first().unwrap(), acc))
 }
 
-fn part2(inp: &[Vec<i64>]) -> i64 {
-    yeet(inp, |acc, row| row.first().unwrap() - acc)
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day09.rs"));
 }
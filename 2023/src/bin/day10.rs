@@ -1,59 +1,55 @@
+use aoc_utils::grid::{Grid, Pos};
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 use std::collections::BTreeSet;
 
-type GridRef = (usize, usize);
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day10.txt");
 
-fn main() {
-    let inp = parse_input();
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    let (path, p1) = part1(&inp);
-    let p2 = part2(&inp, path);
-
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+    harness::run_both(input, parse_input, |inp| {
+        let (path, p1) = part1(&inp);
+        (p1, part2(&inp, path))
+    })
 }
 
-fn parse_input() -> Grid {
-    let tiles = include_str!("../../inputs/day10.txt")
-        .lines()
-        .map(|line| line.as_bytes().to_owned())
-        .collect();
+pub(crate) fn parse_input(inp: &'static str) -> Result<Pipes, ParseError> {
+    let src = Source::new(inp);
+    let tiles = Grid::parse(inp, "a pipe, \".\" or S", |c| {
+        matches!(c, '|' | '-' | 'L' | 'J' | '7' | 'F' | '.' | 'S').then_some(c as u8)
+    })?;
 
-    Grid { tiles }
-}
+    let mut starts = inp.match_indices('S').map(|(i, _)| &inp[i..]);
+    if starts.next().is_none() {
+        return Err(src.error(src.end(), "a start tile S"));
+    }
+    if let Some(second) = starts.next() {
+        return Err(src.error(second, "only one start"));
+    }
 
-struct Grid {
-    tiles: Vec<Vec<u8>>,
+    Ok(Pipes { tiles })
 }
 
-impl Grid {
-    fn find_start(&self) -> GridRef {
-        self.tiles
-            .iter()
-            .enumerate()
-            .find_map(|(row, line)| line.iter().position(|c| *c == b'S').map(|col| (row, col)))
-            .unwrap()
-    }
-
-    fn rows(&self) -> usize {
-        self.tiles.len()
-    }
+pub(crate) struct Pipes {
+    tiles: Grid<u8>,
+}
 
-    fn cols(&self) -> usize {
-        self.tiles[0].len()
+impl Pipes {
+    fn find_start(&self) -> Pos {
+        self.tiles.position(|c| *c == b'S').unwrap()
     }
 
-    fn cardinal_directions(&self, (row, col): GridRef) -> [Option<GridRef>; 4] {
-        let up = row.checked_sub(1).map(|r| (r, col));
-        let down = (row + 1 < self.rows()).then_some((row + 1, col));
-        let left = col.checked_sub(1).map(|c| (row, c));
-        let right = (col + 1 < self.cols()).then_some((row, col + 1));
-        [up, down, left, right]
+    fn cardinal_directions(&self, pos: Pos) -> [Option<Pos>; 4] {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)].map(|delta| self.tiles.offset(pos, delta))
     }
 
-    fn connections(&self, (row, col): GridRef) -> Option<[GridRef; 2]> {
+    fn connections(&self, (row, col): Pos) -> Option<[Pos; 2]> {
         let [up, down, left, right] = self.cardinal_directions((row, col));
 
-        match self.tiles[row][col] {
+        match self.tiles[(row, col)] {
             b'|' => Some([up?, down?]),
             b'-' => Some([left?, right?]),
             b'L' => Some([up?, right?]),
@@ -76,8 +72,8 @@ impl Grid {
         }
     }
 
-    fn categorise_start(&self, (row, col): GridRef) -> u8 {
-        debug_assert_eq!(self.tiles[row][col], b'S');
+    fn categorise_start(&self, (row, col): Pos) -> u8 {
+        debug_assert_eq!(self.tiles[(row, col)], b'S');
 
         // first find connections
         let connections = self
@@ -105,7 +101,7 @@ impl Grid {
     }
 }
 
-fn part1(inp: &Grid) -> (Vec<GridRef>, u32) {
+pub(crate) fn part1(inp: &Pipes) -> (Vec<Pos>, u32) {
     let start = inp.find_start();
 
     // build the entire loop
@@ -124,15 +120,15 @@ fn part1(inp: &Grid) -> (Vec<GridRef>, u32) {
         path.push(next);
     }
 
-    let ans = path.len() as u32 / 2;
+    let ans = checked::cast::<_, u32>(path.len()) / 2;
     (path, ans)
 }
 
-fn part2(inp: &Grid, path: Vec<GridRef>) -> u32 {
-    let path_set: BTreeSet<GridRef> = path.iter().copied().collect();
+pub(crate) fn part2(inp: &Pipes, path: Vec<Pos>) -> u32 {
+    let path_set: BTreeSet<Pos> = path.iter().copied().collect();
 
     let mut count = 0;
-    for (row, row_tiles) in inp.tiles.iter().enumerate() {
+    for (row, row_tiles) in inp.tiles.rows().enumerate() {
         let mut in_region = false;
         for (col, mut tile) in row_tiles.iter().copied().enumerate() {
             if tile == b'S' {
@@ -151,3 +147,20 @@ fn part2(inp: &Grid, path: Vec<GridRef>) -> u32 {
 
     count
 }
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).1.to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        let inp = parse_input(in_str).unwrap();
+        let (path, _) = part1(&inp);
+        part2(&inp, path).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day10.rs"));
+}
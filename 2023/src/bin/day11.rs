@@ -1,31 +1,52 @@
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 use itertools::Itertools;
 
-fn main() {
-    let (inp1, inp2) = parse_input();
+/// The position of each galaxy once the empty rows and columns have grown.
+type Galaxies = Vec<(u64, u64)>;
 
-    let p1 = solve(&inp1);
-    let p2 = solve(&inp2);
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day11.txt");
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
+
+    harness::run(
+        input,
+        parse_input,
+        |(inp1, _)| solve(inp1),
+        |(_, inp2)| solve(inp2),
+    )
 }
 
-fn parse_input() -> (Vec<(u64, u64)>, Vec<(u64, u64)>) {
-    let rows: Vec<_> = include_str!("../../inputs/day11.txt").lines().collect();
+pub(crate) fn parse_input(inp: &'static str) -> Result<(Galaxies, Galaxies), ParseError> {
+    let src = Source::new(inp);
+    let rows: Vec<_> = inp.lines().collect();
+    let Some(width) = rows.first().map(|row| row.len()) else {
+        return Err(src.error(inp, "an image"));
+    };
 
     // iterate all cells tracking whether any cell was taken in each col/row
     let mut row_taken = vec![false; rows.len()];
-    let mut col_taken = vec![false; rows[0].len()];
+    let mut col_taken = vec![false; width];
 
     // collect all galaxies as well
     let mut galaxies = vec![];
 
     for (row, row_chars) in rows.iter().enumerate() {
+        if row_chars.len() != width {
+            return Err(src.error(row_chars, format!("a row {width} wide")));
+        }
         for (col, tile) in row_chars.bytes().enumerate() {
-            if tile == b'#' {
-                row_taken[row] = true;
-                col_taken[col] = true;
-                galaxies.push((row as u64, col as u64));
+            match tile {
+                b'#' => {
+                    row_taken[row] = true;
+                    col_taken[col] = true;
+                    galaxies.push((row as u64, col as u64));
+                }
+                b'.' => {}
+                _ => return Err(src.error_at(row_chars, col, "\".\" or \"#\"")),
             }
         }
     }
@@ -57,20 +78,32 @@ fn parse_input() -> (Vec<(u64, u64)>, Vec<(u64, u64)>) {
     }
 
     for (row, col) in gal2.iter_mut() {
-        *row += row_offsets[*row as usize] * 999_999;
-        *col += col_offsets[*col as usize] * 999_999;
+        *row = checked::add(*row, checked::mul(row_offsets[*row as usize], 999_999));
+        *col = checked::add(*col, checked::mul(col_offsets[*col as usize], 999_999));
     }
 
-    (gal1, gal2)
+    Ok((gal1, gal2))
 }
 
-fn solve(inp: &[(u64, u64)]) -> u64 {
-    inp.iter()
-        .combinations(2)
-        .map(|galaxies| {
-            let (x1, y1) = *galaxies[0];
-            let (x2, y2) = *galaxies[1];
-            x1.abs_diff(x2) + y1.abs_diff(y2)
-        })
-        .sum()
+pub(crate) fn solve(inp: &[(u64, u64)]) -> u64 {
+    checked::sum(inp.iter().combinations(2).map(|galaxies| {
+        let (x1, y1) = *galaxies[0];
+        let (x2, y2) = *galaxies[1];
+        checked::add(x1.abs_diff(x2), y1.abs_diff(y2))
+    }))
+}
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        solve(&parse_input(in_str).unwrap().0).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        solve(&parse_input(in_str).unwrap().1).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day11.rs"));
 }
@@ -1,38 +1,49 @@
-use rustc_hash::FxHashMap;
-use std::cell::RefCell;
+use aoc_utils::memo::Memo;
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
+use rustc_hash::FxHasher;
+use std::hash::BuildHasherDefault;
 
-fn main() {
-    let inp = parse_input();
+/// A row of springs, and the sizes of its groups of damaged ones.
+type Record = (&'static [u8], Vec<usize>);
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day12.txt");
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
+
+    harness::run(input, parse_input, |inp| part1(inp), |inp| part2(inp))
 }
 
-fn parse_input() -> Vec<(&'static [u8], Vec<usize>)> {
-    include_str!("../../inputs/day12.txt")
-        .lines()
+pub(crate) fn parse_input(inp: &'static str) -> Result<Vec<Record>, ParseError> {
+    let src = Source::new(inp);
+    inp.lines()
         .map(|line| {
-            let (record, nums) = line.split_once(' ').expect("Failed to split record");
-            (
+            let (record, nums) = src.split_once(line, " ")?;
+            if let Some(i) = record.bytes().position(|b| !b"?.#".contains(&b)) {
+                return Err(src.error_at(record, i, "?, . or #"));
+            }
+
+            Ok((
                 record.as_bytes(),
                 nums.split(',')
-                    .map(str::parse)
-                    .collect::<Result<_, _>>()
-                    .expect("Failed to parse numbers"),
-            )
+                    .map(|num| src.number(num))
+                    .collect::<Result<_, _>>()?,
+            ))
         })
         .collect()
 }
 
-fn enumerate_possible_block_placements(blocks: Vec<&'static [u8]>, groups: &[usize]) -> u64 {
-    type FuncInput = (Vec<&'static [u8]>, Vec<usize>);
-    thread_local! {
-        static MEMO: RefCell<FxHashMap<FuncInput, u64>> = RefCell::new(FxHashMap::default());
-    };
+/// Ways to place the remaining groups in the remaining blocks.
+type Placements = Memo<(Vec<&'static [u8]>, Vec<usize>), u64, BuildHasherDefault<FxHasher>>;
 
+fn enumerate_possible_block_placements(
+    memo: &mut Placements,
+    blocks: Vec<&'static [u8]>,
+    groups: &[usize],
+) -> u64 {
     // if there are no blocks and no groups then there is only one way
     if blocks.is_empty() {
         return groups.is_empty().into();
@@ -46,69 +57,63 @@ fn enumerate_possible_block_placements(blocks: Vec<&'static [u8]>, groups: &[usi
     }
 
     // check the memoisation cache after the trivial cases
-    if let Some(stored) =
-        MEMO.with_borrow(|memo| memo.get(&(blocks.clone(), groups.to_owned())).copied())
-    {
-        return stored;
-    }
-
-    // enumerate starting positions until they enumerate to zero remaining
-    let fixing_group_len = groups[0];
-    let remaining_groups = &groups[1..];
-    let mut total = 0;
-
-    for (idx, &block) in blocks.iter().enumerate() {
-        let can_continue = !block.contains(&b'#');
-        if block.len() < fixing_group_len {
-            if can_continue {
-                continue;
-            } else {
-                break;
-            }
-        }
-
-        for offset in 0..=block.len() - fixing_group_len {
-            let left_valid = offset
-                .checked_sub(1)
-                .map(|i| !block[..=i].contains(&b'#'))
-                .unwrap_or(true);
-            let right_valid = block
-                .get(offset + fixing_group_len)
-                .map(|&b| b != b'#')
-                .unwrap_or(true);
-
-            // if this is a valid position to fix the group at then recurse with the blocks adjusted
-            if left_valid && right_valid {
-                let mut sub_blocks = blocks[idx..].to_owned();
-
-                // if the group we just placed ends the block then remove it
-                let fixed_space = offset + fixing_group_len + 1;
-                if sub_blocks[0].len() <= fixed_space {
-                    sub_blocks.remove(0);
+    memo.get((blocks, groups.to_owned()), |memo, (blocks, groups)| {
+        // enumerate starting positions until they enumerate to zero remaining
+        let fixing_group_len = groups[0];
+        let remaining_groups = &groups[1..];
+        let mut total = 0;
+
+        for (idx, &block) in blocks.iter().enumerate() {
+            let can_continue = !block.contains(&b'#');
+            if block.len() < fixing_group_len {
+                if can_continue {
+                    continue;
                 } else {
-                    // otherwise cut off the "fixed" portion of the first block
-                    sub_blocks[0] = &sub_blocks[0][fixed_space..];
+                    break;
                 }
+            }
 
-                total += enumerate_possible_block_placements(sub_blocks, remaining_groups);
+            for offset in 0..=block.len() - fixing_group_len {
+                let left_valid = offset
+                    .checked_sub(1)
+                    .map(|i| !block[..=i].contains(&b'#'))
+                    .unwrap_or(true);
+                let right_valid = block
+                    .get(offset + fixing_group_len)
+                    .map(|&b| b != b'#')
+                    .unwrap_or(true);
+
+                // if this is a valid position to fix the group at then recurse with the blocks adjusted
+                if left_valid && right_valid {
+                    let mut sub_blocks = blocks[idx..].to_owned();
+
+                    // if the group we just placed ends the block then remove it
+                    let fixed_space = offset + fixing_group_len + 1;
+                    if sub_blocks[0].len() <= fixed_space {
+                        sub_blocks.remove(0);
+                    } else {
+                        // otherwise cut off the "fixed" portion of the first block
+                        sub_blocks[0] = &sub_blocks[0][fixed_space..];
+                    }
+
+                    total = checked::add(
+                        total,
+                        enumerate_possible_block_placements(memo, sub_blocks, remaining_groups),
+                    );
+                }
             }
-        }
 
-        // if the block contains a hashtag the fixed block MUST go there
-        if !can_continue {
-            break;
+            // if the block contains a hashtag the fixed block MUST go there
+            if !can_continue {
+                break;
+            }
         }
-    }
 
-    // store the result in the cache
-    MEMO.with_borrow_mut(|memo| {
-        memo.insert((blocks.clone(), groups.to_owned()), total);
-    });
-
-    total
+        total
+    })
 }
 
-fn count_arrangements(s: &'static [u8], mut groups: &[usize]) -> u64 {
+fn count_arrangements(memo: &mut Placements, s: &'static [u8], mut groups: &[usize]) -> u64 {
     // first determine any fixed blocks
     let mut blocks: Vec<_> = s
         .split(|&x| x == b'.')
@@ -140,23 +145,38 @@ fn count_arrangements(s: &'static [u8], mut groups: &[usize]) -> u64 {
         return 1;
     }
 
-    enumerate_possible_block_placements(blocks, groups)
+    enumerate_possible_block_placements(memo, blocks, groups)
 }
 
-fn part1(inp: &[(&'static [u8], Vec<usize>)]) -> u64 {
-    inp.iter()
-        .map(|(record, nums)| count_arrangements(record, nums))
-        .sum()
+pub(crate) fn part1(inp: &[Record]) -> u64 {
+    let mut memo = Placements::default();
+    checked::sum(
+        inp.iter()
+            .map(|(record, nums)| count_arrangements(&mut memo, record, nums)),
+    )
 }
 
-fn part2(inp: &[(&'static [u8], Vec<usize>)]) -> u64 {
-    inp.iter()
-        .map(|(record, nums)| {
-            // memory is cheap :)
-            let unfolded_record: &'static [u8] =
-                Box::new([*record].repeat(5).join(&b"?"[..])).leak();
-            let unfolded_nums = nums.repeat(5);
-            count_arrangements(unfolded_record, &unfolded_nums)
-        })
-        .sum()
+pub(crate) fn part2(inp: &[Record]) -> u64 {
+    let mut memo = Placements::default();
+    checked::sum(inp.iter().map(|(record, nums)| {
+        // memory is cheap :)
+        let unfolded_record: &'static [u8] = Box::new([*record].repeat(5).join(&b"?"[..])).leak();
+        let unfolded_nums = nums.repeat(5);
+        count_arrangements(&mut memo, unfolded_record, &unfolded_nums)
+    }))
+}
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day12.rs"));
 }
@@ -1,29 +1,43 @@
-#![feature(never_type, unwrap_infallible)]
-
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 use std::str::FromStr;
 
-fn main() {
-    let inp = parse_input();
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day13.txt");
 
-    let p1 = solve(&inp, 0);
-    let p2 = solve(&inp, 1);
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+    harness::run(input, parse_input, |inp| solve(inp, 0), |inp| solve(inp, 1))
 }
 
 #[derive(Debug)]
-struct Pattern {
+pub(crate) struct Pattern {
     tiles: Vec<Vec<bool>>,
 }
 
 impl FromStr for Pattern {
-    type Err = !;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
+        let width = s.lines().next().map_or(0, str::len);
+        if width == 0 {
+            return Err(src.error(s, "a pattern"));
+        }
+
         let tiles = s
             .lines()
-            .map(|line| line.bytes().map(|x| x == b'#').collect())
-            .collect();
+            .map(|line| {
+                if let Some(i) = line.bytes().position(|b| b != b'.' && b != b'#') {
+                    return Err(src.error_at(line, i, "\".\" or \"#\""));
+                }
+                if line.len() != width {
+                    return Err(src.error(line, format!("a row {width} wide")));
+                }
+                Ok(line.bytes().map(|x| x == b'#').collect())
+            })
+            .collect::<Result<_, _>>()?;
 
         Ok(Self { tiles })
     }
@@ -80,22 +94,37 @@ impl Pattern {
     }
 }
 
-fn parse_input() -> Vec<Pattern> {
-    include_str!("../../inputs/day13.txt")
+pub(crate) fn parse_input(inp: &'static str) -> Result<Vec<Pattern>, ParseError> {
+    let src = Source::new(inp);
+    inp.trim_end()
         .split("\n\n")
-        .map(|block| block.parse().into_ok())
+        .map(|block| src.parse(block))
         .collect()
 }
 
-fn solve(inp: &[Pattern], error_target: usize) -> u32 {
-    inp.iter()
-        .map(|pat| {
-            let (is_horiz, pos) = pat.find_reflection(error_target);
-            if is_horiz {
-                pos as u32 * 100
-            } else {
-                pos as u32
-            }
-        })
-        .sum()
+pub(crate) fn solve(inp: &[Pattern], error_target: usize) -> u32 {
+    checked::sum(inp.iter().map(|pat| {
+        let (is_horiz, pos) = pat.find_reflection(error_target);
+        let pos = checked::cast(pos);
+        if is_horiz {
+            checked::mul(pos, 100)
+        } else {
+            pos
+        }
+    }))
+}
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        solve(&parse_input(in_str).unwrap(), 0).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        solve(&parse_input(in_str).unwrap(), 1).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day13.rs"));
 }
@@ -1,38 +1,38 @@
+use aoc_utils::grid::{Grid, Pos};
+use aoc_utils::parse::ParseError;
+use aoc_utils::record::{Palette, Recorder};
+use aoc_utils::{checked, cycle, harness, input_path, inputs};
 use core::fmt;
-use core::fmt::Write;
-use core::hash::Hash;
-use std::collections::HashMap;
 
-fn main() {
-    let inp = parse_input();
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day14.txt");
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+    harness::run(input, parse_input, part1, part2)
 }
 
-fn parse_input() -> Vec<&'static [u8]> {
-    include_str!("../../inputs/day14.txt")
-        .lines()
-        .map(|line| line.as_bytes())
-        .collect()
+pub(crate) fn parse_input(inp: &'static str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(inp, "O, # or .", |c| {
+        matches!(c, 'O' | '#' | '.').then_some(c as u8)
+    })
 }
 
-fn part1(inp: &[&[u8]]) -> u32 {
+pub(crate) fn part1(inp: &Grid<u8>) -> u32 {
     let mut total = 0;
 
-    for col in 0..inp[0].len() {
+    for col in 0..inp.width() {
         let mut top_free = 0;
-        for (i, row) in inp.iter().enumerate() {
-            match row[col] {
+        for (i, tile) in inp.column(col).enumerate() {
+            match tile {
                 b'.' => (),
                 // this isn't necessarily correct but we will hit the # and correct it before we
                 // hit an o
                 b'#' => top_free = i + 1,
                 b'O' => {
-                    total += inp.len() - top_free;
+                    total = checked::add(total, inp.height() - top_free);
                     top_free += 1;
                 }
                 _ => unreachable!("other characters are cringe"),
@@ -40,53 +40,38 @@ fn part1(inp: &[&[u8]]) -> u32 {
         }
     }
 
-    total as u32
+    checked::cast(total)
 }
 
-#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Platform {
-    tiles: Vec<u8>,
-    row_width: usize,
+    tiles: Grid<u8>,
 }
 
 impl fmt::Display for Platform {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (i, c) in self.tiles.iter().enumerate() {
-            f.write_char(*c as char)?;
-            if (i + 1) % self.row_width == 0 {
-                f.write_char('\n')?;
-            }
-        }
-
-        Ok(())
-    }
-}
-
-impl From<&[&[u8]]> for Platform {
-    fn from(value: &[&[u8]]) -> Self {
-        Self {
-            tiles: value.iter().flat_map(|row| row.iter().copied()).collect(),
-            row_width: value[0].len(),
-        }
+        write!(f, "{}", self.tiles.map(|&c| c as char))
     }
 }
 
 impl Platform {
-    fn compute_idx(&self, (row, col): (usize, usize)) -> usize {
-        row * self.row_width + col
+    fn palette() -> Palette<u8> {
+        Palette::new([30, 30, 30])
+            .with(b'#', [120, 120, 130])
+            .with(b'O', [220, 200, 150])
     }
 
-    fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
-        let a = self.compute_idx(a);
-        let b = self.compute_idx(b);
+    fn swap(&mut self, a: Pos, b: Pos) {
+        let a = self.tiles.index_of(a);
+        let b = self.tiles.index_of(b);
 
         if a == b {
             return;
         }
 
-        debug_assert_eq!(self.tiles[a], b'O');
-        debug_assert_eq!(self.tiles[b], b'.');
-        self.tiles.swap(a, b);
+        debug_assert_eq!(self.tiles.cells()[a], b'O');
+        debug_assert_eq!(self.tiles.cells()[b], b'.');
+        self.tiles.cells_mut().swap(a, b);
     }
 
     fn spin(&mut self) {
@@ -97,8 +82,8 @@ impl Platform {
     }
 
     fn tilt(&mut self, direction: Direction) {
-        let no_cols = self.tiles.len() / self.row_width;
-        let no_rows = self.row_width;
+        let no_cols = self.tiles.width();
+        let no_rows = self.tiles.height();
 
         let (outer_lim, inner_lim, rev_inner) = match direction {
             Direction::North => (no_cols, no_rows, false),
@@ -115,12 +100,12 @@ impl Platform {
                 }
 
                 #[rustfmt::skip]
-                let tile_idx = match direction {
-                    Direction::East  | Direction::West  => self.compute_idx((o, i)),
-                    Direction::North | Direction::South => self.compute_idx((i, o)),
+                let tile_pos = match direction {
+                    Direction::East  | Direction::West  => (o, i),
+                    Direction::North | Direction::South => (i, o),
                 };
 
-                match self.tiles[tile_idx] {
+                match self.tiles[tile_pos] {
                     b'.' => (),
                     b'#' => {
                         top_free = if rev_inner {
@@ -150,16 +135,12 @@ impl Platform {
     }
 
     fn north_weight(&self) -> u32 {
-        self.tiles
-            .iter()
-            .enumerate()
-            .filter_map(|(i, c)| (*c == b'O').then_some(i))
-            .map(|i| {
-                let row = i / self.row_width;
-                let rows = self.tiles.len() / self.row_width;
-                (rows - row) as u32
-            })
-            .sum()
+        checked::sum(
+            self.tiles
+                .iter()
+                .filter(|&(_, c)| *c == b'O')
+                .map(|((row, _), _)| checked::cast(self.tiles.height() - row)),
+        )
     }
 }
 
@@ -171,22 +152,42 @@ enum Direction {
     East,
 }
 
-fn part2(inp: &[&[u8]]) -> u32 {
-    const LIMIT: usize = 1_000_000_000;
-    let mut plat: Platform = inp.into();
-
-    let mut seen = vec![plat.clone()];
-    let mut state_to_last_seen = HashMap::new();
-    for i in 0..LIMIT {
+pub(crate) fn part2(inp: &Grid<u8>) -> u32 {
+    const LIMIT: u64 = 1_000_000_000;
+    let spin = |plat: &Platform| {
+        let mut plat = plat.clone();
         plat.spin();
+        plat
+    };
+
+    let platform = Platform { tiles: inp.clone() };
+    // a spin moves every rock, so each platform is only spun once while looking for the cycle
+    let found = cycle::hashed(platform.clone(), spin);
+    if let Some(mut recorder) = Recorder::requested("part2", Platform::palette()) {
+        // every spin up to where the platform starts repeating, and once round the loop
+        (0..found.start + found.period).fold(platform.clone(), |platform, _| {
+            recorder.frame(&platform.tiles);
+            spin(&platform)
+        });
+        recorder.finish();
+    }
 
-        if let Some(last_seen) = state_to_last_seen.insert(plat.clone(), i) {
-            let period = i - last_seen;
-            return seen[last_seen + ((LIMIT - last_seen) % period)].north_weight();
-        }
+    (0..found.equivalent_step(LIMIT))
+        .fold(platform, |platform, _| spin(&platform))
+        .north_weight()
+}
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
 
-        seen.push(plat.clone());
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
     }
 
-    plat.north_weight()
+    include!(concat!(env!("OUT_DIR"), "/examples/day14.rs"));
 }
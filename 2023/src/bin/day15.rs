@@ -1,31 +1,50 @@
-fn main() {
-    let inp = parse_input();
+use aoc_utils::parse::{ParseError, Source};
+use aoc_utils::{checked, harness, input_path, inputs};
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day15.txt");
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
+
+    harness::run(input, parse_input, |inp| part1(inp), |inp| part2(inp))
 }
 
-fn parse_input() -> Vec<&'static str> {
-    include_str!("../../inputs/day15.txt")
-        .trim()
+pub(crate) fn parse_input(inp: &'static str) -> Result<Vec<&'static str>, ParseError> {
+    let src = Source::new(inp);
+    inp.trim()
         .split(',')
+        .map(|step| {
+            if let Some((label, focal_len)) = step.split_once('=') {
+                check_label(&src, label)?;
+                src.number::<u8>(focal_len)?;
+            } else {
+                check_label(&src, src.strip_suffix(step, "-")?)?;
+            }
+            Ok(step)
+        })
         .collect()
 }
 
+fn check_label(src: &Source, label: &str) -> Result<(), ParseError> {
+    if label.is_empty() || !label.bytes().all(|b| b.is_ascii_lowercase()) {
+        return Err(src.error(label, "a label"));
+    }
+    Ok(())
+}
+
 fn hash(data: &str) -> usize {
     data.bytes()
         .fold(0u8, |state, c| (state.wrapping_add(c)).wrapping_mul(17))
         .into()
 }
 
-fn part1(inp: &[&str]) -> u32 {
-    inp.iter().map(|step| hash(step) as u32).sum()
+pub(crate) fn part1(inp: &[&str]) -> u32 {
+    checked::sum(inp.iter().map(|step| checked::cast::<_, u32>(hash(step))))
 }
 
-fn part2(inp: &[&str]) -> u32 {
+pub(crate) fn part2(inp: &[&str]) -> u32 {
     let mut map = vec![];
     map.resize_with(256, Vec::new);
 
@@ -45,14 +64,28 @@ fn part2(inp: &[&str]) -> u32 {
         }
     }
 
-    map.into_iter()
-        .enumerate()
-        .map(|(i, r#box)| {
-            r#box
-                .into_iter()
-                .enumerate()
-                .map(|(j, (_, focal_len))| (i as u32 + 1) * (j as u32 + 1) * focal_len as u32)
-                .sum::<u32>()
-        })
-        .sum()
+    checked::sum(map.into_iter().enumerate().map(|(i, r#box)| {
+        checked::sum(r#box.into_iter().enumerate().map(|(j, (_, focal_len))| {
+            checked::product([
+                checked::cast::<_, u32>(i) + 1,
+                checked::cast::<_, u32>(j) + 1,
+                u32::from(focal_len),
+            ])
+        }))
+    }))
+}
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day15.rs"));
 }
@@ -1,20 +1,22 @@
+use aoc_utils::grid::{Grid, Pos};
+use aoc_utils::parse::ParseError;
+use aoc_utils::{checked, harness, input_path, inputs};
 use std::collections::BTreeSet;
 
-fn main() {
-    let inp = parse_input();
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day16.txt");
 
-    let p1 = part1(&inp);
-    let p2 = part2(&inp);
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    println!("Part 1: {p1}");
-    println!("Part 2: {p2}");
+    harness::run(input, parse_input, part1, part2)
 }
 
-fn parse_input() -> Vec<&'static [u8]> {
-    include_str!("../../inputs/day16.txt")
-        .lines()
-        .map(|line| line.as_bytes())
-        .collect()
+pub(crate) fn parse_input(inp: &'static str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(inp, "a mirror, splitter or \".\"", |c| {
+        matches!(c, '.' | '/' | '\\' | '|' | '-').then_some(c as u8)
+    })
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -27,16 +29,14 @@ enum Direction {
 
 impl Direction {
     #[rustfmt::skip]
-    fn step(&self, grid: &[&[u8]], x: usize, y: usize) -> Option<(usize, usize)> {
-        let max_x = grid.len() - 1;
-        let max_y = grid[0].len() - 1;
-        match self {
-            Self::Up    if y != 0    => Some((x, y - 1)),
-            Self::Down  if y < max_y => Some((x, y + 1)),
-            Self::Left  if x != 0    => Some((x - 1, y)),
-            Self::Right if x < max_x => Some((x + 1, y)),
-            _ => None
-        }
+    fn step(&self, grid: &Grid<u8>, pos: Pos) -> Option<Pos> {
+        let delta = match self {
+            Self::Up    => (-1, 0),
+            Self::Down  => (1, 0),
+            Self::Left  => (0, -1),
+            Self::Right => (0, 1),
+        };
+        grid.offset(pos, delta)
     }
 
     #[rustfmt::skip]
@@ -56,42 +56,41 @@ impl Direction {
     }
 }
 
-fn count_energised(inp: &[&[u8]], init: (usize, usize, Direction)) -> u32 {
-    let mut energised = Vec::new();
-    energised.resize_with(inp.len(), || vec![false; inp[0].len()]);
+fn count_energised(inp: &Grid<u8>, init: (Pos, Direction)) -> u32 {
+    let mut energised = inp.map(|_| false);
 
     let mut to_explore = vec![init];
     let mut explored = BTreeSet::new();
-    while let Some((x, y, dir)) = to_explore.pop() {
-        energised[y][x] = true;
-        if !explored.insert((x, y, dir.clone())) {
+    while let Some((pos, dir)) = to_explore.pop() {
+        energised[pos] = true;
+        if !explored.insert((pos, dir.clone())) {
             continue;
         }
 
         let next_states = dir
-            .encounter(inp[y][x])
+            .encounter(inp[pos])
             .into_iter()
             .flatten()
-            .filter_map(|dir| dir.step(inp, x, y).map(|(x, y)| (x, y, dir)));
+            .filter_map(|dir| dir.step(inp, pos).map(|pos| (pos, dir)));
 
         to_explore.extend(next_states);
     }
 
-    energised.into_iter().flatten().filter(|x| *x).count() as u32
+    checked::cast(energised.cells().iter().filter(|x| **x).count())
 }
 
-fn part1(inp: &[&[u8]]) -> u32 {
-    count_energised(inp, (0, 0, Direction::Right))
+pub(crate) fn part1(inp: &Grid<u8>) -> u32 {
+    count_energised(inp, ((0, 0), Direction::Right))
 }
 
-fn part2(inp: &[&[u8]]) -> u32 {
-    let max_x = inp[0].len() - 1;
-    let max_y = inp.len() - 1;
+pub(crate) fn part2(inp: &Grid<u8>) -> u32 {
+    let max_col = inp.width() - 1;
+    let max_row = inp.height() - 1;
 
-    let top = (0..=max_x).map(|x| (x, 0, Direction::Down));
-    let bottom = (0..=max_x).map(|x| (x, max_y, Direction::Up));
-    let left = (0..=max_y).map(|y| (0, y, Direction::Right));
-    let right = (0..=max_y).map(|y| (max_x, y, Direction::Left));
+    let top = (0..=max_col).map(|col| ((0, col), Direction::Down));
+    let bottom = (0..=max_col).map(|col| ((max_row, col), Direction::Up));
+    let left = (0..=max_row).map(|row| ((row, 0), Direction::Right));
+    let right = (0..=max_row).map(|row| ((row, max_col), Direction::Left));
 
     top.chain(bottom)
         .chain(left)
@@ -100,3 +99,18 @@ fn part2(inp: &[&[u8]]) -> u32 {
         .max()
         .unwrap()
 }
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples/day16.rs"));
+}
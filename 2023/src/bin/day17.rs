@@ -1,20 +1,22 @@
-use std::collections::{BinaryHeap, HashSet};
+use aoc_utils::grid::{Grid, Pos};
+use aoc_utils::parse::ParseError;
+use aoc_utils::{checked, harness, input_path, inputs, search};
 
-fn main() {
-    let inp = parse_input();
+pub(crate) const INPUT_PATH: &str = input_path!("inputs/day17.txt");
 
-    let p1 = part1(&inp);
-    println!("Part 1: {p1}");
+fn main() -> anyhow::Result<()> {
+    let Some(input) = harness::load_input(INPUT_PATH)? else {
+        return harness::skip(inputs::MISSING);
+    };
 
-    let p2 = part2(&inp);
-    println!("Part 2: {p2}");
+    harness::run(input, parse_input, part1, part2)
 }
 
-fn parse_input() -> Vec<Vec<u8>> {
-    include_str!("../../inputs/day17.txt")
-        .lines()
-        .map(|line| line.bytes().map(|c| c - b'0').collect())
-        .collect()
+pub(crate) fn parse_input(inp: &'static str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(inp, "a heat loss from 1 to 9", |c| match c {
+        '1'..='9' => c.to_digit(10).map(|loss| loss as u8),
+        _ => None,
+    })
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -26,6 +28,15 @@ enum Direction {
 }
 
 impl Direction {
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
     fn turns(self) -> [Direction; 2] {
         use Direction::*;
 
@@ -36,126 +47,60 @@ impl Direction {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct HeapNode {
-    pos: (usize, usize),
-    loss: u32,
-    cannot_move_in: Direction,
-}
-
-impl PartialEq for HeapNode {
-    fn eq(&self, other: &Self) -> bool {
-        self.cmp(other).is_eq()
-    }
+/// Every way of turning at `pos` and going between `min_move_len` and `max_move_len` blocks in a
+/// straight line, with the heat lost on the way.
+fn moves(
+    grid: &Grid<u8>,
+    (pos, cannot_move_in): (Pos, Direction),
+    min_move_len: usize,
+    max_move_len: usize,
+) -> impl Iterator<Item = ((Pos, Direction), u32)> + '_ {
+    cannot_move_in.turns().into_iter().flat_map(move |dir| {
+        let mut state = (pos, 0);
+        std::iter::from_fn(move || {
+            let next = grid.offset(state.0, dir.delta())?;
+            state = (next, checked::add(state.1, u32::from(grid[next])));
+            Some(((next, dir), state.1))
+        })
+        .skip(min_move_len - 1)
+        .take(max_move_len - min_move_len + 1)
+    })
 }
 
-impl Eq for HeapNode {}
-
-impl PartialOrd for HeapNode {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+fn minimum_heat_loss(grid: &Grid<u8>, min_move_len: usize, max_move_len: usize) -> u32 {
+    let dst = (grid.height() - 1, grid.width() - 1);
+    // every block loses at least 1, so this never overestimates
+    let distance = |&((row, col), _): &(Pos, Direction)| checked::cast(dst.0 - row + dst.1 - col);
+
+    search::astar(
+        [((0, 0), Direction::Right), ((0, 0), Direction::Down)],
+        |&state| moves(grid, state, min_move_len, max_move_len),
+        distance,
+        |&(pos, _)| pos == dst,
+    )
+    .expect("Failed to find a path")
+    .cost
 }
 
-impl Ord for HeapNode {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.loss.cmp(&other.loss).reverse()
-    }
+pub(crate) fn part1(inp: &Grid<u8>) -> u32 {
+    minimum_heat_loss(inp, 1, 3)
 }
 
-struct Graph<'a> {
-    grid: &'a [Vec<u8>],
+pub(crate) fn part2(inp: &Grid<u8>) -> u32 {
+    minimum_heat_loss(inp, 4, 10)
 }
 
-impl<'a> Graph<'a> {
-    fn neighbours(
-        &'a self,
-        node: HeapNode,
-        min_move_len: usize,
-        max_move_len: usize,
-    ) -> impl Iterator<Item = HeapNode> + 'a {
-        node.cannot_move_in
-            .turns()
-            .into_iter()
-            .flat_map(move |dir| {
-                let mut state = node;
-                std::iter::from_fn(move || {
-                    state = self.move_to(state, dir)?;
-                    Some(state)
-                })
-                .skip(min_move_len - 1)
-                .take(max_move_len - min_move_len + 1)
-            })
-    }
+#[cfg(test)]
+mod examples {
+    use super::*;
 
-    fn move_to(&self, node: HeapNode, dir: Direction) -> Option<HeapNode> {
-        let (x, y) = node.pos;
-        let max_x = self.grid[0].len();
-        let max_y = self.grid.len();
-
-        let next_pos = match dir {
-            Direction::Up => (x, y.checked_sub(1)?),
-            Direction::Down => (x, (y + 1 < max_y).then_some(y + 1)?),
-            Direction::Left => (x.checked_sub(1)?, y),
-            Direction::Right => ((x + 1 < max_x).then_some(x + 1)?, y),
-        };
-
-        let (nx, ny) = next_pos;
-        Some(HeapNode {
-            pos: next_pos,
-            loss: node.loss + self.grid[ny][nx] as u32,
-            cannot_move_in: dir,
-        })
+    fn solve_part1(in_str: &'static str) -> String {
+        part1(&parse_input(in_str).unwrap()).to_string()
     }
-}
-
-fn minimum_heat_loss(grid: &[Vec<u8>], min_move_len: usize, max_move_len: usize) -> u32 {
-    let graph = Graph { grid };
-
-    let mut to_explore = BinaryHeap::from([
-        HeapNode {
-            pos: (0, 0),
-            loss: 0,
-            cannot_move_in: Direction::Right,
-        },
-        HeapNode {
-            pos: (0, 0),
-            loss: 0,
-            cannot_move_in: Direction::Down,
-        },
-    ]);
-    let mut seen = HashSet::new();
-
-    let dst = (grid[0].len() - 1, grid.len() - 1);
-    while let Some(node) = to_explore.pop() {
-        let HeapNode {
-            pos,
-            loss,
-            cannot_move_in,
-        } = node;
-
-        if !seen.insert((pos, cannot_move_in)) {
-            continue;
-        }
-
-        if pos == dst {
-            return loss;
-        }
 
-        to_explore.extend(
-            graph
-                .neighbours(node, min_move_len, max_move_len)
-                .filter(|node| !seen.contains(&(node.pos, node.cannot_move_in))),
-        )
+    fn solve_part2(in_str: &'static str) -> String {
+        part2(&parse_input(in_str).unwrap()).to_string()
     }
 
-    unreachable!("Failed to find a path");
-}
-
-fn part1(inp: &[Vec<u8>]) -> u32 {
-    minimum_heat_loss(inp, 1, 3)
-}
-
-fn part2(inp: &[Vec<u8>]) -> u32 {
-    minimum_heat_loss(inp, 4, 10)
+    include!(concat!(env!("OUT_DIR"), "/examples/day17.rs"));
 }
@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 use advent_of_code_2024::*;
+use aoc_utils::{input_or_skip, input_path};
 use concat_idents::concat_idents;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
@@ -8,30 +9,30 @@ macro_rules! bench_day {
     ($day:literal, $module:path, $input:literal) => {
         concat_idents!(part1_bench = bench_, $day, _part1 {
             pub fn part1_bench(c: &mut Criterion) {
-                let input = $module::Input::from_str(include_str!($input)).unwrap();
+                let input = $module::Input::from_str(input_or_skip!(input_path!($input))).unwrap();
                 c.bench_function(concat!($day, "_part1"), |b| b.iter(|| $module::part1(black_box(&input))));
             }
         });
 
         concat_idents!(part2_bench = bench_, $day, _part2 {
             pub fn part2_bench(c: &mut Criterion) {
-                let input = $module::Input::from_str(include_str!($input)).unwrap();
+                let input = $module::Input::from_str(input_or_skip!(input_path!($input))).unwrap();
                 c.bench_function(concat!($day, "_part2"), |b| b.iter(|| $module::part2(black_box(&input))));
             }
         });
     };
 }
 
-bench_day!("day01", day01, "../inputs/day1.input");
-bench_day!("day02", day02, "../inputs/day2.input");
+bench_day!("day01", day01, "inputs/day1.input");
+bench_day!("day02", day02, "inputs/day2.input");
 
 fn bench_day03_part1(c: &mut Criterion) {
-    let input = include_str!("../inputs/day2.input");
+    let input = input_or_skip!(input_path!("inputs/day3.input"));
     c.bench_function("day03_part1", |b| b.iter(|| day03::part1(black_box(input))));
 }
 
 fn bench_day03_part2(c: &mut Criterion) {
-    let input = include_str!("../inputs/day2.input");
+    let input = input_or_skip!(input_path!("inputs/day3.input"));
     c.bench_function("day03_part2", |b| b.iter(|| day03::part2(black_box(input))));
 }
 
@@ -1,6 +1,8 @@
-use std::{collections::BTreeMap, str::FromStr};
+use std::str::FromStr;
 
-use color_eyre::eyre::{bail, Report, Result};
+use aoc_utils::checked;
+use aoc_utils::counter::Counter;
+use aoc_utils::parse::{ParseError, Source};
 
 pub struct Input {
     pub left: Vec<u32>,
@@ -8,19 +10,18 @@ pub struct Input {
 }
 
 impl FromStr for Input {
-    type Err = Report;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
         let mut left = vec![];
         let mut right = vec![];
 
         for line in s.lines() {
-            let Some((l, r)) = line.split_once("   ") else {
-                bail!("Failed to split line into left and right halves: {line:?}");
-            };
+            let (l, r) = src.split_once(line, "   ")?;
 
-            left.push(l.parse()?);
-            right.push(r.parse()?);
+            left.push(src.number(l)?);
+            right.push(src.number(r)?);
         }
 
         Ok(Input { left, right })
@@ -34,23 +35,18 @@ pub fn part1(input: &Input) -> u32 {
     left.sort();
     right.sort();
 
-    left.into_iter()
-        .zip(right)
-        .map(|(l, r)| l.abs_diff(r))
-        .sum()
+    checked::sum(left.into_iter().zip(right).map(|(l, r)| l.abs_diff(r)))
 }
 
 pub fn part2(input: &Input) -> u32 {
-    let mut counts: BTreeMap<_, u32> = BTreeMap::new();
-    for num in &input.right {
-        *counts.entry(num).or_default() += 1;
-    }
-
-    input
-        .left
-        .iter()
-        .map(|num| num * counts.get(num).unwrap_or(&0))
-        .sum()
+    let counts: Counter<u32> = input.right.iter().copied().collect();
+
+    checked::sum(
+        input
+            .left
+            .iter()
+            .map(|&num| checked::mul(num, checked::cast(counts.get(&num)))),
+    )
 }
 
 #[cfg(test)]
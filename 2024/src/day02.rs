@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
-use color_eyre::eyre::{Report, Result};
+use aoc_utils::checked;
+use aoc_utils::parse::{ParseError, Source};
 
 pub struct Input {
     pub(crate) numbers: Vec<u8>,
@@ -40,12 +41,13 @@ impl<'a> Iterator for Rows<'a> {
 }
 
 impl FromStr for Input {
-    type Err = Report;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
         let numbers: Vec<_> = s
             .split_ascii_whitespace()
-            .map(str::parse)
+            .map(|num| src.number(num))
             .collect::<Result<_, _>>()?;
 
         let row_widths = s
@@ -60,6 +62,8 @@ impl FromStr for Input {
     }
 }
 
+// heights are only compared and `abs_diff`ed, which can't overflow, and one too big for a `u8`
+// is turned down by the parser
 fn row_is_safe(row: &[u8]) -> bool {
     let monotonic = row.is_sorted_by(|a, b| a < b) || row.is_sorted_by(|a, b| a > b);
     if !monotonic {
@@ -77,7 +81,7 @@ pub fn part1(input: &Input) -> u32 {
 
     for row in input.rows() {
         if row_is_safe(row) {
-            count += 1;
+            count = checked::add(count, 1);
         }
     }
 
@@ -112,13 +116,13 @@ pub fn part2(input: &Input) -> u32 {
 
     'outer: for row in input.rows() {
         if row_is_safe(row) {
-            count += 1;
+            count = checked::add(count, 1);
             continue;
         }
 
         for i in 0..row.len() {
             if row_is_safe_skipping(row, i) {
-                count += 1;
+                count = checked::add(count, 1);
                 continue 'outer;
             }
         }
@@ -1,3 +1,5 @@
+use aoc_utils::checked;
+
 pub type Input = str;
 
 pub fn part1(input: &Input) -> u32 {
@@ -20,7 +22,7 @@ pub fn part1(input: &Input) -> u32 {
             continue;
         };
 
-        sum += left * right;
+        sum = checked::add(sum, checked::mul(left, right));
     }
 
     sum
@@ -35,7 +37,7 @@ pub fn part2(input: &Input) -> u32 {
             None => seg,
         };
 
-        sum += part1(enabled);
+        sum = checked::add(sum, part1(enabled));
     }
 
     sum
@@ -0,0 +1,16 @@
+use advent_of_code_2024::{day01, day02};
+use aoc_utils::fuzz;
+
+#[test]
+fn day01_parser_rejects_junk() {
+    let example = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+    assert!(example.parse::<day01::Input>().is_ok());
+    fuzz::check("day01", &[example], str::parse::<day01::Input>);
+}
+
+#[test]
+fn day02_parser_rejects_junk() {
+    let example = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+    assert!(example.parse::<day02::Input>().is_ok());
+    fuzz::check("day02", &[example], str::parse::<day02::Input>);
+}
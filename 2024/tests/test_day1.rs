@@ -1,14 +1,15 @@
 use advent_of_code_2024::day01::*;
+use aoc_utils::{input_or_skip, input_path};
 use std::str::FromStr;
 
 #[test]
 fn test_day01_part1() {
-    let input = Input::from_str(include_str!("../inputs/day1.input")).unwrap();
+    let input = Input::from_str(input_or_skip!(input_path!("inputs/day1.input"))).unwrap();
     assert_eq!(part1(&input), 2430334);
 }
 
 #[test]
 fn test_day01_part2() {
-    let input = Input::from_str(include_str!("../inputs/day1.input")).unwrap();
+    let input = Input::from_str(input_or_skip!(input_path!("inputs/day1.input"))).unwrap();
     assert_eq!(part2(&input), 28786472);
 }
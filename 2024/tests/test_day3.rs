@@ -1,13 +1,14 @@
 use advent_of_code_2024::day03::*;
+use aoc_utils::{input_or_skip, input_path};
 
 #[test]
 fn test_day03_part1() {
-    let input = include_str!("../inputs/day3.input");
-    assert_eq!(part1(&input), 178538786);
+    let input = input_or_skip!(input_path!("inputs/day3.input"));
+    assert_eq!(part1(input), 178538786);
 }
 
 #[test]
 fn test_day03_part2() {
-    let input = include_str!("../inputs/day3.input");
-    assert_eq!(part2(&input), 102467299);
+    let input = input_or_skip!(input_path!("inputs/day3.input"));
+    assert_eq!(part2(input), 102467299);
 }
@@ -0,0 +1,93 @@
+//! Running every day with checked arithmetic, to find any whose integer types are too small.
+//!
+//! Days are built with the `checked` feature (see [`aoc_utils::checked`]), so the first overflow
+//! in their hot arithmetic panics, naming the operands and where it happened, rather than
+//! wrapping round to a wrong answer.
+
+use std::fmt::Write as _;
+
+use crate::timing::{escape_cell, Timing};
+use crate::years::Outcome;
+
+/// Whether a day failed by overflowing, rather than in some other way.
+pub fn overflowed(timing: &Timing) -> bool {
+    matches!(&timing.outcome, Outcome::Failed(reason) if reason.contains("overflow"))
+}
+
+/// Render the outcome of each day as a markdown table.
+pub fn markdown(timings: &[Timing]) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "# Overflow audit\n").unwrap();
+    writeln!(
+        out,
+        "Every day run once on its input, with checked arithmetic."
+    )
+    .unwrap();
+    if !cfg!(feature = "checked") && timings.iter().any(|timing| timing.year == 2024) {
+        writeln!(
+            out,
+            "2024 runs in this process, so is only checked with the runner built with `--features checked`."
+        )
+        .unwrap();
+    }
+    writeln!(out, "\n| Year | Day | Result |").unwrap();
+    writeln!(out, "|-----:|----:|:-------|").unwrap();
+
+    for timing in timings {
+        let result = match &timing.outcome {
+            Outcome::Ran(_) => "ok".to_string(),
+            Outcome::Skipped(reason) => format!("skipped: {}", escape_cell(reason)),
+            Outcome::Failed(reason) if overflowed(timing) => {
+                format!("**overflowed**: {}", escape_cell(reason))
+            }
+            Outcome::Failed(reason) => format!("failed: {}", escape_cell(reason)),
+        };
+        writeln!(out, "| {} | {} | {result} |", timing.year, timing.day).unwrap();
+    }
+
+    let count = timings.iter().filter(|timing| overflowed(timing)).count();
+    if count > 0 {
+        writeln!(out, "\n{count} of {} days overflowed.", timings.len()).unwrap();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_utils::harness::Report;
+
+    use super::*;
+
+    #[test]
+    fn flags_overflows() {
+        let timing = |day, outcome| Timing {
+            year: 2020,
+            day,
+            outcome,
+        };
+        let timings = [
+            timing(1, Outcome::Ran(Report::default())),
+            timing(
+                18,
+                Outcome::Failed(
+                    "thread 'main' panicked at src/bin/day18.rs:122:29: overflow: 6402373705728000 * 4096 doesn't fit in usize".to_string(),
+                ),
+            ),
+            timing(22, Outcome::Failed("thread 'main' panicked".to_string())),
+            timing(25, Outcome::Skipped("input missing".to_string())),
+        ];
+
+        assert!(!overflowed(&timings[0]));
+        assert!(overflowed(&timings[1]));
+        assert!(!overflowed(&timings[2]));
+
+        let table = markdown(&timings);
+        assert!(table.contains("| 2020 | 1 | ok |"));
+        assert!(table.contains("| 2020 | 18 | **overflowed**: thread 'main' panicked at src/bin/day18.rs:122:29: overflow: "));
+        assert!(table.contains("| 2020 | 22 | failed: thread 'main' panicked |"));
+        assert!(table.contains("| 2020 | 25 | skipped: input missing |"));
+        assert!(table.ends_with("\n1 of 4 days overflowed.\n"));
+    }
+}
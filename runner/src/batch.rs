@@ -0,0 +1,313 @@
+//! Running a day on every input in a directory, such as inputs collected from different people,
+//! to check that an optimisation works for all of them and not just the one it was tried on.
+//!
+//! An `answers` file among the inputs, in the format of [`aoc_utils::answers`], gives the answers
+//! each input should have, and any input which doesn't get them is flagged.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use aoc_utils::answers::{Answer, Answers};
+use serde::Serialize;
+
+use crate::timing::{self, format_bytes, format_duration, JsonStats};
+use crate::years::{Day, Outcome};
+
+/// The name of the file of expected answers in a directory of inputs.
+pub const ANSWERS: &str = "answers";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    /// The input's file name.
+    pub file: String,
+    pub bytes: usize,
+    pub outcome: Outcome,
+    /// What the answers file says the answers are, if it has them.
+    pub expected: Option<Answer>,
+}
+
+impl Run {
+    /// The parts whose answer isn't the expected one, with what was expected. Days which didn't
+    /// run have nothing to compare.
+    pub fn mismatches(&self) -> Vec<(u8, &str)> {
+        let (Outcome::Ran(report), Some(expected)) = (&self.outcome, &self.expected) else {
+            return vec![];
+        };
+
+        [1, 2]
+            .into_iter()
+            .filter_map(|part| {
+                let expected = expected.part(part)?;
+                let answer = report.answers[usize::from(part - 1)].as_deref();
+                (answer != Some(expected)).then_some((part, expected))
+            })
+            .collect()
+    }
+
+    /// A word on how the answers compare, for the tables.
+    fn check(&self) -> String {
+        let mismatches = self.mismatches();
+        match (&self.outcome, &self.expected) {
+            (Outcome::Ran(_), Some(_)) if mismatches.is_empty() => "ok".to_string(),
+            (Outcome::Ran(_), Some(_)) => mismatches
+                .iter()
+                .map(|(part, expected)| format!("part {part} should be {expected}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => String::new(),
+        }
+    }
+}
+
+/// The inputs in `dir`, in order of their names, leaving out the answers and hidden files.
+fn inputs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut inputs = vec![];
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to list {}", dir.display()))? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if entry.file_type()?.is_file() && name != ANSWERS && !name.starts_with('.') {
+            inputs.push(entry.path());
+        }
+    }
+
+    inputs.sort_unstable();
+    Ok(inputs)
+}
+
+/// Run `day` on every input in `dir`, checking them against the answers file if there is one.
+pub fn batch(day: &Day, dir: &Path, reps: usize) -> Result<Vec<Run>> {
+    let inputs = inputs(dir)?;
+    if inputs.is_empty() {
+        bail!("There are no inputs in {}", dir.display());
+    }
+
+    let answers_path = dir.join(ANSWERS);
+    let answers = if answers_path.exists() {
+        Answers::load(&answers_path)?
+    } else {
+        Answers::default()
+    };
+
+    inputs
+        .iter()
+        .map(|path| {
+            let file = path
+                .file_name()
+                .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+            let bytes = fs::metadata(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+                .len() as usize;
+
+            eprintln!("Running {file}...");
+            Ok(Run {
+                bytes,
+                outcome: day.run(path, reps),
+                expected: answers.get(&file).cloned(),
+                file,
+            })
+        })
+        .collect()
+}
+
+/// Render the runs as a markdown table.
+pub fn markdown(year: u16, day: u8, dir: &Path, runs: &[Run], reps: usize) -> String {
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "# {year} day {day} on the inputs in {}\n",
+        dir.display()
+    )
+    .unwrap();
+    writeln!(out, "Median (min) of {reps} runs on each input.").unwrap();
+    if runs.iter().any(|run| run.expected.is_some()) {
+        writeln!(out, "Answers are checked against `{ANSWERS}`.").unwrap();
+    }
+    writeln!(
+        out,
+        "\n| Input | Size | Answer 1 | Answer 2 | Parse | Part 1 | Part 2 | Both parts | Total | Check |"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "|:------|-----:|---------:|---------:|------:|-------:|-------:|-----------:|------:|:------|"
+    )
+    .unwrap();
+
+    for run in runs {
+        let file = timing::escape_cell(&run.file);
+        write!(out, "| {file} | {} |", format_bytes(run.bytes)).unwrap();
+        match &run.outcome {
+            Outcome::Ran(report) => {
+                for answer in &report.answers {
+                    let answer = answer.as_deref().map_or(String::new(), timing::escape_cell);
+                    write!(out, " {answer} |").unwrap();
+                }
+                let total = timing::phase_cells(&mut out, report);
+                let check = timing::escape_cell(&run.check());
+                writeln!(out, " {} | {check} |", format_duration(total)).unwrap();
+            }
+            Outcome::Skipped(reason) => {
+                let reason = timing::escape_cell(reason);
+                writeln!(out, " skipped: {reason} | | | | | | | |").unwrap();
+            }
+            Outcome::Failed(reason) => {
+                let reason = timing::escape_cell(reason);
+                writeln!(out, " failed: {reason} | | | | | | | |").unwrap();
+            }
+        }
+    }
+
+    let wrong = runs
+        .iter()
+        .filter(|run| !run.mismatches().is_empty())
+        .count();
+    if wrong > 0 {
+        writeln!(
+            out,
+            "\n{wrong} of {} inputs disagree with `{ANSWERS}`.",
+            runs.len()
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+#[derive(Serialize)]
+struct JsonBatch<'a> {
+    year: u16,
+    day: u8,
+    reps: usize,
+    inputs: Vec<JsonRun<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonRun<'a> {
+    file: &'a str,
+    bytes: usize,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'a str>,
+    answers: [Option<&'a str>; 2],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<[Option<&'a str>; 2]>,
+    /// The parts which didn't get the expected answer.
+    wrong: Vec<u8>,
+    phases: BTreeMap<&'static str, JsonStats>,
+}
+
+/// Render the runs as pretty printed JSON.
+pub fn json(year: u16, day: u8, runs: &[Run], reps: usize) -> serde_json::Result<String> {
+    let inputs = runs
+        .iter()
+        .map(|run| {
+            let mut json = JsonRun {
+                file: &run.file,
+                bytes: run.bytes,
+                status: "ok",
+                reason: None,
+                answers: [None, None],
+                expected: run
+                    .expected
+                    .as_ref()
+                    .map(|expected| [expected.part(1), expected.part(2)]),
+                wrong: run.mismatches().iter().map(|&(part, _)| part).collect(),
+                phases: BTreeMap::new(),
+            };
+
+            match &run.outcome {
+                Outcome::Ran(report) => {
+                    json.answers = [report.answers[0].as_deref(), report.answers[1].as_deref()];
+                    json.phases = timing::json_phases(report);
+                }
+                Outcome::Skipped(reason) => {
+                    json.status = "skipped";
+                    json.reason = Some(reason);
+                }
+                Outcome::Failed(reason) => {
+                    json.status = "failed";
+                    json.reason = Some(reason);
+                }
+            }
+
+            json
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&JsonBatch {
+        year,
+        day,
+        reps,
+        inputs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stub::scratch_dir;
+
+    #[test]
+    fn checks_every_input() {
+        let dir = scratch_dir("batch");
+        let example = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        fs::write(dir.join("alice.txt"), example).unwrap();
+        fs::write(dir.join("bob.txt"), example).unwrap();
+        fs::write(dir.join("carol.txt"), "1   1\n").unwrap();
+        fs::write(dir.join("dave.txt"), "not numbers\n").unwrap();
+        fs::write(
+            dir.join(ANSWERS),
+            "alice.txt 11 31\nbob.txt 11 30\ncarol.txt 0 -\n",
+        )
+        .unwrap();
+
+        let day = Day::prepare(2024, 1, "stable").unwrap();
+        let runs = batch(&day, &dir, 2).unwrap();
+        let files: Vec<_> = runs.iter().map(|run| run.file.as_str()).collect();
+        assert_eq!(files, ["alice.txt", "bob.txt", "carol.txt", "dave.txt"]);
+
+        assert!(runs[0].mismatches().is_empty());
+        assert_eq!(runs[1].mismatches(), [(2, "30")]);
+        assert!(runs[2].mismatches().is_empty(), "only part 1 is checked");
+        assert!(matches!(runs[3].outcome, Outcome::Failed(_)));
+        assert!(runs[3].expected.is_none());
+
+        let table = markdown(2024, 1, &dir, &runs, 2);
+        let rows: Vec<_> = table
+            .lines()
+            .filter(|l| l.starts_with("| "))
+            .skip(1)
+            .collect();
+        assert!(
+            rows[0].starts_with("| alice.txt | 36 B | 11 | 31 |"),
+            "{}",
+            rows[0]
+        );
+        assert!(rows[0].ends_with(" | ok |"), "{}", rows[0]);
+        assert!(rows[1].ends_with(" | part 2 should be 30 |"), "{}", rows[1]);
+        assert!(rows[3].contains("failed: "), "{}", rows[3]);
+        assert!(table.contains("1 of 4 inputs disagree"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&json(2024, 1, &runs, 2).unwrap()).unwrap();
+        assert_eq!(json["inputs"][1]["wrong"], serde_json::json!([2]));
+        assert_eq!(
+            json["inputs"][2]["expected"],
+            serde_json::json!(["0", null])
+        );
+        assert_eq!(json["inputs"][3]["status"], "failed");
+    }
+
+    #[test]
+    fn needs_inputs() {
+        let dir = scratch_dir("batch_empty");
+        fs::write(dir.join(ANSWERS), "").unwrap();
+        let day = Day::prepare(2024, 1, "stable").unwrap();
+        assert!(batch(&day, &dir, 1).is_err());
+    }
+}
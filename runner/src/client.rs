@@ -0,0 +1,327 @@
+//! Talking to the advent of code website, politely.
+//!
+//! Every request carries the session cookie from `AOC_SESSION`, and first waits for the
+//! [`RateLimiter`], which remembers when the last request went out in a file so that the limit
+//! holds across runs. Inputs never change, so once fetched they are only ever read from disk.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The least time to leave between requests.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = concat!("advent_of_code_rust runner/", env!("CARGO_PKG_VERSION"));
+
+/// Spaces requests out by at least `interval`, remembering the last one in `state`.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    state: PathBuf,
+    interval: Duration,
+}
+
+impl RateLimiter {
+    pub fn new(state: impl Into<PathBuf>, interval: Duration) -> Self {
+        Self {
+            state: state.into(),
+            interval,
+        }
+    }
+
+    fn last_request(&self) -> Option<SystemTime> {
+        let millis = fs::read_to_string(&self.state).ok()?.trim().parse().ok()?;
+        Some(UNIX_EPOCH + Duration::from_millis(millis))
+    }
+
+    /// Block until it's polite to make another request, then note that one is being made.
+    pub fn wait(&self) -> Result<()> {
+        if let Some(last) = self.last_request() {
+            // a clock which went backwards counts as no time having passed
+            let elapsed = last.elapsed().unwrap_or_default();
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+
+        if let Some(dir) = self.state.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        fs::write(&self.state, now.to_string())
+            .with_context(|| format!("Failed to write {}", self.state.display()))
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    /// Only needed once a request is made, so that cached inputs can be used without one.
+    session: Option<String>,
+    agent: ureq::Agent,
+    limiter: RateLimiter,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, limiter: RateLimiter) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: Some(session.trim().to_string()),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            limiter,
+        }
+    }
+
+    /// A client using the session token from `AOC_SESSION`, if it's set.
+    pub fn from_env(base_url: &str, limiter: RateLimiter) -> Self {
+        let mut client = Self::new(base_url, "", limiter);
+        client.session = std::env::var(SESSION_VAR)
+            .ok()
+            .map(|s| s.trim().to_string());
+        client
+    }
+
+    fn url(&self, year: u16, day: u8, page: &str) -> String {
+        format!("{}/{year}/day/{day}{page}", self.base_url)
+    }
+
+    fn cookie(&self) -> Result<String> {
+        match &self.session {
+            Some(session) if !session.is_empty() => Ok(format!("session={session}")),
+            _ => bail!("Set {SESSION_VAR} to your adventofcode.com session cookie"),
+        }
+    }
+
+    /// Read a response's body, turning error statuses into errors.
+    fn body(response: Result<ureq::Response, ureq::Error>, what: &str) -> Result<String> {
+        match response {
+            Ok(response) => response
+                .into_string()
+                .with_context(|| format!("Failed to read {what}")),
+            Err(ureq::Error::Status(404, _)) => bail!("No {what} yet, is the puzzle unlocked?"),
+            Err(ureq::Error::Status(code @ (400 | 401 | 500), _)) => {
+                bail!("Failed to get {what} ({code}), is {SESSION_VAR} out of date?")
+            }
+            Err(e) => Err(e).with_context(|| format!("Failed to get {what}")),
+        }
+    }
+
+    /// Download the puzzle input for `year` and `day`.
+    pub fn input(&self, year: u16, day: u8) -> Result<String> {
+        let cookie = self.cookie()?;
+        self.limiter.wait()?;
+        let response = self
+            .agent
+            .get(&self.url(year, day, "/input"))
+            .set("Cookie", &cookie)
+            .call();
+        Self::body(response, "input")
+    }
+
+    /// Post `answer` for `part` of `year` and `day`, returning the page that comes back.
+    pub fn answer(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<String> {
+        let cookie = self.cookie()?;
+        self.limiter.wait()?;
+        let response = self
+            .agent
+            .post(&self.url(year, day, "/answer"))
+            .set("Cookie", &cookie)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        Self::body(response, "answer page")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Make sure the input for `year` and `day` is at `path`, only downloading it if it isn't there.
+pub fn fetch_input(client: &Client, path: &Path, year: u16, day: u8) -> Result<Fetched> {
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let input = client.input(year, day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    fs::write(path, input).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    Ok(Fetched::Downloaded)
+}
+
+/// A stand-in for the website, for testing against.
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::thread::{self, JoinHandle};
+
+    use tiny_http::{Header, Response, Server};
+
+    #[derive(Debug, Clone)]
+    pub struct Request {
+        pub method: String,
+        pub url: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    /// Serves `respond`'s `(status, body)` for each request until dropped.
+    pub struct Stub {
+        server: Arc<Server>,
+        thread: Option<JoinHandle<()>>,
+        requests: Arc<Mutex<Vec<Request>>>,
+    }
+
+    impl Stub {
+        pub fn new(respond: impl Fn(&Request) -> (u16, String) + Send + 'static) -> Self {
+            let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+            let requests = Arc::new(Mutex::new(vec![]));
+
+            let thread = thread::spawn({
+                let server = Arc::clone(&server);
+                let requests = Arc::clone(&requests);
+                move || {
+                    for mut request in server.incoming_requests() {
+                        let mut body = String::new();
+                        request.as_reader().read_to_string(&mut body).unwrap();
+                        let seen = Request {
+                            method: request.method().to_string(),
+                            url: request.url().to_string(),
+                            cookie: request
+                                .headers()
+                                .iter()
+                                .find(|header| header.field.equiv("Cookie"))
+                                .map(|header| header.value.to_string()),
+                            body,
+                        };
+
+                        let (status, body) = respond(&seen);
+                        requests.lock().unwrap().push(seen);
+                        let html = Header::from_bytes("Content-Type", "text/html").unwrap();
+                        let response = Response::from_string(body)
+                            .with_status_code(status)
+                            .with_header(html);
+                        request.respond(response).unwrap();
+                    }
+                }
+            });
+
+            Self {
+                server,
+                thread: Some(thread),
+                requests,
+            }
+        }
+
+        pub fn url(&self) -> String {
+            format!("http://{}", self.server.server_addr())
+        }
+
+        pub fn requests(&self) -> Vec<Request> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    impl Drop for Stub {
+        fn drop(&mut self) {
+            self.server.unblock();
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+
+    /// A fresh temporary directory for a test to write to.
+    pub fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_runner_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::stub::{scratch_dir, Stub};
+    use super::*;
+
+    fn client(stub: &Stub, dir: &Path, interval: Duration) -> Client {
+        let limiter = RateLimiter::new(dir.join("last_request"), interval);
+        Client::new(&stub.url(), "abc123\n", limiter)
+    }
+
+    #[test]
+    fn downloads_then_reads_from_cache() {
+        let dir = scratch_dir("cache");
+        let stub = Stub::new(|_| (200, "1\n2\n3\n".to_string()));
+        let client = client(&stub, &dir, Duration::ZERO);
+        let path = dir.join("inputs/day07.txt");
+
+        assert_eq!(
+            fetch_input(&client, &path, 2021, 7).unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+        assert_eq!(
+            fetch_input(&client, &path, 2021, 7).unwrap(),
+            Fetched::Cached
+        );
+
+        let requests = stub.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].url, "/2021/day/7/input");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+    }
+
+    #[test]
+    fn needs_a_session_to_download() {
+        let dir = scratch_dir("no_session");
+        let stub = Stub::new(|_| (200, "input".to_string()));
+        let limiter = RateLimiter::new(dir.join("last_request"), Duration::ZERO);
+        let client = Client::new(&stub.url(), " ", limiter);
+
+        assert!(client.input(2020, 1).is_err());
+        assert!(stub.requests().is_empty());
+    }
+
+    #[test]
+    fn locked_puzzle() {
+        let dir = scratch_dir("locked");
+        let stub = Stub::new(|_| (404, "Not Found".to_string()));
+        let client = client(&stub, &dir, Duration::ZERO);
+        let path = dir.join("day25.txt");
+
+        let error = fetch_input(&client, &path, 2030, 25).unwrap_err();
+        assert!(error.to_string().contains("unlocked"), "{error:#}");
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn rate_limited_across_clients() {
+        let dir = scratch_dir("rate_limit");
+        let stub = Stub::new(|_| (200, "input".to_string()));
+        let interval = Duration::from_millis(300);
+
+        let start = Instant::now();
+        client(&stub, &dir, interval).input(2020, 1).unwrap();
+        // a second run of the runner, sharing the limiter's state
+        client(&stub, &dir, interval).input(2020, 2).unwrap();
+
+        assert!(start.elapsed() >= interval);
+        assert_eq!(stub.requests().len(), 2);
+    }
+}
@@ -0,0 +1,413 @@
+mod audit;
+mod batch;
+mod client;
+mod scaling;
+mod serve;
+mod submit;
+mod timing;
+mod years;
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_utils::generate;
+use clap::{Args, Parser, Subcommand};
+
+use client::{Client, Fetched, RateLimiter};
+use submit::{Attempts, Verdict};
+use timing::Timing;
+use years::{Day, Outcome, RunOptions, YEARS};
+
+#[derive(Parser)]
+#[command(about = "Runs the advent of code solutions from every year")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Time parsing and each part of every day, writing markdown and JSON reports
+    Time(TimeArgs),
+    /// Download a day's puzzle input, unless it has been already
+    Fetch(FetchArgs),
+    /// Submit a day's answer to one part, unless it's been tried before
+    Submit(SubmitArgs),
+    /// Time a day on generated inputs of each size, to see how it scales
+    Scale(ScaleArgs),
+    /// Run a day on every input in a directory, checking them against its answers file
+    Batch(BatchArgs),
+    /// Solve inputs POSTed to /<year>/<day> on localhost, answering with JSON
+    Serve(ServeArgs),
+    /// Run every day once with checked arithmetic, to find any which overflow
+    Audit(AuditArgs),
+    /// Run a day once, printing what it reports from inside its solution
+    Trace(TraceArgs),
+}
+
+#[derive(Args)]
+struct TimeArgs {
+    /// Only time this year
+    #[arg(long)]
+    year: Option<u16>,
+    /// Only time this day
+    #[arg(long)]
+    day: Option<u8>,
+    /// How many times to run each phase
+    #[arg(long, default_value_t = 10)]
+    reps: usize,
+    /// Where to write the markdown report
+    #[arg(long, default_value = "timings.md")]
+    markdown: PathBuf,
+    /// Where to write the JSON report
+    #[arg(long, default_value = "timings.json")]
+    json: PathBuf,
+    /// The toolchain to build the older years with
+    #[arg(long, default_value = "stable")]
+    toolchain: String,
+}
+
+/// Where to find the advent of code website.
+#[derive(Args)]
+struct SiteArgs {
+    /// The website's address, which can point at a stand-in for testing
+    #[arg(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+    base_url: String,
+}
+
+impl SiteArgs {
+    fn client(&self) -> Client {
+        let limiter = RateLimiter::new(
+            years::root().join(".aoc/last_request"),
+            client::MIN_INTERVAL,
+        );
+        Client::from_env(&self.base_url, limiter)
+    }
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    #[command(flatten)]
+    site: SiteArgs,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Submit this instead of running the day to get its answer
+    #[arg(long)]
+    answer: Option<String>,
+    /// The toolchain to build the older years with
+    #[arg(long, default_value = "stable")]
+    toolchain: String,
+    #[command(flatten)]
+    site: SiteArgs,
+}
+
+#[derive(Args)]
+struct ScaleArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    /// The sizes of input to generate, i.e. `--sizes 1000,10000,100000`
+    #[arg(long, value_delimiter = ',', required = true)]
+    sizes: Vec<usize>,
+    /// The seed to generate inputs from, the same one giving the same inputs
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+    /// How many times to run each phase
+    #[arg(long, default_value_t = 10)]
+    reps: usize,
+    /// Also write the curve to this JSON file
+    #[arg(long)]
+    json: Option<PathBuf>,
+    /// The toolchain to build the older years with
+    #[arg(long, default_value = "stable")]
+    toolchain: String,
+}
+
+#[derive(Args)]
+struct BatchArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    /// The directory of inputs, which can also hold an `answers` file
+    dir: PathBuf,
+    /// How many times to run each phase
+    #[arg(long, default_value_t = 10)]
+    reps: usize,
+    /// Also write the results to this JSON file
+    #[arg(long)]
+    json: Option<PathBuf>,
+    /// The toolchain to build the older years with
+    #[arg(long, default_value = "stable")]
+    toolchain: String,
+}
+
+#[derive(Args)]
+struct ServeArgs {
+    /// The port to listen on, on localhost only
+    #[arg(long, default_value_t = 3030)]
+    port: u16,
+    /// The toolchain to build the older years with
+    #[arg(long, default_value = "stable")]
+    toolchain: String,
+}
+
+#[derive(Args)]
+struct AuditArgs {
+    /// Only audit this year
+    #[arg(long)]
+    year: Option<u16>,
+    /// Only audit this day
+    #[arg(long)]
+    day: Option<u8>,
+    /// The toolchain to build the older years with
+    #[arg(long, default_value = "stable")]
+    toolchain: String,
+}
+
+#[derive(Args)]
+struct TraceArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    /// The comma separated spans or events to print, or all of them
+    #[arg(long, default_value = "all")]
+    filter: String,
+    /// Write the trace to this file rather than stderr
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// Run on this input rather than the day's own
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// The toolchain to build the older years with
+    #[arg(long, default_value = "stable")]
+    toolchain: String,
+}
+
+fn check_day(year: u16, day: u8) -> Result<()> {
+    years(Some(year))?;
+    if !(1..=25).contains(&day) {
+        bail!("There is no day {day}, they go from 1 to 25");
+    }
+    Ok(())
+}
+
+fn years(year: Option<u16>) -> Result<Vec<u16>> {
+    match year {
+        Some(year) if !YEARS.contains(&year) => {
+            bail!("No solutions for {year}, try one of {YEARS:?}")
+        }
+        Some(year) => Ok(vec![year]),
+        None => Ok(YEARS.to_vec()),
+    }
+}
+
+fn time(args: TimeArgs) -> Result<()> {
+    let options = RunOptions {
+        reps: args.reps,
+        day: args.day,
+        toolchain: args.toolchain,
+        checked: false,
+    };
+
+    let mut timings = vec![];
+    for year in years(args.year)? {
+        eprintln!("Timing {year}...");
+        for (day, outcome) in years::run_year(year, &options)? {
+            timings.push(Timing { year, day, outcome });
+        }
+    }
+
+    let markdown = timing::markdown(&timings, options.reps);
+    fs::write(&args.markdown, &markdown)
+        .with_context(|| format!("Failed to write {}", args.markdown.display()))?;
+    fs::write(&args.json, timing::json(&timings, options.reps)?)
+        .with_context(|| format!("Failed to write {}", args.json.display()))?;
+    print!("{markdown}");
+
+    Ok(())
+}
+
+fn fetch(args: FetchArgs) -> Result<()> {
+    check_day(args.year, args.day)?;
+    let path = years::input_path(args.year, args.day);
+
+    match client::fetch_input(&args.site.client(), &path, args.year, args.day)? {
+        Fetched::Cached => eprintln!("Already have {}", path.display()),
+        Fetched::Downloaded => eprintln!("Saved {}", path.display()),
+    }
+
+    Ok(())
+}
+
+/// Run a day once and pick out its answer to `part`.
+fn compute_answer(year: u16, day: u8, part: u8, toolchain: String) -> Result<String> {
+    let options = RunOptions {
+        reps: 1,
+        day: Some(day),
+        toolchain,
+        checked: false,
+    };
+
+    let outcome = years::run_year(year, &options)?
+        .into_iter()
+        .find_map(|(d, outcome)| (d == day).then_some(outcome));
+    let report = match outcome {
+        Some(Outcome::Ran(report)) => report,
+        Some(Outcome::Skipped(reason) | Outcome::Failed(reason)) => {
+            bail!("Couldn't run {year} day {day}: {reason}")
+        }
+        None => bail!("There's no solution for {year} day {day}"),
+    };
+
+    report.answers[usize::from(part - 1)]
+        .clone()
+        .with_context(|| format!("{year} day {day} has no answer to part {part}"))
+}
+
+fn submit(args: SubmitArgs) -> Result<()> {
+    check_day(args.year, args.day)?;
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => compute_answer(args.year, args.day, args.part, args.toolchain)?,
+    };
+
+    let mut attempts = Attempts::load(years::root().join(".aoc/attempts.json"))?;
+    let day = (args.year, args.day, args.part);
+    let verdict = submit::submit(&args.site.client(), &mut attempts, day, &answer)?;
+    println!("{answer}: {verdict}");
+
+    if !matches!(verdict, Verdict::Correct) {
+        eprintln!("Every attempt is recorded in {}", attempts.path().display());
+    }
+
+    Ok(())
+}
+
+fn scale(args: ScaleArgs) -> Result<()> {
+    check_day(args.year, args.day)?;
+    let generator = generate::generator(args.year, args.day).ok_or_else(|| {
+        let days: Vec<_> = generate::GENERATORS
+            .iter()
+            .map(|g| format!("{} day {}", g.year, g.day))
+            .collect();
+        anyhow!(
+            "There's no input generator for {} day {}, only for {}",
+            args.year,
+            args.day,
+            days.join(", ")
+        )
+    })?;
+
+    let day = Day::prepare(args.year, args.day, &args.toolchain)?;
+    let dir = years::root().join(".aoc/scaling");
+    let points = scaling::scale(generator, &day, &args.sizes, args.seed, args.reps, &dir)?;
+
+    if let Some(path) = &args.json {
+        fs::write(
+            path,
+            scaling::json(generator, &points, args.seed, args.reps)?,
+        )
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    print!(
+        "{}",
+        scaling::markdown(generator, &points, args.seed, args.reps)
+    );
+
+    Ok(())
+}
+
+fn batch(args: BatchArgs) -> Result<()> {
+    check_day(args.year, args.day)?;
+    let day = Day::prepare(args.year, args.day, &args.toolchain)?;
+    let runs = batch::batch(&day, &args.dir, args.reps)?;
+
+    if let Some(path) = &args.json {
+        fs::write(path, batch::json(args.year, args.day, &runs, args.reps)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    print!(
+        "{}",
+        batch::markdown(args.year, args.day, &args.dir, &runs, args.reps)
+    );
+
+    let wrong = runs
+        .iter()
+        .filter(|run| !run.mismatches().is_empty())
+        .count();
+    if wrong > 0 {
+        bail!("{wrong} inputs got the wrong answers");
+    }
+    Ok(())
+}
+
+fn serve(args: ServeArgs) -> Result<()> {
+    let server = tiny_http::Server::http(("127.0.0.1", args.port))
+        .map_err(|e| anyhow!("Failed to listen on port {}: {e}", args.port))?;
+    eprintln!("Listening on http://{}", server.server_addr());
+
+    let dir = years::root().join(".aoc/serve");
+    serve::Service::new(args.toolchain, dir).serve(&server)
+}
+
+fn audit(args: AuditArgs) -> Result<()> {
+    let options = RunOptions {
+        reps: 1,
+        day: args.day,
+        toolchain: args.toolchain,
+        checked: true,
+    };
+
+    let mut timings = vec![];
+    for year in years(args.year)? {
+        eprintln!("Auditing {year}...");
+        for (day, outcome) in years::run_year(year, &options)? {
+            timings.push(Timing { year, day, outcome });
+        }
+    }
+    print!("{}", audit::markdown(&timings));
+
+    let overflowed = timings.iter().filter(|t| audit::overflowed(t)).count();
+    if overflowed > 0 {
+        bail!("{overflowed} days overflowed");
+    }
+    Ok(())
+}
+
+fn trace(args: TraceArgs) -> Result<()> {
+    check_day(args.year, args.day)?;
+    let day = Day::prepare_with(args.year, args.day, &args.toolchain, &["trace"])?;
+    let input = args
+        .input
+        .unwrap_or_else(|| years::input_path(args.year, args.day));
+    day.trace(&input, &args.filter, args.out.as_deref())
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Time(args) => time(args),
+        Command::Fetch(args) => fetch(args),
+        Command::Submit(args) => submit(args),
+        Command::Scale(args) => scale(args),
+        Command::Batch(args) => batch(args),
+        Command::Serve(args) => serve(args),
+        Command::Audit(args) => audit(args),
+        Command::Trace(args) => trace(args),
+    }
+}
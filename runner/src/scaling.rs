@@ -0,0 +1,258 @@
+//! Timing a day on generated inputs of growing size, to see how it scales.
+//!
+//! Inputs come from [`aoc_utils::generate`] and are kept under `.aoc/scaling`, named after their
+//! day, size and seed, so a surprising point on the curve can be rerun or inspected.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use aoc_utils::generate::Generator;
+use serde::Serialize;
+
+use crate::timing::{self, format_bytes, format_duration, JsonStats};
+use crate::years::{Day, Outcome};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Point {
+    pub size: usize,
+    /// The length of the generated input.
+    pub bytes: usize,
+    pub outcome: Outcome,
+}
+
+impl Point {
+    fn total(&self) -> Option<Duration> {
+        match &self.outcome {
+            Outcome::Ran(report) => report
+                .samples
+                .iter()
+                .map(|(phase, _)| report.stats(*phase).map(|stats| stats.median))
+                .sum(),
+            _ => None,
+        }
+    }
+}
+
+/// Where the input of `size` from `seed` is kept.
+pub fn input_path(dir: &Path, generator: &Generator, size: usize, seed: u64) -> PathBuf {
+    dir.join(format!(
+        "{}_day{:02}_{size}_{seed}.txt",
+        generator.year, generator.day
+    ))
+}
+
+/// Generate an input of each size into `dir` and time `day` on it.
+pub fn scale(
+    generator: &Generator,
+    day: &Day,
+    sizes: &[usize],
+    seed: u64,
+    reps: usize,
+    dir: &Path,
+) -> Result<Vec<Point>> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    sizes
+        .iter()
+        .map(|&size| {
+            let path = input_path(dir, generator, size, seed);
+            let input = generator.generate(size, seed);
+            fs::write(&path, &input)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+
+            eprintln!("Timing size {size}...");
+            Ok(Point {
+                size,
+                bytes: input.len(),
+                outcome: day.run(&path, reps),
+            })
+        })
+        .collect()
+}
+
+/// How the total time grows with size since the previous point, as the power `k` in
+/// `time ∝ sizeᵏ`: about 1 for linear, 2 for quadratic, and so on.
+fn exponent(previous: &Point, point: &Point) -> Option<f64> {
+    let (t0, t1) = (previous.total()?, point.total()?);
+    if previous.size == point.size || previous.size == 0 || t0.is_zero() {
+        return None;
+    }
+
+    let time = t1.as_secs_f64() / t0.as_secs_f64();
+    let size = point.size as f64 / previous.size as f64;
+    Some(time.ln() / size.ln())
+}
+
+/// Render the curve as a markdown table.
+pub fn markdown(generator: &Generator, points: &[Point], seed: u64, reps: usize) -> String {
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "# Scaling of {} day {}\n",
+        generator.year, generator.day
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "Median (min) of {reps} runs on inputs generated from seed {seed}, sized in {}.",
+        generator.size
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "Growth is the power of the size which the total grew by since the last size.\n"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "| Size | Input | Parse | Part 1 | Part 2 | Both parts | Total | Growth |"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "|-----:|------:|------:|-------:|-------:|-----------:|------:|-------:|"
+    )
+    .unwrap();
+
+    let mut previous: Option<&Point> = None;
+    for point in points {
+        write!(out, "| {} | {} |", point.size, format_bytes(point.bytes)).unwrap();
+        match &point.outcome {
+            Outcome::Ran(report) => {
+                let total = timing::phase_cells(&mut out, report);
+                let growth = previous
+                    .and_then(|previous| exponent(previous, point))
+                    .map_or(String::new(), |k| format!("{k:.2}"));
+                writeln!(out, " {} | {growth} |", format_duration(total)).unwrap();
+            }
+            Outcome::Skipped(reason) => {
+                let reason = timing::escape_cell(reason);
+                writeln!(out, " skipped: {reason} | | | | | |").unwrap();
+            }
+            Outcome::Failed(reason) => {
+                let reason = timing::escape_cell(reason);
+                writeln!(out, " failed: {reason} | | | | | |").unwrap();
+            }
+        }
+        previous = Some(point);
+    }
+
+    out
+}
+
+#[derive(Serialize)]
+struct JsonCurve {
+    year: u16,
+    day: u8,
+    size: &'static str,
+    seed: u64,
+    reps: usize,
+    points: Vec<JsonPoint>,
+}
+
+#[derive(Serialize)]
+struct JsonPoint {
+    size: usize,
+    bytes: usize,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    phases: BTreeMap<&'static str, JsonStats>,
+}
+
+/// Render the curve as pretty printed JSON.
+pub fn json(
+    generator: &Generator,
+    points: &[Point],
+    seed: u64,
+    reps: usize,
+) -> serde_json::Result<String> {
+    let points = points
+        .iter()
+        .map(|point| {
+            let (status, reason, phases) = match &point.outcome {
+                Outcome::Ran(report) => ("ok", None, timing::json_phases(report)),
+                Outcome::Skipped(reason) => ("skipped", Some(reason.clone()), BTreeMap::new()),
+                Outcome::Failed(reason) => ("failed", Some(reason.clone()), BTreeMap::new()),
+            };
+            JsonPoint {
+                size: point.size,
+                bytes: point.bytes,
+                status,
+                reason,
+                phases,
+            }
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&JsonCurve {
+        year: generator.year,
+        day: generator.day,
+        size: generator.size,
+        seed,
+        reps,
+        points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_utils::generate::{generator, GENERATORS};
+    use aoc_utils::harness::{Phase, Report};
+
+    use super::*;
+    use crate::client::stub::scratch_dir;
+
+    #[test]
+    fn runs_2024_on_generated_inputs() {
+        let dir = scratch_dir("scaling");
+        for generator in GENERATORS.iter().filter(|g| g.year == 2024) {
+            let day = Day::prepare(2024, generator.day, "stable").unwrap();
+            let points = scale(generator, &day, &[10, 100], 1, 2, &dir).unwrap();
+
+            for point in &points {
+                let Outcome::Ran(report) = &point.outcome else {
+                    panic!("day {} failed: {:?}", generator.day, point.outcome);
+                };
+                assert!(report.answers.iter().all(Option::is_some));
+            }
+            assert!(points[0].bytes < points[1].bytes);
+        }
+    }
+
+    fn point(size: usize, ns: u64) -> Point {
+        Point {
+            size,
+            bytes: size * 10,
+            outcome: Outcome::Ran(Report {
+                samples: vec![(Phase::Solve, vec![Duration::from_nanos(ns)])],
+                ..Report::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn growth() {
+        let (small, big) = (point(100, 1000), point(1000, 100_000));
+        assert!((exponent(&small, &big).unwrap() - 2.0).abs() < 1e-9);
+        assert!((exponent(&small, &point(1000, 10_000)).unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(exponent(&small, &small), None);
+
+        let generator = generator(2021, 5).unwrap();
+        let failed = Point {
+            outcome: Outcome::Failed("overflow".to_string()),
+            ..point(10_000, 0)
+        };
+        let table = markdown(generator, &[small, big, failed], 1, 1);
+        let rows: Vec<_> = table.lines().skip(7).collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].ends_with(" 1.00 µs |  |"), "{}", rows[0]);
+        assert!(rows[1].ends_with(" 100.00 µs | 2.00 |"), "{}", rows[1]);
+        assert!(rows[2].contains("failed: overflow"), "{}", rows[2]);
+    }
+}
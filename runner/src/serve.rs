@@ -0,0 +1,320 @@
+//! Solving inputs sent over HTTP, so that other tools can use the solutions without building them.
+//!
+//! `POST /<year>/<day>` with an input as the body runs that day on it, and answers with JSON:
+//!
+//! ```text
+//! $ curl --data-binary @input.txt localhost:3030/2021/5
+//! {
+//!   "year": 2021,
+//!   "day": 5,
+//!   "status": "ok",
+//!   "answers": ["5", "12"],
+//!   "phases": { "parse": { "median_ns": 7300, ... }, ... }
+//! }
+//! ```
+//!
+//! An input the day can't parse gets a 422 with `"status": "failed"` and the parse error under
+//! `"error"`, and a day which doesn't exist a 404. `?reps=N` times each phase `N` times.
+//!
+//! Requests are answered one at a time, so that they don't skew each other's timings. Inputs are
+//! limited to [`MAX_INPUT`] bytes. 2024's days run in this process, borrowing the request's own
+//! input, which goes when the request does. One which panics gets a 500 rather than taking the
+//! service down with it. The older years' days are written out for their binaries to read.
+//!
+//! This is a subcommand of the runner's `aoc` binary rather than a binary of its own. It builds
+//! and runs the older years exactly as the other subcommands do, and a second binary would need
+//! all of that split out into a library.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::timing::{self, JsonStats};
+use crate::years::{self, Day, Outcome};
+
+/// The largest input accepted, far more than any real one.
+pub const MAX_INPUT: u64 = 16 << 20;
+
+#[derive(Serialize)]
+struct Solved<'a> {
+    year: u16,
+    day: u8,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+    answers: [Option<&'a str>; 2],
+    phases: BTreeMap<&'static str, JsonStats>,
+}
+
+#[derive(Serialize)]
+struct Refused<'a> {
+    error: &'a str,
+}
+
+/// Solves requests, keeping each day ready once it's been asked for.
+pub struct Service {
+    days: HashMap<(u16, u8), Day>,
+    toolchain: String,
+    /// Where each input is written for the day to read.
+    dir: PathBuf,
+}
+
+impl Service {
+    pub fn new(toolchain: String, dir: PathBuf) -> Self {
+        Self {
+            days: HashMap::new(),
+            toolchain,
+            dir,
+        }
+    }
+
+    /// The status and JSON body to answer a request with.
+    pub fn handle(&mut self, method: &Method, url: &str, input: &str) -> (u16, String) {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let Some((year, day)) = route(path) else {
+            return refuse(
+                404,
+                &format!("There's nothing at {path}, try /<year>/<day>"),
+            );
+        };
+        if *method != Method::Post {
+            return refuse(405, "POST an input to solve it");
+        }
+
+        let Some(reps) = reps(query) else {
+            return refuse(400, "reps must be a positive number");
+        };
+
+        let path = self.dir.join("input.txt");
+        let day_ready = match self.prepare(year, day) {
+            Ok(day) => day,
+            Err(e) => return refuse(404, &format!("{e:#}")),
+        };
+
+        let outcome = match day_ready {
+            Day::InProcess(measure) => {
+                let measured = panic::catch_unwind(AssertUnwindSafe(|| {
+                    years::measure_input(*measure, reps, input)
+                }));
+                match measured {
+                    Ok(outcome) => outcome,
+                    Err(panic) => {
+                        let message = panic
+                            .downcast_ref::<&str>()
+                            .copied()
+                            .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+                            .unwrap_or("no message");
+                        return refuse(500, &format!("{year} day {day} panicked: {message}"));
+                    }
+                }
+            }
+            Day::Binary(_) => {
+                if let Err(e) = fs::write(&path, input) {
+                    return refuse(500, &format!("Failed to write {}: {e}", path.display()));
+                }
+                day_ready.run(&path, reps)
+            }
+        };
+        let mut solved = Solved {
+            year,
+            day,
+            status: "ok",
+            error: None,
+            answers: [None, None],
+            phases: BTreeMap::new(),
+        };
+        let status = match &outcome {
+            Outcome::Ran(report) => {
+                solved.answers = [report.answers[0].as_deref(), report.answers[1].as_deref()];
+                solved.phases = timing::json_phases(report);
+                200
+            }
+            Outcome::Skipped(reason) | Outcome::Failed(reason) => {
+                solved.status = "failed";
+                solved.error = Some(reason.strip_prefix("Error: ").unwrap_or(reason));
+                422
+            }
+        };
+
+        (status, to_json(&solved))
+    }
+
+    fn prepare(&mut self, year: u16, day: u8) -> Result<&Day> {
+        if !self.days.contains_key(&(year, day)) {
+            crate::check_day(year, day)?;
+            let prepared = Day::prepare(year, day, &self.toolchain)?;
+            self.days.insert((year, day), prepared);
+        }
+        Ok(&self.days[&(year, day)])
+    }
+
+    /// Answer `request`, reading no more of its body than [`MAX_INPUT`].
+    fn respond(&mut self, mut request: Request) -> Result<()> {
+        let mut input = String::new();
+        let read = request
+            .as_reader()
+            .take(MAX_INPUT + 1)
+            .read_to_string(&mut input);
+
+        let (status, body) = match read {
+            Ok(len) if len as u64 > MAX_INPUT => {
+                refuse(413, &format!("Inputs can be at most {MAX_INPUT} bytes"))
+            }
+            Ok(_) => self.handle(request.method(), request.url(), &input),
+            Err(e) => refuse(400, &format!("Couldn't read the input: {e}")),
+        };
+
+        let json = Header::from_bytes("Content-Type", "application/json")
+            .map_err(|()| anyhow!("Bad content type header"))?;
+        request
+            .respond(
+                Response::from_string(body)
+                    .with_status_code(status)
+                    .with_header(json),
+            )
+            .context("Failed to respond")
+    }
+
+    /// Answer every request to `server` until it's unblocked.
+    pub fn serve(&mut self, server: &Server) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+
+        for request in server.incoming_requests() {
+            // a client which hangs up early is no reason to stop
+            if let Err(e) = self.respond(request) {
+                eprintln!("{e:#}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `/2021/5` -> `(2021, 5)`
+fn route(path: &str) -> Option<(u16, u8)> {
+    let (year, day) = path
+        .strip_prefix('/')?
+        .trim_end_matches('/')
+        .split_once('/')?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+/// `reps=N` from a query string, 1 if it isn't there, or `None` if it isn't a positive number.
+fn reps(query: &str) -> Option<usize> {
+    match query.split('&').find_map(|pair| pair.strip_prefix("reps=")) {
+        Some(reps) => reps.parse().ok().filter(|&reps| reps > 0),
+        None => Some(1),
+    }
+}
+
+fn to_json(value: &impl Serialize) -> String {
+    serde_json::to_string_pretty(value).expect("responses always serialise")
+}
+
+fn refuse(status: u16, error: &str) -> (u16, String) {
+    (status, to_json(&Refused { error }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::*;
+    use crate::client::stub::scratch_dir;
+
+    #[test]
+    fn routes() {
+        assert_eq!(route("/2021/5"), Some((2021, 5)));
+        assert_eq!(route("/2021/05/"), Some((2021, 5)));
+        assert_eq!(route("/2021"), None);
+        assert_eq!(route("/2021/five"), None);
+        assert_eq!(route("2021/5"), None);
+
+        assert_eq!(reps(""), Some(1));
+        assert_eq!(reps("x=1&reps=20"), Some(20));
+        assert_eq!(reps("reps=0"), None);
+        assert_eq!(reps("reps=lots"), None);
+    }
+
+    /// POST `input` to `url`, returning the status and the JSON which came back.
+    fn post(url: &str, input: &str) -> (u16, serde_json::Value) {
+        let response = match ureq::post(url).send_string(input) {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => panic!("{e}"),
+        };
+        let status = response.status();
+        (
+            status,
+            serde_json::from_str(&response.into_string().unwrap()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn panics_are_server_errors() {
+        let mut service = Service::new("stable".to_string(), scratch_dir("serve_panic"));
+        service.days.insert(
+            (2024, 1),
+            Day::InProcess(|_, input| panic!("can't solve {input:?}")),
+        );
+
+        let (status, body) = service.handle(&Method::Post, "/2024/1", "3   4\n");
+        assert_eq!(status, 500);
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            json["error"],
+            "2024 day 1 panicked: can't solve \"3   4\\n\""
+        );
+
+        // and the service carries on
+        service.days.clear();
+        assert_eq!(service.handle(&Method::Post, "/2024/1", "3   4\n").0, 200);
+    }
+
+    #[test]
+    fn solves_over_http() {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let url = format!("http://{}", server.server_addr());
+        let thread = thread::spawn({
+            let server = Arc::clone(&server);
+            let mut service = Service::new("stable".to_string(), scratch_dir("serve"));
+            move || service.serve(&server).unwrap()
+        });
+
+        let example = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        let (status, json) = post(&format!("{url}/2024/1?reps=3"), example);
+        assert_eq!(status, 200);
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["answers"], serde_json::json!(["11", "31"]));
+        assert_eq!(json["phases"]["part2"]["samples"], 3);
+
+        let (status, json) = post(&format!("{url}/2024/1"), "3   4\n4   x\n");
+        assert_eq!(status, 422);
+        assert_eq!(json["status"], "failed");
+        let error = json["error"].as_str().unwrap();
+        assert!(error.starts_with("line 2, column 5"), "{error}");
+
+        let (status, json) = post(&format!("{url}/2024/26"), example);
+        assert_eq!(status, 404);
+        assert!(json["error"].as_str().unwrap().contains("day 26"));
+        assert_eq!(post(&format!("{url}/1999/1"), example).0, 404);
+        assert_eq!(post(&format!("{url}/2024/1?reps=0"), example).0, 400);
+        assert_eq!(post(&format!("{url}/solve"), example).0, 404);
+
+        let status = match ureq::get(&format!("{url}/2024/1")).call() {
+            Err(ureq::Error::Status(status, _)) => status,
+            other => panic!("{other:?}"),
+        };
+        assert_eq!(status, 405);
+
+        server.unblock();
+        thread.join().unwrap();
+    }
+}
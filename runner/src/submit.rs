@@ -0,0 +1,362 @@
+//! Submitting answers, and remembering how every submission went.
+//!
+//! Every attempt is appended to a log, which is checked before anything is sent so that a wrong
+//! answer is never submitted twice and a solved part isn't submitted again at all.
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the website made of an answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        hint: Option<Hint>,
+    },
+    /// Answered too soon after the last attempt, and nothing was checked.
+    RateLimited {
+        wait_secs: u64,
+    },
+    /// The part has already been solved, or isn't unlocked yet.
+    WrongLevel,
+    /// Anything else, with the page's message.
+    Unknown {
+        message: String,
+    },
+}
+
+impl Verdict {
+    /// Read the verdict from the page returned after posting an answer.
+    pub fn from_page(html: &str) -> Self {
+        let message = article_text(html);
+
+        if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("That's not the right answer") {
+            let hint = if message.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if message.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            Verdict::Wrong { hint }
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::RateLimited {
+                wait_secs: wait_time(&message).map_or(60, |wait| wait.as_secs()),
+            }
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown { message }
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong { hint: None } => write!(f, "wrong"),
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh),
+            } => write!(f, "wrong, too high"),
+            Verdict::Wrong {
+                hint: Some(Hint::TooLow),
+            } => write!(f, "wrong, too low"),
+            Verdict::RateLimited { wait_secs } => {
+                write!(f, "answered too recently, wait {wait_secs}s")
+            }
+            Verdict::WrongLevel => write!(f, "already solved, or not unlocked yet"),
+            Verdict::Unknown { message } => write!(f, "unrecognised response: {message}"),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, where the verdict lives, or of the whole page without one.
+fn article_text(html: &str) -> String {
+    // the article's own opening tag is only half split off, so start off inside it
+    let (article, mut in_tag) = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or((html, false), |(article, _)| (article, true));
+
+    let mut text = String::new();
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// `You have 1m 23s left to wait.` -> 83s
+fn wait_time(message: &str) -> Option<Duration> {
+    let (_, rest) = message.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    wait.split_whitespace()
+        .try_fold(Duration::ZERO, |total, part| {
+            let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            let secs = match unit {
+                "h" => number * 60 * 60,
+                "m" => number * 60,
+                "s" => number,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs(secs))
+        })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub submitted_at: u64,
+    #[serde(flatten)]
+    pub verdict: Verdict,
+}
+
+/// Every answer ever submitted, kept as a JSON file.
+#[derive(Debug)]
+pub struct Attempts {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Attempts {
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let attempts = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+
+        Ok(Self { path, attempts })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The earlier attempts at this part, oldest first.
+    pub fn at(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| a.year == year && a.day == day && a.part == part)
+    }
+
+    /// Why `answer` mustn't be submitted, if it mustn't.
+    pub fn refusal(&self, year: u16, day: u8, part: u8, answer: &str) -> Option<String> {
+        if let Some(correct) = self
+            .at(year, day, part)
+            .find(|a| a.verdict == Verdict::Correct)
+        {
+            return Some(format!("Already solved, with {}", correct.answer));
+        }
+
+        self.at(year, day, part)
+            .find(|a| a.answer == answer && matches!(a.verdict, Verdict::Wrong { .. }))
+            .map(|wrong| format!("Already submitted {answer}, which was {}", wrong.verdict))
+    }
+
+    fn record(&mut self, attempt: Attempt) -> Result<()> {
+        self.attempts.push(attempt);
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.attempts)?)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+/// Submit `answer`, unless an earlier attempt says not to, and record how it went.
+pub fn submit(
+    client: &Client,
+    attempts: &mut Attempts,
+    (year, day, part): (u16, u8, u8),
+    answer: &str,
+) -> Result<Verdict> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        bail!("Refusing to submit an empty answer");
+    }
+    if let Some(reason) = attempts.refusal(year, day, part, answer) {
+        bail!("{reason}, not submitting again");
+    }
+
+    let page = client.answer(year, day, part, answer)?;
+    let verdict = Verdict::from_page(&page);
+    attempts.record(Attempt {
+        year,
+        day,
+        part,
+        answer: answer.to_string(),
+        submitted_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        verdict: verdict.clone(),
+    })?;
+
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stub::{scratch_dir, Stub};
+    use crate::client::RateLimiter;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<!DOCTYPE html><html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>"
+        )
+    }
+
+    #[test]
+    fn verdicts() {
+        let correct = page(
+            "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.",
+        );
+        assert_eq!(Verdict::from_page(&correct), Verdict::Correct);
+
+        let high = page("That's not the right answer; your answer is too high.  If you're stuck, <a href=\"/2020/day/1\">[Return to Day 1]</a>");
+        assert_eq!(
+            Verdict::from_page(&high),
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh)
+            }
+        );
+
+        let low = page("That's not the right answer; your answer is too low.");
+        assert_eq!(
+            Verdict::from_page(&low),
+            Verdict::Wrong {
+                hint: Some(Hint::TooLow)
+            }
+        );
+
+        let wrong = page("That's not the right answer.  If you're stuck, make sure you're using the full input data.");
+        assert_eq!(Verdict::from_page(&wrong), Verdict::Wrong { hint: None });
+
+        let level =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(Verdict::from_page(&level), Verdict::WrongLevel);
+
+        let other = page("Something <em>new</em>.");
+        assert_eq!(
+            Verdict::from_page(&other),
+            Verdict::Unknown {
+                message: "Something new.".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn rate_limited() {
+        let recent = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href=\"/2020/day/1\">[Return to Day 1]</a>");
+        assert_eq!(
+            Verdict::from_page(&recent),
+            Verdict::RateLimited { wait_secs: 83 }
+        );
+
+        let recent = page("You gave an answer too recently.  You have 41s left to wait.");
+        assert_eq!(
+            Verdict::from_page(&recent),
+            Verdict::RateLimited { wait_secs: 41 }
+        );
+    }
+
+    fn attempt(part: u8, answer: &str, verdict: Verdict) -> Attempt {
+        Attempt {
+            year: 2020,
+            day: 1,
+            part,
+            answer: answer.to_string(),
+            submitted_at: 0,
+            verdict,
+        }
+    }
+
+    #[test]
+    fn refusals() {
+        let mut attempts = Attempts::load(scratch_dir("refusals").join("attempts.json")).unwrap();
+        attempts.attempts = vec![
+            attempt(1, "10", Verdict::Wrong { hint: None }),
+            attempt(1, "11", Verdict::RateLimited { wait_secs: 30 }),
+            attempt(2, "5", Verdict::Correct),
+        ];
+
+        assert!(attempts.refusal(2020, 1, 1, "10").is_some());
+        assert_eq!(attempts.refusal(2020, 1, 1, "11"), None);
+        assert_eq!(attempts.refusal(2020, 1, 1, "12"), None);
+        assert_eq!(attempts.refusal(2020, 2, 1, "10"), None);
+        assert_eq!(
+            attempts.refusal(2020, 1, 2, "6").as_deref(),
+            Some("Already solved, with 5")
+        );
+    }
+
+    #[test]
+    fn submits_and_records() {
+        let dir = scratch_dir("submit");
+        let stub = Stub::new(|request| match request.body.as_str() {
+            "level=1&answer=514579" => (200, page("That's the right answer!")),
+            _ => (
+                200,
+                page("That's not the right answer; your answer is too low."),
+            ),
+        });
+        let limiter = RateLimiter::new(dir.join("last_request"), Duration::ZERO);
+        let client = Client::new(&stub.url(), "abc123", limiter);
+        let log = dir.join("attempts.json");
+        let mut attempts = Attempts::load(&log).unwrap();
+
+        let verdict = submit(&client, &mut attempts, (2020, 1, 1), "1234\n").unwrap();
+        assert_eq!(
+            verdict,
+            Verdict::Wrong {
+                hint: Some(Hint::TooLow)
+            }
+        );
+        assert!(submit(&client, &mut attempts, (2020, 1, 1), "1234").is_err());
+        let verdict = submit(&client, &mut attempts, (2020, 1, 1), "514579").unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+
+        let requests = stub.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].url, "/2020/day/1/answer");
+        assert_eq!(requests[0].body, "level=1&answer=1234");
+
+        // a later run sees the same log
+        let reloaded = Attempts::load(&log).unwrap();
+        assert_eq!(reloaded.at(2020, 1, 1).count(), 2);
+        assert!(reloaded.refusal(2020, 1, 1, "42").is_some());
+    }
+}
@@ -0,0 +1,364 @@
+//! Timing reports for every day, as a markdown table per year and as JSON.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use aoc_utils::harness::{Phase, Report, Stats};
+use serde::Serialize;
+
+use crate::years::Outcome;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub year: u16,
+    pub day: u8,
+    pub outcome: Outcome,
+}
+
+/// Format a duration with a unit suited to its size, i.e. `12.34 µs`.
+pub fn format_duration(duration: Duration) -> String {
+    let ns = duration.as_nanos() as f64;
+    match ns {
+        ns if ns < 1e3 => format!("{ns:.0} ns"),
+        ns if ns < 1e6 => format!("{:.2} µs", ns / 1e3),
+        ns if ns < 1e9 => format!("{:.2} ms", ns / 1e6),
+        ns => format!("{:.2} s", ns / 1e9),
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    match bytes as f64 {
+        b if b < 1e3 => format!("{bytes} B"),
+        b if b < 1e6 => format!("{:.1} kB", b / 1e3),
+        b => format!("{:.1} MB", b / 1e6),
+    }
+}
+
+/// Render the timings as a markdown document, with a table per year.
+pub fn markdown(timings: &[Timing], reps: usize) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "# Timings\n").unwrap();
+    writeln!(out, "Median (min) of {reps} runs of each release build.").unwrap();
+    writeln!(
+        out,
+        "Days which can't separate their parts report both under \"Both parts\"."
+    )
+    .unwrap();
+
+    let counted_memory = timings
+        .iter()
+        .any(|timing| matches!(&timing.outcome, Outcome::Ran(report) if !report.memory.is_empty()));
+    if counted_memory {
+        writeln!(
+            out,
+            "Memory is the peak heap (allocations) of each phase, beyond what was already live."
+        )
+        .unwrap();
+    }
+
+    let mut years: BTreeMap<u16, Vec<&Timing>> = BTreeMap::new();
+    for timing in timings {
+        years.entry(timing.year).or_default().push(timing);
+    }
+
+    for (year, timings) in years {
+        writeln!(out, "\n## {year}\n").unwrap();
+        writeln!(
+            out,
+            "| Day | Parse | Part 1 | Part 2 | Both parts | Total |"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "|----:|------:|-------:|-------:|-----------:|------:|"
+        )
+        .unwrap();
+
+        for timing in &timings {
+            let report = match &timing.outcome {
+                Outcome::Ran(report) => report,
+                Outcome::Skipped(reason) => {
+                    writeln!(
+                        out,
+                        "| {} | skipped: {} | | | | |",
+                        timing.day,
+                        escape_cell(reason)
+                    )
+                    .unwrap();
+                    continue;
+                }
+                Outcome::Failed(reason) => {
+                    writeln!(
+                        out,
+                        "| {} | failed: {} | | | | |",
+                        timing.day,
+                        escape_cell(reason)
+                    )
+                    .unwrap();
+                    continue;
+                }
+            };
+
+            write!(out, "| {} |", timing.day).unwrap();
+            let total = phase_cells(&mut out, report);
+            writeln!(out, " {} |", format_duration(total)).unwrap();
+        }
+
+        memory_table(&mut out, &timings);
+    }
+
+    out
+}
+
+/// The heap each day used, for the days which counted it.
+fn memory_table(out: &mut String, timings: &[&Timing]) {
+    let counted: Vec<_> = timings
+        .iter()
+        .filter_map(|timing| match &timing.outcome {
+            Outcome::Ran(report) if !report.memory.is_empty() => Some((timing.day, report)),
+            _ => None,
+        })
+        .collect();
+    if counted.is_empty() {
+        return;
+    }
+
+    writeln!(
+        out,
+        "\n| Day | Parse memory | Part 1 memory | Part 2 memory | Both parts memory |"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "|----:|-------------:|--------------:|--------------:|------------------:|"
+    )
+    .unwrap();
+    for (day, report) in counted {
+        write!(out, "| {day} |").unwrap();
+        for phase in Phase::ALL {
+            match report.memory(phase) {
+                Some(memory) => write!(
+                    out,
+                    " {} ({}) |",
+                    format_bytes(memory.peak_bytes),
+                    memory.allocations
+                )
+                .unwrap(),
+                None => write!(out, " |").unwrap(),
+            }
+        }
+        writeln!(out).unwrap();
+    }
+}
+
+/// Write a table cell with the median (min) of each phase, returning the total of the medians.
+pub fn phase_cells(out: &mut String, report: &Report) -> Duration {
+    let mut total = Duration::ZERO;
+    for phase in Phase::ALL {
+        match report.stats(phase) {
+            Some(Stats { median, min }) => {
+                total += median;
+                write!(
+                    out,
+                    " {} ({}) |",
+                    format_duration(median),
+                    format_duration(min)
+                )
+                .unwrap();
+            }
+            None => write!(out, " |").unwrap(),
+        }
+    }
+    total
+}
+
+pub fn escape_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    reps: usize,
+    days: Vec<JsonDay<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonDay<'a> {
+    year: u16,
+    day: u8,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'a str>,
+    answers: [Option<&'a str>; 2],
+    phases: BTreeMap<&'static str, JsonStats>,
+}
+
+#[derive(Serialize)]
+pub struct JsonStats {
+    median_ns: u128,
+    min_ns: u128,
+    samples: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peak_bytes: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allocations: Option<usize>,
+}
+
+/// The stats of each phase which `report` timed, by name.
+pub fn json_phases(report: &Report) -> BTreeMap<&'static str, JsonStats> {
+    Phase::ALL
+        .into_iter()
+        .filter_map(|phase| {
+            let stats = report.stats(phase)?;
+            let memory = report.memory(phase);
+            let stats = JsonStats {
+                median_ns: stats.median.as_nanos(),
+                min_ns: stats.min.as_nanos(),
+                samples: report.samples(phase).len(),
+                peak_bytes: memory.map(|m| m.peak_bytes),
+                allocations: memory.map(|m| m.allocations),
+            };
+            Some((phase.name(), stats))
+        })
+        .collect()
+}
+
+/// Render the timings as pretty printed JSON.
+pub fn json(timings: &[Timing], reps: usize) -> serde_json::Result<String> {
+    let days = timings
+        .iter()
+        .map(|timing| {
+            let mut day = JsonDay {
+                year: timing.year,
+                day: timing.day,
+                status: "ok",
+                reason: None,
+                answers: [None, None],
+                phases: BTreeMap::new(),
+            };
+
+            match &timing.outcome {
+                Outcome::Ran(report) => {
+                    day.answers = [report.answers[0].as_deref(), report.answers[1].as_deref()];
+                    day.phases = json_phases(report);
+                }
+                Outcome::Skipped(reason) => {
+                    day.status = "skipped";
+                    day.reason = Some(reason);
+                }
+                Outcome::Failed(reason) => {
+                    day.status = "failed";
+                    day.reason = Some(reason);
+                }
+            }
+
+            day
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&JsonReport { reps, days })
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_utils::harness::Memory;
+
+    use super::*;
+
+    fn example() -> Vec<Timing> {
+        let ns = |ns: &[u64]| {
+            ns.iter()
+                .copied()
+                .map(Duration::from_nanos)
+                .collect::<Vec<_>>()
+        };
+
+        vec![
+            Timing {
+                year: 2020,
+                day: 1,
+                outcome: Outcome::Ran(Report {
+                    answers: [Some("514579".to_string()), Some("241861950".to_string())],
+                    samples: vec![
+                        (Phase::Parse, ns(&[1500, 1000, 2000])),
+                        (Phase::Part1, ns(&[300, 200, 400])),
+                        (Phase::Part2, ns(&[2_500_000, 2_000_000, 3_000_000])),
+                    ],
+                    memory: vec![
+                        (
+                            Phase::Parse,
+                            Memory {
+                                peak_bytes: 1600,
+                                allocations: 2,
+                            },
+                        ),
+                        (Phase::Part1, Memory::default()),
+                        (
+                            Phase::Part2,
+                            Memory {
+                                peak_bytes: 3_400_000,
+                                allocations: 17,
+                            },
+                        ),
+                    ],
+                    skipped: None,
+                }),
+            },
+            Timing {
+                year: 2020,
+                day: 22,
+                outcome: Outcome::Failed("thread 'main' panicked | oops".to_string()),
+            },
+            Timing {
+                year: 2024,
+                day: 3,
+                outcome: Outcome::Skipped("input missing".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.35 µs");
+        assert_eq!(format_duration(Duration::from_millis(250)), "250.00 ms");
+        assert_eq!(format_duration(Duration::from_secs(3)), "3.00 s");
+    }
+
+    #[test]
+    fn markdown_tables() {
+        let md = markdown(&example(), 3);
+
+        assert!(md.contains("## 2020\n"));
+        assert!(md.contains("## 2024\n"));
+        assert!(md.contains(
+            "| 1 | 1.50 µs (1.00 µs) | 300 ns (200 ns) | 2.50 ms (2.00 ms) | | 2.50 ms |"
+        ));
+        assert!(md.contains("| 22 | failed: thread 'main' panicked \\| oops | | | | |"));
+        assert!(md.contains("| 3 | skipped: input missing | | | | |"));
+
+        assert!(md.contains("peak heap (allocations)"));
+        assert!(md.contains("| 1 | 1.6 kB (2) | 0 B (0) | 3.4 MB (17) | |\n"));
+        assert_eq!(
+            md.matches("Parse memory").count(),
+            1,
+            "only 2020 counted any"
+        );
+    }
+
+    #[test]
+    fn json_report() {
+        let json: serde_json::Value = serde_json::from_str(&json(&example(), 3).unwrap()).unwrap();
+
+        assert_eq!(json["reps"], 3);
+        assert_eq!(json["days"][0]["answers"][1], "241861950");
+        assert_eq!(json["days"][0]["phases"]["part2"]["median_ns"], 2_500_000);
+        assert_eq!(json["days"][0]["phases"]["parse"]["samples"], 3);
+        assert_eq!(json["days"][0]["phases"]["part2"]["peak_bytes"], 3_400_000);
+        assert_eq!(json["days"][0]["phases"]["part2"]["allocations"], 17);
+        assert_eq!(json["days"][1]["status"], "failed");
+        assert_eq!(json["days"][2]["reason"], "input missing");
+    }
+}
@@ -0,0 +1,394 @@
+//! Where each year's solutions live, and how to get a [`Report`] out of each day.
+//!
+//! The older years are separate crates, so their day binaries are built and run as subprocesses,
+//! talking the report protocol from [`aoc_utils::harness`]. 2024 is a library in the workspace, so
+//! it is linked in and measured in-process.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io::{self, BufWriter, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use advent_of_code_2024::{day01, day02, day03};
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_utils::harness::{self, Report};
+use aoc_utils::{inputs, trace};
+use serde::Deserialize;
+
+pub const YEARS: [u16; 5] = [2019, 2020, 2021, 2023, 2024];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Ran(Report),
+    Skipped(String),
+    Failed(String),
+}
+
+/// Options for running days, common to every year.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub reps: usize,
+    /// Only run this day.
+    pub day: Option<u8>,
+    /// The toolchain used to build the older years, passed to cargo as `+<toolchain>`.
+    pub toolchain: String,
+    /// Build the older years with checked arithmetic, as a runner built with it does anyway.
+    pub checked: bool,
+}
+
+/// The repository root, which every year's directory hangs off.
+pub fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner lives inside the repository")
+        .to_path_buf()
+}
+
+/// Where the puzzle input for `day` of `year` lives, whether or not it's there yet.
+pub fn input_path(year: u16, day: u8) -> PathBuf {
+    let dir = root().join(year.to_string());
+    match year {
+        2019..=2021 => dir.join(format!("src/inputs/day{day:02}.inp")),
+        2023 => dir.join(format!("inputs/day{day:02}.txt")),
+        _ => dir.join(format!("inputs/day{day}.input")),
+    }
+}
+
+/// Run every day of `year`, in order.
+pub fn run_year(year: u16, options: &RunOptions) -> Result<Vec<(u8, Outcome)>> {
+    let wanted = |day: u8| options.day.is_none_or(|d| d == day);
+
+    if year == 2024 {
+        return Ok(DAYS_2024
+            .iter()
+            .filter(|(day, _)| wanted(*day))
+            .map(|&(day, measure)| (day, run_in_process(day, measure, options.reps)))
+            .collect());
+    }
+
+    let dir = root().join(year.to_string());
+    let days: Vec<u8> = bin_days(&dir)?
+        .into_iter()
+        .filter(|&day| wanted(day))
+        .collect();
+    if days.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let features: &[&str] = if options.checked { &["checked"] } else { &[] };
+    let binaries = build_binaries(&dir, &options.toolchain, features)?;
+    Ok(days
+        .into_iter()
+        .map(|day| {
+            let outcome = match binaries.get(&day) {
+                Some(exe) => run_binary(exe, options.reps, None),
+                None => Outcome::Failed("failed to build".to_string()),
+            };
+            (day, outcome)
+        })
+        .collect())
+}
+
+/// `day07` -> `7`
+fn day_number(name: &str) -> Option<u8> {
+    name.strip_prefix("day")?.parse().ok()
+}
+
+/// The days which have a binary in `<dir>/src/bin`.
+fn bin_days(dir: &Path) -> Result<Vec<u8>> {
+    let bin_dir = dir.join("src/bin");
+    let mut days = vec![];
+
+    for entry in
+        fs::read_dir(&bin_dir).with_context(|| format!("Failed to list {}", bin_dir.display()))?
+    {
+        let path = entry?.path();
+        if let Some(day) = path
+            .file_stem()
+            .and_then(|stem| day_number(&stem.to_string_lossy()))
+        {
+            days.push(day);
+        }
+    }
+
+    days.sort_unstable();
+    Ok(days)
+}
+
+#[derive(Deserialize)]
+struct Artifact {
+    reason: String,
+    target: Target,
+    executable: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    name: String,
+}
+
+/// Build every binary in `dir` with `features`, returning the executables which built. A runner
+/// counting memory or checking arithmetic has the days do the same.
+fn build_binaries(dir: &Path, toolchain: &str, features: &[&str]) -> Result<BTreeMap<u8, PathBuf>> {
+    let mut command = Command::new("cargo");
+    command
+        .arg(format!("+{toolchain}"))
+        .args(["build", "--release", "--bins", "--keep-going"])
+        .arg("--message-format=json-render-diagnostics")
+        .current_dir(dir)
+        .stderr(Stdio::inherit());
+    let mut features = features.to_vec();
+    if cfg!(feature = "memory") {
+        features.push("memory");
+    }
+    if cfg!(feature = "checked") && !features.contains(&"checked") {
+        features.push("checked");
+    }
+    if !features.is_empty() {
+        command.args(["--features", &features.join(",")]);
+    }
+    let output = command.output().context("Failed to run cargo")?;
+
+    // with --keep-going one broken day doesn't stop the rest, so ignore the exit status and use
+    // whatever did build
+    let mut binaries = BTreeMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(artifact) = serde_json::from_str::<Artifact>(line) else {
+            continue;
+        };
+
+        if artifact.reason != "compiler-artifact" {
+            continue;
+        }
+
+        if let (Some(day), Some(exe)) = (day_number(&artifact.target.name), artifact.executable) {
+            binaries.insert(day, exe);
+        }
+    }
+
+    Ok(binaries)
+}
+
+/// The interesting part of a failed day's stderr, skipping backtraces and notes.
+fn error_summary(stderr: &str) -> String {
+    stderr
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| {
+            !line.is_empty() && !line.starts_with("note:") && !line.starts_with("Stack backtrace")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Run a day's binary on `input`, or on its own input without one.
+fn run_binary(exe: &Path, reps: usize, input: Option<&Path>) -> Outcome {
+    let mut command = Command::new(exe);
+    command.args(["--report", "--reps", &reps.to_string()]);
+    if let Some(input) = input {
+        command.arg("--input").arg(input);
+    }
+
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(e) => return Outcome::Failed(format!("failed to run {}: {e}", exe.display())),
+    };
+
+    if !output.status.success() {
+        let summary = error_summary(&String::from_utf8_lossy(&output.stderr));
+        return Outcome::Failed(if summary.is_empty() {
+            output.status.to_string()
+        } else {
+            summary
+        });
+    }
+
+    match String::from_utf8_lossy(&output.stdout).parse::<Report>() {
+        Ok(Report {
+            skipped: Some(reason),
+            ..
+        }) => Outcome::Skipped(reason),
+        Ok(report) if report.samples.is_empty() => {
+            Outcome::Failed("no timings reported".to_string())
+        }
+        Ok(report) => Outcome::Ran(report),
+        Err(e) => Outcome::Failed(format!("{e:#}")),
+    }
+}
+
+pub type Measure = for<'a> fn(usize, &'a str) -> Result<Report>;
+
+const DAYS_2024: [(u8, Measure); 3] = [
+    (1, |reps, input| {
+        harness::measure(
+            reps,
+            input,
+            str::parse::<day01::Input>,
+            day01::part1,
+            day01::part2,
+        )
+    }),
+    (2, |reps, input| {
+        harness::measure(
+            reps,
+            input,
+            str::parse::<day02::Input>,
+            day02::part1,
+            day02::part2,
+        )
+    }),
+    (3, |reps, input| {
+        harness::measure(
+            reps,
+            input,
+            Ok::<_, anyhow::Error>,
+            |s| day03::part1(s),
+            |s| day03::part2(s),
+        )
+    }),
+];
+
+fn run_in_process(day: u8, measure: Measure, reps: usize) -> Outcome {
+    run_measure(measure, reps, &input_path(2024, day))
+}
+
+fn run_measure(measure: Measure, reps: usize, input: &Path) -> Outcome {
+    let input = match fs::read_to_string(input) {
+        Ok(input) => input,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            return Outcome::Skipped(inputs::MISSING.to_string())
+        }
+        Err(e) => return Outcome::Failed(format!("Failed to read {}: {e}", input.display())),
+    };
+    measure_input(measure, reps, &input)
+}
+
+/// Run a 2024 day on `input`, which it only borrows for as long as it runs.
+pub fn measure_input(measure: Measure, reps: usize, input: &str) -> Outcome {
+    match measure(reps, input) {
+        Ok(report) => Outcome::Ran(report),
+        Err(e) => Outcome::Failed(format!("{e:#}")),
+    }
+}
+
+/// One day, ready to run on inputs other than its own.
+pub enum Day {
+    InProcess(Measure),
+    Binary(PathBuf),
+}
+
+impl Day {
+    /// Get `day` of `year` ready, building it first if it's in one of the older years.
+    pub fn prepare(year: u16, day: u8, toolchain: &str) -> Result<Self> {
+        Self::prepare_with(year, day, toolchain, &[])
+    }
+
+    /// Like [`Day::prepare`], building the older years with `features`.
+    pub fn prepare_with(year: u16, day: u8, toolchain: &str, features: &[&str]) -> Result<Self> {
+        if year == 2024 {
+            return DAYS_2024
+                .iter()
+                .find(|(d, _)| *d == day)
+                .map(|&(_, measure)| Day::InProcess(measure))
+                .ok_or_else(|| anyhow!("There's no solution for 2024 day {day}"));
+        }
+
+        build_binaries(&root().join(year.to_string()), toolchain, features)?
+            .remove(&day)
+            .map(Day::Binary)
+            .ok_or_else(|| anyhow!("{year} day {day} didn't build"))
+    }
+
+    pub fn run(&self, input: &Path, reps: usize) -> Outcome {
+        match self {
+            Day::InProcess(measure) => run_measure(*measure, reps, input),
+            Day::Binary(exe) => run_binary(exe, reps, Some(input)),
+        }
+    }
+
+    /// Run once on `input`, writing what the day reports from inside the spans or events `filter`
+    /// names into `out`, or else to stderr, and printing its answers. A binary needs to have been
+    /// prepared with the `trace` feature, and 2024 needs the runner built with it.
+    pub fn trace(&self, input: &Path, filter: &str, out: Option<&Path>) -> Result<()> {
+        match self {
+            Day::InProcess(measure) => {
+                if !trace::ENABLED {
+                    bail!("2024 runs in this process, so build the runner with --features trace to trace it");
+                }
+                let sink: Box<dyn io::Write + Send> = match out {
+                    Some(path) => {
+                        Box::new(BufWriter::new(File::create(path).with_context(|| {
+                            format!("Failed to create {}", path.display())
+                        })?))
+                    }
+                    None => Box::new(io::stderr()),
+                };
+                trace::trace_to(filter.parse()?, sink);
+
+                let outcome = run_measure(*measure, 1, input);
+                trace::flush();
+                match outcome {
+                    Outcome::Ran(report) => {
+                        for (part, answer) in (1..).zip(&report.answers) {
+                            if let Some(answer) = answer {
+                                println!("Part {part}: {answer}");
+                            }
+                        }
+                        Ok(())
+                    }
+                    Outcome::Skipped(reason) | Outcome::Failed(reason) => bail!(reason),
+                }
+            }
+            Day::Binary(exe) => {
+                let mut command = Command::new(exe);
+                command.arg("--input").arg(input).args(["--trace", filter]);
+                if let Some(out) = out {
+                    command.arg("--trace-to").arg(out);
+                }
+
+                let status = command
+                    .status()
+                    .with_context(|| format!("Failed to run {}", exe.display()))?;
+                if !status.success() {
+                    bail!("{} {status}", exe.display());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_numbers() {
+        assert_eq!(day_number("day07"), Some(7));
+        assert_eq!(day_number("day25"), Some(25));
+        assert_eq!(day_number("intcode"), None);
+    }
+
+    #[test]
+    fn input_paths() {
+        let root = root();
+        assert_eq!(input_path(2020, 7), root.join("2020/src/inputs/day07.inp"));
+        assert_eq!(input_path(2023, 7), root.join("2023/inputs/day07.txt"));
+        assert_eq!(input_path(2024, 7), root.join("2024/inputs/day7.input"));
+    }
+
+    #[test]
+    fn error_summary_skips_backtraces() {
+        let stderr = "Error: Unknown argument \"--bogus\"\n\nStack backtrace:\n   0: main\n";
+        assert_eq!(error_summary(stderr), "Error: Unknown argument \"--bogus\"");
+
+        let stderr = "\nthread 'main' panicked at src/bin/day22.rs:85:29:\nBoth players ran out\nnote: run with `RUST_BACKTRACE=1`\n";
+        assert_eq!(
+            error_summary(stderr),
+            "thread 'main' panicked at src/bin/day22.rs:85:29: Both players ran out"
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Expected answers for example inputs.
+//!
+//! Each year keeps an `answers` file next to its `test_inputs`, with one line per input file:
+//!
+//! ```text
+//! # file      part 1  part 2
+//! day15.inp1  436     175594
+//! day19.inp2  3       12
+//! day21.inp1  5       mxmxvkd,sqjhc,fvjkl
+//! ```
+//!
+//! A `-` marks a part that the example doesn't cover, and `#` starts a comment.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub file: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answer {
+    /// The day this input belongs to, i.e. `day15` for `day15.inp1`.
+    pub fn day(&self) -> &str {
+        self.file
+            .split_once('.')
+            .map_or(self.file.as_str(), |(day, _)| day)
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: Vec<Answer>,
+}
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .parse()
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn get(&self, file: &str) -> Option<&Answer> {
+        self.entries.iter().find(|answer| answer.file == file)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Answer> {
+        self.entries.iter()
+    }
+}
+
+impl FromStr for Answers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries: Vec<Answer> = vec![];
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split_once('#').map_or(line, |(data, _)| data);
+            let fields: Vec<_> = line.split_ascii_whitespace().collect();
+            let [file, part1, part2] = fields[..] else {
+                if fields.is_empty() {
+                    continue;
+                }
+
+                bail!("line {}: expected `<file> <part 1> <part 2>`, found {line:?}", i + 1);
+            };
+
+            if entries.iter().any(|answer| answer.file == file) {
+                bail!("line {}: duplicate entry for {file:?}", i + 1);
+            }
+
+            let part = |ans: &str| (ans != "-").then(|| ans.to_string());
+            entries.push(Answer {
+                file: file.to_string(),
+                part1: part(part1),
+                part2: part(part2),
+            });
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+/// Generate a `#[test]` per answer in `<test_inputs>/answers`, meant to be called from a build
+/// script.
+///
+/// The tests for each day are written to `<out_dir>/examples/<day>.rs`, and expect to be
+/// `include!`d into a module which defines `solve_part1` and `solve_part2`, each taking the
+/// example input as a `&str` and returning something comparable with a `&str`. A part which no
+/// example has an answer for is still referred to, so that its solver isn't dead code.
+pub fn generate_example_tests(test_inputs: impl AsRef<Path>, out_dir: impl AsRef<Path>) -> Result<()> {
+    let test_inputs = test_inputs.as_ref();
+    let answers_path = test_inputs.join("answers");
+    println!("cargo:rerun-if-changed={}", test_inputs.display());
+    println!("cargo:rerun-if-changed={}", answers_path.display());
+
+    let answers = Answers::load(&answers_path)?;
+    let mut days: BTreeMap<&str, (String, [bool; 2])> = BTreeMap::new();
+
+    for answer in answers.iter() {
+        let input_path = fs::canonicalize(test_inputs.join(&answer.file))
+            .with_context(|| format!("No example input named {:?}", answer.file))?;
+        let test_name = answer
+            .file
+            .split_once('.')
+            .map_or("example", |(_, ext)| ext)
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+
+        let (tests, tested) = days.entry(answer.day()).or_default();
+        for part in [1, 2] {
+            let Some(expected) = answer.part(part) else {
+                continue;
+            };
+            tested[usize::from(part - 1)] = true;
+
+            writeln!(
+                tests,
+                "#[test]\n\
+                 fn {test_name}_part{part}() {{\n    \
+                     assert_eq!(solve_part{part}(include_str!({input_path:?})), {expected:?});\n\
+                 }}\n",
+            )?;
+        }
+    }
+
+    let examples_dir = out_dir.as_ref().join("examples");
+    fs::create_dir_all(&examples_dir)?;
+    for (day, (mut tests, tested)) in days {
+        for (part, _) in (1..).zip(tested).filter(|&(_, tested)| !tested) {
+            writeln!(
+                tests,
+                "// no example has an answer for part {part}\n\
+                 const _: () = {{\n    \
+                     let _ = solve_part{part};\n\
+                 }};\n",
+            )?;
+        }
+        fs::write(examples_dir.join(format!("{day}.rs")), tests)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_ANSWERS: &str = "\
+        # file      part 1  part 2\n\
+        day15.inp1  436     175594\n\
+        \n\
+        day19.inp1  2       -       # part 2 needs rules 8 and 11\n\
+        day21.inp1  5       mxmxvkd,sqjhc,fvjkl\n\
+    ";
+
+    #[test]
+    fn example_parse() {
+        let answers = Answers::from_str(EXAMPLE_ANSWERS).unwrap();
+
+        assert_eq!(answers.iter().count(), 3);
+        assert_eq!(
+            answers.get("day19.inp1"),
+            Some(&Answer {
+                file: "day19.inp1".to_string(),
+                part1: Some("2".to_string()),
+                part2: None,
+            })
+        );
+        assert_eq!(answers.get("day21.inp1").unwrap().part(2), Some("mxmxvkd,sqjhc,fvjkl"));
+        assert_eq!(answers.get("day21.inp1").unwrap().day(), "day21");
+        assert_eq!(answers.get("day01.inp1"), None);
+    }
+
+    #[test]
+    fn parse_rejects_bad_lines() {
+        assert!(Answers::from_str("day01.inp1 514579\n").is_err());
+        assert!(Answers::from_str("day01.inp1 1 2\nday01.inp1 1 2\n").is_err());
+    }
+}
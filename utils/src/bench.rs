@@ -0,0 +1,57 @@
+//! Criterion benchmarks of a day, split into the same phases as [`crate::harness`] times.
+//!
+//! Each year's `benches/all_days.rs` pulls in its day binaries as modules and hands them to
+//! [`bench_day`] or [`bench_day_both`], the same way their `main`s hand them to the harness.
+
+use std::fmt::Debug;
+
+use criterion::{black_box, BatchSize, Criterion};
+
+/// Benchmark parsing `input`, as `<name>_parse`, returning the parsed input.
+pub fn bench_parse<I, E: Debug>(
+    c: &mut Criterion,
+    name: &str,
+    input: &'static str,
+    parse: impl Fn(&'static str) -> Result<I, E>,
+) -> I {
+    let parsed = parse(input).unwrap_or_else(|e| panic!("{name} failed to parse: {e:?}"));
+    c.bench_function(&format!("{name}_parse"), |b| {
+        b.iter(|| parse(black_box(input)))
+    });
+    parsed
+}
+
+/// Benchmark parsing and each part, as `<name>_parse`, `<name>_part1` and `<name>_part2`.
+pub fn bench_day<I, E: Debug, A, B>(
+    c: &mut Criterion,
+    name: &str,
+    input: &'static str,
+    parse: impl Fn(&'static str) -> Result<I, E>,
+    part1: impl Fn(&I) -> A,
+    part2: impl Fn(&I) -> B,
+) {
+    let parsed = bench_parse(c, name, input, parse);
+    c.bench_function(&format!("{name}_part1"), |b| {
+        b.iter(|| part1(black_box(&parsed)))
+    });
+    c.bench_function(&format!("{name}_part2"), |b| {
+        b.iter(|| part2(black_box(&parsed)))
+    });
+}
+
+/// Like [`bench_day`], for days which compute both parts in one go, as `<name>_solve`.
+///
+/// `solve` takes ownership of the parsed input, so each iteration gets a fresh clone made outside
+/// of the timed section.
+pub fn bench_day_both<I: Clone, E: Debug, A, B>(
+    c: &mut Criterion,
+    name: &str,
+    input: &'static str,
+    parse: impl Fn(&'static str) -> Result<I, E>,
+    solve: impl Fn(I) -> (A, B),
+) {
+    let parsed = bench_parse(c, name, input, parse);
+    c.bench_function(&format!("{name}_solve"), |b| {
+        b.iter_batched(|| parsed.clone(), &solve, BatchSize::SmallInput)
+    });
+}
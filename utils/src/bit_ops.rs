@@ -0,0 +1,393 @@
+//! Bit twiddling on the integer types, and a set of small numbers packed into bits.
+//!
+//! [`BitOps`] works on a single integer, which is all a puzzle needs when it has no more than 128
+//! things to keep track of. [`BitSet`] grows as far as it needs to, a word at a time:
+//!
+//! ```
+//! use aoc_utils::bit_ops::{BitOps, BitSet};
+//!
+//! let edge = 0b0000011011_u16;
+//! assert_eq!(edge.reverse_low(10), 0b1101100000);
+//! assert_eq!(edge.get_bits(1..5), 0b1101);
+//!
+//! let winners: BitSet = [41, 48, 83, 86, 17, 200].into_iter().collect();
+//! let mine: BitSet = [83, 86, 6, 31, 17, 9, 48, 53, 200].into_iter().collect();
+//! assert_eq!((&winners & &mine).iter().collect::<Vec<_>>(), [17, 48, 83, 86, 200]);
+//! ```
+
+use std::fmt;
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Range, Sub, SubAssign,
+};
+
+pub trait BitOps: Sized {
+    fn set_bit(&mut self, n: u32);
+    fn test_bit(&self, n: u32) -> bool;
+    fn clear_bit(&mut self, n: u32);
+
+    /// The bits in `range`, shifted down to the bottom.
+    fn get_bits(&self, range: Range<u32>) -> Self;
+    /// Overwrite the bits in `range` with the bottom bits of `value`.
+    fn set_bits(&mut self, range: Range<u32>, value: Self);
+    /// The lowest `n` bits in the opposite order, with everything above them cleared.
+    fn reverse_low(&self, n: u32) -> Self;
+    /// The positions of the set bits, lowest first.
+    fn ones(&self) -> impl Iterator<Item = u32>;
+}
+
+macro_rules! impl_bitops {
+    ($t:ty) => {
+        impl BitOps for $t {
+            fn set_bit(&mut self, n: u32) {
+                *self |= 1 << n;
+            }
+
+            fn test_bit(&self, n: u32) -> bool {
+                (*self & (1 << n)) == (1 << n)
+            }
+
+            fn clear_bit(&mut self, n: u32) {
+                *self &= !(1 << n);
+            }
+
+            fn get_bits(&self, range: Range<u32>) -> Self {
+                (*self >> range.start) & low_mask!($t, range.len() as u32)
+            }
+
+            fn set_bits(&mut self, range: Range<u32>, value: Self) {
+                let mask = low_mask!($t, range.len() as u32) << range.start;
+                *self = (*self & !mask) | ((value << range.start) & mask);
+            }
+
+            fn reverse_low(&self, n: u32) -> Self {
+                if n == 0 {
+                    return 0;
+                }
+                (self.reverse_bits() >> (<$t>::BITS - n)) & low_mask!($t, n)
+            }
+
+            fn ones(&self) -> impl Iterator<Item = u32> {
+                let mut rest = *self;
+                std::iter::from_fn(move || {
+                    (rest != 0).then(|| {
+                        let n = rest.trailing_zeros();
+                        rest &= rest.wrapping_sub(1);
+                        n
+                    })
+                })
+            }
+        }
+    };
+
+    ($t:ty, $($ts:ty),+) => {
+        impl_bitops! { $t }
+        impl_bitops! { $($ts),+ }
+    };
+}
+
+/// The lowest `$n` bits of a `$t` set, for `$n` up to all of them.
+macro_rules! low_mask {
+    ($t:ty, $n:expr) => {
+        match $n {
+            n if n >= <$t>::BITS => !0,
+            n => (1 as $t << n).wrapping_sub(1),
+        }
+    };
+}
+
+impl_bitops! { usize, u128, u64, u32, u16, u8, isize, i128, i64, i32, i16, i8 }
+
+const WORD: usize = u64::BITS as usize;
+
+/// A set of `usize`s, as one bit each in as many `u64`s as the largest of them needs.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    /// Never ends in a zero word, so equal sets have equal words.
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty set with room for everything below `bits` before it needs to grow.
+    pub fn with_capacity(bits: usize) -> Self {
+        Self {
+            words: Vec::with_capacity(bits.div_ceil(WORD)),
+        }
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+
+    /// Add `n`, returning whether it wasn't already there.
+    pub fn insert(&mut self, n: usize) -> bool {
+        let (word, bit) = (n / WORD, (n % WORD) as u32);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let new = !self.words[word].test_bit(bit);
+        self.words[word].set_bit(bit);
+        new
+    }
+
+    /// Take out `n`, returning whether it was there.
+    pub fn remove(&mut self, n: usize) -> bool {
+        let was_there = self.contains(n);
+        if was_there {
+            self.words[n / WORD].clear_bit((n % WORD) as u32);
+            self.trim();
+        }
+        was_there
+    }
+
+    pub fn contains(&self, n: usize) -> bool {
+        self.words
+            .get(n / WORD)
+            .is_some_and(|word| word.test_bit((n % WORD) as u32))
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    /// How many numbers are in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Every number in the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, word)| word.ones().map(move |bit| i * WORD + bit as usize))
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words.len() <= other.words.len()
+            && self
+                .words
+                .iter()
+                .zip(&other.words)
+                .all(|(a, b)| a & !b == 0)
+    }
+
+    /// The bits in `range`, at most 64 of them, shifted down to the bottom of a `u64`.
+    pub fn get_bits(&self, range: Range<usize>) -> u64 {
+        assert!(range.len() <= WORD, "{range:?} is more than {WORD} bits");
+        let word = |i: usize| self.words.get(i).copied().unwrap_or(0);
+        let (i, shift) = (range.start / WORD, (range.start % WORD) as u32);
+        let mut bits = word(i) >> shift;
+        if shift > 0 {
+            bits |= word(i + 1) << (WORD as u32 - shift);
+        }
+        bits.get_bits(0..range.len() as u32)
+    }
+
+    /// Overwrite the bits in `range`, at most 64 of them, with the bottom bits of `value`.
+    pub fn set_bits(&mut self, range: Range<usize>, value: u64) {
+        assert!(range.len() <= WORD, "{range:?} is more than {WORD} bits");
+        if range.is_empty() {
+            return;
+        }
+        let last_word = (range.end - 1) / WORD;
+        if last_word >= self.words.len() {
+            self.words.resize(last_word + 1, 0);
+        }
+
+        let (i, shift) = (range.start / WORD, (range.start % WORD) as u32);
+        let low_len = (range.len() as u32).min(WORD as u32 - shift);
+        self.words[i].set_bits(shift..shift + low_len, value);
+        if low_len < range.len() as u32 {
+            let high_len = range.len() as u32 - low_len;
+            self.words[i + 1].set_bits(0..high_len, value >> low_len);
+        }
+        self.trim();
+    }
+
+    /// The numbers below `n`, each moved from `i` to `n - 1 - i`.
+    pub fn reverse_low(&self, n: usize) -> Self {
+        self.iter()
+            .take_while(|&i| i < n)
+            .map(|i| n - 1 - i)
+            .collect()
+    }
+}
+
+impl fmt::Debug for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for n in iter {
+            self.insert(n);
+        }
+    }
+}
+
+impl BitOrAssign<&BitSet> for BitSet {
+    fn bitor_assign(&mut self, rhs: &BitSet) {
+        if rhs.words.len() > self.words.len() {
+            self.words.resize(rhs.words.len(), 0);
+        }
+        for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+            *a |= b;
+        }
+    }
+}
+
+impl BitAndAssign<&BitSet> for BitSet {
+    fn bitand_assign(&mut self, rhs: &BitSet) {
+        self.words.truncate(rhs.words.len());
+        for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+            *a &= b;
+        }
+        self.trim();
+    }
+}
+
+impl BitXorAssign<&BitSet> for BitSet {
+    fn bitxor_assign(&mut self, rhs: &BitSet) {
+        if rhs.words.len() > self.words.len() {
+            self.words.resize(rhs.words.len(), 0);
+        }
+        for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+            *a ^= b;
+        }
+        self.trim();
+    }
+}
+
+/// Everything in the left set which isn't in the right.
+impl SubAssign<&BitSet> for BitSet {
+    fn sub_assign(&mut self, rhs: &BitSet) {
+        for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+            *a &= !b;
+        }
+        self.trim();
+    }
+}
+
+macro_rules! impl_set_op {
+    ($($op:ident $method:ident $assign:ident),*) => {
+        $(impl $op for &BitSet {
+            type Output = BitSet;
+
+            fn $method(self, rhs: &BitSet) -> BitSet {
+                let mut set = self.clone();
+                set.$assign(rhs);
+                set
+            }
+        })*
+    };
+}
+
+impl_set_op!(
+    BitOr bitor bitor_assign,
+    BitAnd bitand bitand_assign,
+    BitXor bitxor bitxor_assign,
+    Sub sub sub_assign
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        let mut n = 0b1011_0110_u8;
+        assert_eq!(n.get_bits(2..6), 0b1101);
+        assert_eq!(n.get_bits(0..8), n);
+        n.set_bits(0..4, 0b1111_1001);
+        assert_eq!(n, 0b1011_1001);
+        assert_eq!(n.reverse_low(4), 0b1001);
+        assert_eq!(n.reverse_low(8), 0b1001_1101);
+        assert_eq!(n.reverse_low(0), 0);
+        assert_eq!(n.ones().collect::<Vec<_>>(), [0, 3, 4, 5, 7]);
+
+        // sign bits mustn't leak into what's extracted or reversed
+        let m = -1_i16;
+        assert_eq!(m.get_bits(4..7), 0b111);
+        assert_eq!(m.reverse_low(3), 0b111);
+        assert_eq!(m.ones().count(), 16);
+    }
+
+    #[test]
+    fn membership() {
+        let mut set = BitSet::new();
+        assert!(set.insert(3));
+        assert!(set.insert(300));
+        assert!(!set.insert(3));
+        assert!(set.contains(300) && !set.contains(299) && !set.contains(10_000));
+        assert_eq!((set.len(), set.first()), (2, Some(3)));
+
+        assert!(set.remove(300));
+        assert!(!set.remove(300));
+        assert_eq!(set, [3].into_iter().collect());
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(format!("{:?}", BitSet::from_iter([1, 70])), "{1, 70}");
+    }
+
+    #[test]
+    fn set_algebra() {
+        let a: BitSet = [1, 5, 64, 130].into_iter().collect();
+        let b: BitSet = [5, 64, 200].into_iter().collect();
+        let list = |s: BitSet| s.iter().collect::<Vec<_>>();
+
+        assert_eq!(list(&a | &b), [1, 5, 64, 130, 200]);
+        assert_eq!(list(&a & &b), [5, 64]);
+        assert_eq!(list(&a ^ &b), [1, 130, 200]);
+        assert_eq!(list(&a - &b), [1, 130]);
+        assert_eq!(list(&b - &a), [200]);
+        // emptied words don't stop sets comparing equal
+        assert_eq!(&b - &b, BitSet::new());
+        assert!((&a & &b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+    }
+
+    #[test]
+    fn bit_ranges() {
+        let mut set = BitSet::new();
+        set.set_bits(60..70, 0b11_0000_0101);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [60, 62, 68, 69]);
+        assert_eq!(set.get_bits(60..70), 0b11_0000_0101);
+        assert_eq!(set.get_bits(62..126), 0b1100_0001);
+        assert_eq!(set.get_bits(500..510), 0);
+
+        set.set_bits(64..70, 0);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [60, 62]);
+        assert_eq!(set, [60, 62].into_iter().collect());
+
+        let wide: BitSet = [0, 1, 150].into_iter().collect();
+        assert_eq!(
+            wide.reverse_low(151).iter().collect::<Vec<_>>(),
+            [0, 149, 150]
+        );
+        assert_eq!(wide.reverse_low(2).iter().collect::<Vec<_>>(), [0, 1]);
+    }
+}
@@ -0,0 +1,204 @@
+//! Arithmetic which can say where it overflowed, for checking that each day's integer types are
+//! big enough.
+//!
+//! Release builds wrap on overflow, so a type which is too small for some input gives a wrong
+//! answer rather than an error. The arithmetic behind the days' answers goes through these
+//! functions (see the README for which days), which are just the operators normally, but with the
+//! `checked` feature panic at the first overflow, naming the operands, the type and the line the
+//! arithmetic is on:
+//!
+//! ```text
+//! thread 'main' panicked at src/bin/day18.rs:122:29:
+//! overflow: 6402373705728000 * 4096 doesn't fit in usize
+//! ```
+//!
+//! ```
+//! use aoc_utils::checked;
+//!
+//! assert_eq!(checked::add(2u8, 3), 5);
+//! assert_eq!(checked::product([2u64, 3, 7]), 42);
+//! assert_eq!(checked::cast::<usize, u32>(17), 17);
+//! ```
+//!
+//! `aoc audit` runs every day built with the feature.
+
+use std::fmt::Display;
+use std::ops::{Add, Mul, Sub};
+
+/// The integer types.
+pub trait Int:
+    Copy + Display + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const NAME: &'static str;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! int {
+    ($($t:ty),*) => {
+        $(impl Int for $t {
+            const NAME: &'static str = stringify!($t);
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        })*
+    };
+}
+
+int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cold]
+#[track_caller]
+fn overflow<T: Int>(a: T, op: &str, b: T) -> ! {
+    panic!("overflow: {a} {op} {b} doesn't fit in {}", T::NAME)
+}
+
+#[cold]
+#[track_caller]
+fn cast_overflow<T: Int>(n: T, to: &str) -> ! {
+    panic!("overflow: {n} doesn't fit in {to}")
+}
+
+/// Integers which can be cast to a `U`, see [`cast`].
+pub trait Cast<U> {
+    fn cast(self) -> U;
+}
+
+macro_rules! cast {
+    ($($t:ty),*) => {
+        cast!(@from [$($t),*] [$($t),*]);
+    };
+    (@from [$($from:ty),*] $to:tt) => {
+        $(cast!(@to $from $to);)*
+    };
+    (@to $from:ty [$($to:ty),*]) => {
+        $(impl Cast<$to> for $from {
+            #[inline]
+            #[track_caller]
+            fn cast(self) -> $to {
+                if cfg!(feature = "checked") {
+                    <$to>::try_from(self)
+                        .unwrap_or_else(|_| cast_overflow(self, <$to as Int>::NAME))
+                } else {
+                    self as $to
+                }
+            }
+        })*
+    };
+}
+
+cast!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! operation {
+    ($(#[$doc:meta])* $name:ident, $checked:ident, $op:tt) => {
+        $(#[$doc])*
+        #[inline]
+        #[track_caller]
+        pub fn $name<T: Int>(a: T, b: T) -> T {
+            if cfg!(feature = "checked") {
+                match a.$checked(b) {
+                    Some(n) => n,
+                    None => overflow(a, stringify!($op), b),
+                }
+            } else {
+                a $op b
+            }
+        }
+    };
+}
+
+operation!(
+    /// `a + b`
+    add, checked_add, +
+);
+operation!(
+    /// `a - b`
+    sub, checked_sub, -
+);
+operation!(
+    /// `a * b`
+    mul, checked_mul, *
+);
+
+/// `n as U`, for a number which should fit in a `U`.
+#[inline]
+#[track_caller]
+pub fn cast<T: Cast<U>, U>(n: T) -> U {
+    n.cast()
+}
+
+/// Everything in `numbers` added up, like [`Iterator::sum`].
+#[inline]
+#[track_caller]
+pub fn sum<T: Int>(numbers: impl IntoIterator<Item = T>) -> T {
+    let mut total = T::ZERO;
+    for n in numbers {
+        total = add(total, n);
+    }
+    total
+}
+
+/// Everything in `numbers` multiplied together, like [`Iterator::product`].
+#[inline]
+#[track_caller]
+pub fn product<T: Int>(numbers: impl IntoIterator<Item = T>) -> T {
+    let mut total = T::ONE;
+    for n in numbers {
+        total = mul(total, n);
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(add(200u8, 55), 255);
+        assert_eq!(sub(-5i32, 7), -12);
+        assert_eq!(mul(1u64 << 40, 1 << 20), 1 << 60);
+        assert_eq!(sum([1u32, 2, 3]), 6);
+        assert_eq!(sum::<i64>([]), 0);
+        assert_eq!(product::<u8>([]), 1);
+        assert_eq!(product([-2i8, 3, 5]), -30);
+        assert_eq!(cast::<usize, u32>(40), 40);
+        assert_eq!(cast::<i64, u8>(255), 255);
+        assert_eq!(cast::<u8, i64>(200), 200);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn reports_overflow() {
+        let caught = std::panic::catch_unwind(|| sum([u32::MAX - 1, 1, 1])).unwrap_err();
+        assert_eq!(
+            caught.downcast_ref::<String>().map(String::as_str),
+            Some("overflow: 4294967295 + 1 doesn't fit in u32")
+        );
+
+        assert!(std::panic::catch_unwind(|| sub(0usize, 1)).is_err());
+        assert!(std::panic::catch_unwind(|| mul(i64::MIN, -1)).is_err());
+
+        let caught = std::panic::catch_unwind(|| cast::<u64, u32>(1 << 32)).unwrap_err();
+        assert_eq!(
+            caught.downcast_ref::<String>().map(String::as_str),
+            Some("overflow: 4294967296 doesn't fit in u32")
+        );
+        assert!(std::panic::catch_unwind(|| cast::<i32, u32>(-1)).is_err());
+    }
+}
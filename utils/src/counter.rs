@@ -0,0 +1,315 @@
+//! Counting how many times each thing turns up, like a multiset.
+//!
+//! Counts live in a `HashMap` by default, but keys which are small integers can be counted in a
+//! fixed array instead with [`DenseCounter`], so that counting never hashes:
+//!
+//! ```
+//! use aoc_utils::counter::{Counter, DenseCounter};
+//!
+//! let letters: Counter<char> = "NNCB".chars().collect();
+//! assert_eq!(letters.get(&'N'), 2);
+//! assert_eq!(letters.least_common(), Some((1, vec!['B', 'C'])));
+//!
+//! let mut bytes: DenseCounter<u8, 256> = "NNCB".bytes().collect();
+//! bytes.merge(&"CHB".bytes().collect());
+//! assert_eq!(bytes.most_common(), Some((2, vec![b'B', b'C', b'N'])));
+//! assert_eq!(bytes.total(), 7);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::checked;
+
+/// Where a [`Counter`] keeps its counts.
+pub trait Store<T>: Default + Clone {
+    fn get(&self, key: &T) -> usize;
+    fn get_mut(&mut self, key: T) -> &mut usize;
+    /// Every key with a count above zero, and its count.
+    fn iter(&self) -> impl Iterator<Item = (T, usize)> + '_;
+    fn counts_mut(&mut self) -> impl Iterator<Item = &mut usize> + '_;
+
+    /// Add on every count in `other`.
+    #[track_caller]
+    fn merge(&mut self, other: &Self) {
+        for (key, count) in other.iter() {
+            let ours = self.get_mut(key);
+            *ours = checked::add(*ours, count);
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> Store<T> for HashMap<T, usize> {
+    fn get(&self, key: &T) -> usize {
+        HashMap::get(self, key).copied().unwrap_or(0)
+    }
+
+    fn get_mut(&mut self, key: T) -> &mut usize {
+        self.entry(key).or_default()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (T, usize)> + '_ {
+        HashMap::iter(self)
+            .filter(|&(_, &count)| count > 0)
+            .map(|(key, &count)| (key.clone(), count))
+    }
+
+    fn counts_mut(&mut self) -> impl Iterator<Item = &mut usize> + '_ {
+        self.values_mut()
+    }
+}
+
+/// Counts for the keys `0..N`, indexed by the key itself.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Dense<const N: usize>([usize; N]);
+
+impl<const N: usize> Default for Dense<N> {
+    fn default() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<T: Copy + Into<usize> + TryFrom<usize>, const N: usize> Store<T> for Dense<N> {
+    fn get(&self, key: &T) -> usize {
+        self.0.get((*key).into()).copied().unwrap_or(0)
+    }
+
+    fn get_mut(&mut self, key: T) -> &mut usize {
+        let index = key.into();
+        assert!(index < N, "{index} is too big for a dense counter of {N}");
+        &mut self.0[index]
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (T, usize)> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count > 0)
+            .filter_map(|(index, &count)| Some((T::try_from(index).ok()?, count)))
+    }
+
+    fn counts_mut(&mut self) -> impl Iterator<Item = &mut usize> + '_ {
+        self.0.iter_mut()
+    }
+
+    fn merge(&mut self, other: &Self) {
+        for (ours, theirs) in self.0.iter_mut().zip(&other.0) {
+            *ours += theirs;
+        }
+    }
+}
+
+/// How many of each `T` there are.
+pub struct Counter<T, S = HashMap<T, usize>> {
+    counts: S,
+    keys: PhantomData<T>,
+}
+
+/// A [`Counter`] for keys which are all below `N` as a `usize`, kept in an array.
+pub type DenseCounter<T, const N: usize> = Counter<T, Dense<N>>;
+
+impl<T, S: Store<T>> Counter<T, S> {
+    pub fn new() -> Self {
+        Self {
+            counts: S::default(),
+            keys: PhantomData,
+        }
+    }
+
+    /// How many of `key` there are, which is 0 if it's never been added.
+    pub fn get(&self, key: &T) -> usize {
+        self.counts.get(key)
+    }
+
+    #[track_caller]
+    pub fn add(&mut self, key: T) {
+        self.add_n(key, 1);
+    }
+
+    #[track_caller]
+    pub fn add_n(&mut self, key: T, n: usize) {
+        let count = self.counts.get_mut(key);
+        *count = checked::add(*count, n);
+    }
+
+    /// Every key there's at least one of, with how many there are.
+    pub fn iter(&self) -> impl Iterator<Item = (T, usize)> + '_ {
+        self.counts.iter()
+    }
+
+    /// How many different keys there are.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// How many there are altogether.
+    #[track_caller]
+    pub fn total(&self) -> usize {
+        checked::sum(self.iter().map(|(_, count)| count))
+    }
+
+    /// Add on all the counts in `other`.
+    #[track_caller]
+    pub fn merge(&mut self, other: &Self) {
+        self.counts.merge(&other.counts);
+    }
+
+    /// Take away all the counts in `other`, stopping at zero.
+    pub fn subtract(&mut self, other: &Self) {
+        for (key, count) in other.iter() {
+            let ours = self.counts.get_mut(key);
+            *ours = ours.saturating_sub(count);
+        }
+    }
+
+    /// Multiply every count by `factor`.
+    #[track_caller]
+    pub fn scale(&mut self, factor: usize) {
+        for count in self.counts.counts_mut() {
+            *count = checked::mul(*count, factor);
+        }
+    }
+
+    /// The keys for which `better` picks out their count over every other, with that count.
+    fn extreme(&self, better: impl Fn(usize, usize) -> bool) -> Option<(usize, Vec<T>)>
+    where
+        T: Ord,
+    {
+        let mut found: Option<(usize, Vec<T>)> = None;
+        for (key, count) in self.iter() {
+            match &mut found {
+                Some((best, keys)) if *best == count => keys.push(key),
+                Some((best, _)) if !better(count, *best) => {}
+                _ => found = Some((count, vec![key])),
+            }
+        }
+        found.map(|(count, mut keys)| {
+            keys.sort_unstable();
+            (count, keys)
+        })
+    }
+
+    /// The highest count, and every key with it in order.
+    pub fn most_common(&self) -> Option<(usize, Vec<T>)>
+    where
+        T: Ord,
+    {
+        self.extreme(|count, best| count > best)
+    }
+
+    /// The lowest count above zero, and every key with it in order.
+    pub fn least_common(&self) -> Option<(usize, Vec<T>)>
+    where
+        T: Ord,
+    {
+        self.extreme(|count, best| count < best)
+    }
+}
+
+impl<T, S: Store<T>> Default for Counter<T, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S: Clone> Clone for Counter<T, S> {
+    fn clone(&self) -> Self {
+        Self {
+            counts: self.counts.clone(),
+            keys: PhantomData,
+        }
+    }
+}
+
+impl<T: fmt::Debug, S: Store<T>> fmt::Debug for Counter<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Counters are equal if they have the same count for every key, however they got there.
+impl<T, S: Store<T>> PartialEq for Counter<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(key, count)| other.get(&key) == count)
+    }
+}
+
+impl<T, S: Store<T>> Eq for Counter<T, S> {}
+
+impl<T, S: Store<T>> FromIterator<T> for Counter<T, S> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T, S: Store<T>> Extend<T> for Counter<T, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for key in iter {
+            self.add(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counting() {
+        let mut counter: Counter<&str> = ["a", "b", "a", "c", "a"].into_iter().collect();
+        assert_eq!((counter.get(&"a"), counter.get(&"z")), (3, 0));
+        assert_eq!((counter.len(), counter.total()), (3, 5));
+
+        counter.add_n("z", 4);
+        assert_eq!(counter.most_common(), Some((4, vec!["z"])));
+        assert_eq!(counter.least_common(), Some((1, vec!["b", "c"])));
+        assert!(Counter::<u8>::new().most_common().is_none());
+        assert!(Counter::<u8>::new().is_empty());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut a: Counter<u32> = [1, 1, 2, 3].into_iter().collect();
+        let b: Counter<u32> = [1, 3, 3, 4].into_iter().collect();
+
+        a.merge(&b);
+        assert_eq!(a, [1, 1, 1, 2, 3, 3, 3, 4].into_iter().collect());
+        a.subtract(&b);
+        a.subtract(&b);
+        // 3 and 4 only go down as far as zero, and then don't count
+        assert_eq!(a, [1, 2].into_iter().collect());
+        assert_eq!(a.len(), 2);
+
+        a.scale(5);
+        assert_eq!((a.get(&1), a.get(&2), a.total()), (5, 5, 10));
+    }
+
+    #[test]
+    fn dense_matches_hashed() {
+        let text = "the quick brown fox jumps over the lazy dog";
+        let dense: DenseCounter<u8, 128> = text.bytes().collect();
+        let hashed: Counter<u8> = text.bytes().collect();
+
+        let mut from_hashed: Vec<_> = hashed.iter().collect();
+        from_hashed.sort();
+        assert_eq!(dense.iter().collect::<Vec<_>>(), from_hashed);
+        assert_eq!(dense.most_common(), hashed.most_common());
+        assert_eq!(dense.least_common(), hashed.least_common());
+        assert_eq!(dense.get(&200), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "too big")]
+    fn dense_keys_must_fit() {
+        let mut counter: DenseCounter<u8, 16> = Counter::new();
+        counter.add(16);
+    }
+}
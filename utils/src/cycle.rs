@@ -0,0 +1,203 @@
+//! Finding where a sequence of states starts repeating, to skip ahead to a state billions of steps
+//! away.
+//!
+//! Anything stepped from state to state with finitely many states must come back round to one it's
+//! been in before. From then on it repeats, so the state after any number of steps is one of the
+//! first few:
+//!
+//! ```
+//! use aoc_utils::cycle;
+//!
+//! // 0, 1, 2, 3, 4, then 2, 3, 4 again, and so on
+//! let step = |&n: &u32| if n == 4 { 2 } else { n + 1 };
+//!
+//! let found = cycle::brent(0, step);
+//! assert_eq!((found.start, found.period), (2, 3));
+//! assert_eq!(cycle::nth(0, step, 1_000_000_000), 4);
+//! ```
+//!
+//! Both ways of finding a cycle step forever if the states never repeat.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// How many steps it takes to reach the first state which repeats.
+    pub start: u64,
+    /// How many steps it takes for that state to come round again.
+    pub period: u64,
+}
+
+impl Cycle {
+    /// The number of steps, less than `start + period`, which reach the same state as `n` steps.
+    pub fn equivalent_step(&self, n: u64) -> u64 {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Find the cycle with Brent's algorithm, which only ever keeps two states.
+///
+/// It steps about three times as often as [`hashed`], but doesn't store every state, so suits
+/// states which are large or cheap to step.
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let Search::Repeated { period, .. } = search(&initial, &mut step, u64::MAX) else {
+        unreachable!("a u64 of steps without a repeat");
+    };
+
+    // with the hare a period ahead, they meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// How far the first half of [`brent`] got.
+enum Search<S> {
+    /// The hare came back round to the tortoise, `steps` steps on, so is in the cycle.
+    Repeated { hare: S, steps: u64, period: u64 },
+    /// The hare got `limit` steps on first.
+    Reached(S),
+}
+
+/// Run the hare ahead in stretches of doubling length, with the tortoise waiting at the start of
+/// each, until it comes back round to the tortoise or has taken `limit` steps.
+fn search<S: Clone + Eq>(initial: &S, step: &mut impl FnMut(&S) -> S, limit: u64) -> Search<S> {
+    if limit == 0 {
+        return Search::Reached(initial.clone());
+    }
+
+    let mut power = 1;
+    let mut period = 1;
+    let mut steps = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if steps == limit {
+            return Search::Reached(hare);
+        }
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+        steps += 1;
+    }
+
+    Search::Repeated {
+        hare,
+        steps,
+        period,
+    }
+}
+
+/// Find the cycle by remembering every state until one comes round again.
+///
+/// This steps each state just once, so suits states which are expensive to step but small enough
+/// to keep all of them.
+pub fn hashed<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    for n in 0.. {
+        if let Some(first) = seen.insert(state.clone(), n) {
+            return Cycle {
+                start: first,
+                period: n - first,
+            };
+        }
+        state = step(&state);
+    }
+    unreachable!("a u64 of steps without a repeat");
+}
+
+/// The state after `n` steps from `initial`, found as [`brent`] does, so without stepping anything
+/// like `n` times or storing every state. A state reached before the cycle is found is just
+/// stepped to.
+pub fn nth<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S, n: u64) -> S {
+    match search(&initial, &mut step, n) {
+        Search::Reached(state) => state,
+        // the hare is in the cycle, so only the rest of a lap is left to step
+        Search::Repeated {
+            hare,
+            steps,
+            period,
+        } => (0..(n - steps) % period).fold(hare, |state, _| step(&state)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a sequence which wanders for a while before it settles into a cycle
+    fn step(&n: &u64) -> u64 {
+        (n * n + 1) % 1009
+    }
+
+    fn naive_nth(n: u64) -> u64 {
+        (0..n).fold(3, |state, _| step(&state))
+    }
+
+    #[test]
+    fn both_find_the_same_cycle() {
+        let found = brent(3, step);
+        assert_eq!(found, hashed(3, step));
+        assert!(found.start > 0);
+        assert!(found.period > 1);
+
+        let start = naive_nth(found.start);
+        assert_eq!(naive_nth(found.start + found.period), start);
+        assert!((1..found.period).all(|i| naive_nth(found.start + i) != start));
+        assert!((0..found.start).all(|i| naive_nth(i) != start));
+    }
+
+    #[test]
+    fn skipping_ahead() {
+        for n in [0, 1, 5, 100, 1000, 12345] {
+            assert_eq!(nth(3, step, n), naive_nth(n));
+        }
+        let found = brent(3, step);
+        assert_eq!(
+            nth(3, step, u64::MAX),
+            naive_nth(found.equivalent_step(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn fixed_points() {
+        let found = brent(7, |&n: &u8| n);
+        assert_eq!((found.start, found.period), (0, 1));
+        assert_eq!(hashed(7, |&n: &u8| n), found);
+        assert_eq!(nth(0u8, |&n| n.saturating_add(100), u64::MAX), 255);
+    }
+
+    #[test]
+    fn steps_within_the_prefix() {
+        // counting up never repeats, so this only ends if it doesn't search for a cycle
+        let mut stepped = 0;
+        let counted = nth(
+            0u64,
+            |&n| {
+                stepped += 1;
+                n + 1
+            },
+            1000,
+        );
+        assert_eq!((counted, stepped), (1000, 1000));
+    }
+}
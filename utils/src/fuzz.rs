@@ -0,0 +1,151 @@
+//! Feeding parsers junk, to check they turn it down with an error rather than panicking.
+//!
+//! Each year has a `tests/parsers.rs` which [`check`]s every day's `parse_input`, starting from
+//! that day's examples so that plenty of the inputs are nearly valid, which is where the panics
+//! tend to hide. The days are binaries pulled in as modules, so those tests are built without the
+//! test harness, which keeps the days' own example tests from running a second time.
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+use crate::generate::Rng;
+
+/// How many inputs of each kind a parser is given.
+pub const RUNS: usize = 300;
+
+/// Characters tried on top of those the examples use.
+const EXTRA_CHARS: &str = "\n 0123456789-+,:;=()é";
+
+/// The examples for `day` in `dir`, i.e. `day05.inp1`, `day05.inp2`... for `"day05"`.
+pub fn examples(dir: impl AsRef<Path>, day: &str) -> Vec<String> {
+    let prefix = format!("{day}.");
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&prefix))
+        })
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect()
+}
+
+/// The inputs a parser is given: random bytes, random strings of the characters its examples use,
+/// and its examples with a few characters cut, added, changed or repeated.
+pub fn inputs(examples: &[&str], seed: u64) -> Vec<String> {
+    let mut rng = Rng::new(seed);
+
+    let mut alphabet: Vec<char> = examples
+        .iter()
+        .flat_map(|example| example.chars())
+        .chain(EXTRA_CHARS.chars())
+        .collect();
+    alphabet.sort_unstable();
+    alphabet.dedup();
+
+    let mut inputs = Vec::with_capacity(RUNS * 3);
+    for _ in 0..RUNS {
+        let len = rng.range(0..=64) as usize;
+        let bytes: Vec<u8> = (0..len).map(|_| rng.next_u64() as u8).collect();
+        // a parser can only ever be given a str, so bytes which aren't UTF-8 are replaced
+        inputs.push(String::from_utf8_lossy(&bytes).into_owned());
+
+        let len = rng.range(0..=128) as usize;
+        inputs.push((0..len).map(|_| *rng.pick(&alphabet)).collect());
+
+        if !examples.is_empty() {
+            let example = *rng.pick(examples);
+            inputs.push(mutate(&mut rng, example, &alphabet));
+        }
+    }
+
+    inputs
+}
+
+fn mutate(rng: &mut Rng, example: &str, alphabet: &[char]) -> String {
+    let mut chars: Vec<char> = example.chars().collect();
+
+    for _ in 0..rng.range(1..=3) {
+        let at = rng.range(0..=chars.len() as u64) as usize;
+        let end = rng.range(at as u64..=(at + 8).min(chars.len()) as u64) as usize;
+        match rng.range(0..=4) {
+            0 => chars.truncate(at),
+            1 => {
+                chars.drain(at..end);
+            }
+            2 => chars.insert(at, *rng.pick(alphabet)),
+            3 if at < chars.len() => chars[at] = *rng.pick(alphabet),
+            _ => {
+                let repeated = chars[at..end].to_vec();
+                chars.splice(at..at, repeated);
+            }
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+/// Give `parse` every one of [`inputs`], and panic with the one which made it panic, if any did.
+pub fn check<T, E>(
+    name: &str,
+    examples: &[impl AsRef<str>],
+    parse: impl Fn(&'static str) -> Result<T, E>,
+) {
+    let examples: Vec<&str> = examples.iter().map(AsRef::as_ref).collect();
+    // the same inputs each run, but different ones for each parser
+    let seed = name
+        .bytes()
+        .fold(0u64, |seed, b| seed.wrapping_mul(31) + u64::from(b));
+
+    for input in inputs(&examples, seed) {
+        // parsers are free to borrow from their input, so it has to live forever
+        let input: &'static str = input.leak();
+        if panic::catch_unwind(AssertUnwindSafe(|| parse(input))).is_err() {
+            panic!("{name} panicked parsing {input:?}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inputs_are_seeded() {
+        let examples = ["1,2\n3,4\n", "5,6\n"];
+        assert_eq!(inputs(&examples, 7), inputs(&examples, 7));
+        assert_ne!(inputs(&examples, 7), inputs(&examples, 8));
+        assert_eq!(inputs(&examples, 7).len(), RUNS * 3);
+        assert_eq!(inputs(&[], 7).len(), RUNS * 2);
+    }
+
+    #[test]
+    fn passes_parsers_which_return_errors() {
+        check("numbers", &["12\n34\n"], |s| {
+            s.lines()
+                .map(str::parse::<u8>)
+                .collect::<Result<Vec<_>, _>>()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "unwrapping panicked parsing")]
+    fn catches_parsers_which_panic() {
+        check("unwrapping", &["12\n34\n"], |s| {
+            let numbers: Vec<u8> = s.lines().map(|line| line.parse().unwrap()).collect();
+            Ok::<_, ()>(numbers)
+        });
+    }
+}
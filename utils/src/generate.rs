@@ -0,0 +1,565 @@
+//! Seeded generators of puzzle inputs, for seeing how solutions scale beyond the one real input.
+//!
+//! Each [`Generator`] knows one day's input grammar and produces a valid input of any size, where
+//! what "size" counts (lines, numbers, steps...) depends on the day. The same seed always gives
+//! the same input, so scaling runs can be repeated and compared.
+
+use std::fmt::Write as _;
+use std::ops::RangeInclusive;
+
+/// A small, fast, seedable PRNG (SplitMix64), so that inputs don't depend on any outside crate's
+/// idea of randomness staying the same.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which mustn't be empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {start}..={end}");
+        match (end - start).checked_add(1) {
+            Some(len) => start + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    /// True with probability `1 / n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.range(0..=n - 1) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() as u64 - 1) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i as u64) as usize);
+        }
+    }
+}
+
+/// Generates inputs for one day.
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub year: u16,
+    pub day: u8,
+    /// What the size of an input counts.
+    pub size: &'static str,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    /// An input of `size`, the same one every time for the same `seed`.
+    pub fn generate(&self, size: usize, seed: u64) -> String {
+        // mix the day in, so that one seed doesn't give related inputs for different days
+        let day_seed = u64::from(self.year) << 8 | u64::from(self.day);
+        (self.generate)(&mut Rng::new(seed ^ day_seed.rotate_left(32)), size)
+    }
+}
+
+pub const GENERATORS: [Generator; 11] = [
+    Generator {
+        year: 2020,
+        day: 1,
+        size: "expenses",
+        generate: expense_report,
+    },
+    Generator {
+        year: 2020,
+        day: 15,
+        size: "starting numbers",
+        generate: starting_numbers,
+    },
+    Generator {
+        year: 2021,
+        day: 1,
+        size: "depths",
+        generate: depths,
+    },
+    Generator {
+        year: 2021,
+        day: 5,
+        size: "vent lines",
+        generate: vent_lines,
+    },
+    Generator {
+        year: 2021,
+        day: 6,
+        size: "lanternfish",
+        generate: lanternfish,
+    },
+    Generator {
+        year: 2023,
+        day: 5,
+        size: "ranges per map",
+        generate: almanac,
+    },
+    Generator {
+        year: 2023,
+        day: 9,
+        size: "histories",
+        generate: histories,
+    },
+    Generator {
+        year: 2023,
+        day: 15,
+        size: "steps",
+        generate: initialization_sequence,
+    },
+    Generator {
+        year: 2024,
+        day: 1,
+        size: "location pairs",
+        generate: location_lists,
+    },
+    Generator {
+        year: 2024,
+        day: 2,
+        size: "reports",
+        generate: reports,
+    },
+    Generator {
+        year: 2024,
+        day: 3,
+        size: "instructions",
+        generate: corrupted_memory,
+    },
+];
+
+/// The generator for `day` of `year`, if it has one.
+pub fn generator(year: u16, day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.year == year && g.day == day)
+}
+
+fn lines(items: impl IntoIterator<Item = impl std::fmt::Display>) -> String {
+    let mut out = String::new();
+    for item in items {
+        writeln!(out, "{item}").unwrap();
+    }
+    out
+}
+
+/// Distinct numbers below 2020, hiding exactly one pair and one triple which sum to 2020. There
+/// are only so many of those, so reports top out at a little under a thousand expenses.
+fn expense_report(rng: &mut Rng, size: usize) -> String {
+    let sums = |numbers: &[u64]| {
+        let pairs = (0..5).flat_map(|i| (i + 1..5).map(move |j| (i, j)));
+        let pair_sums = pairs
+            .clone()
+            .filter(|&(i, j)| numbers[i] + numbers[j] == 2020);
+        let triple_sums = pairs
+            .filter(|&(i, j)| (j + 1..5).any(|k| numbers[i] + numbers[j] + numbers[k] == 2020));
+        (pair_sums.count(), triple_sums.count())
+    };
+
+    let mut numbers = loop {
+        let pair = rng.range(1011..=1900);
+        let (a, b) = (rng.range(100..=500), rng.range(501..=900));
+        let numbers = vec![pair, 2020 - pair, a, b, 2020 - a - b];
+        if sums(&numbers) == (1, 1) {
+            break numbers;
+        }
+    };
+
+    // two fillers over half of 2020 can't make 2020 with anything, so they only need to avoid
+    // completing a pair or triple with the numbers already there
+    let taken: Vec<u64> = numbers
+        .iter()
+        .flat_map(|&x| numbers.iter().map(move |&y| x + y))
+        .chain(numbers.iter().copied())
+        .filter(|&sum| sum < 2020)
+        .map(|sum| 2020 - sum)
+        .chain(numbers.iter().copied())
+        .collect();
+
+    let size = size.clamp(5, 1009 - taken.len());
+    while numbers.len() < size {
+        let n = rng.range(1011..=2019);
+        if !taken.contains(&n) && !numbers.contains(&n) {
+            numbers.push(n);
+        }
+    }
+
+    rng.shuffle(&mut numbers);
+    lines(numbers)
+}
+
+fn starting_numbers(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<u64> = (0..size.max(1) as u64 * 4).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(size.max(1));
+
+    let numbers: Vec<_> = numbers.iter().map(u64::to_string).collect();
+    numbers.join(",") + "\n"
+}
+
+/// A sonar sweep drifting mostly downwards.
+fn depths(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100..=200);
+    lines((0..size).map(|_| {
+        depth = (depth + rng.range(0..=30)).saturating_sub(10);
+        depth
+    }))
+}
+
+/// Horizontal, vertical and 45 degree lines within a 1000x1000 grid.
+fn vent_lines(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let (x1, y1) = (rng.range(0..=999) as i64, rng.range(0..=999) as i64);
+        let len = rng.range(10..=500) as i64;
+        let (mut dx, mut dy) = *rng.pick(&[
+            (1, 0),
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (1, -1),
+            (-1, 1),
+            (-1, -1),
+        ]);
+
+        // a line of a single point never ends, so turn away from the edge rather than stop at it
+        let room = |start: i64, d: i64| match d {
+            1 => 999 - start,
+            -1 => start,
+            _ => i64::MAX,
+        };
+        if room(x1, dx) == 0 {
+            dx = -dx;
+        }
+        if room(y1, dy) == 0 {
+            dy = -dy;
+        }
+        let len = len.min(room(x1, dx)).min(room(y1, dy));
+
+        format!("{x1},{y1} -> {},{}", x1 + dx * len, y1 + dy * len)
+    }))
+}
+
+fn lanternfish(rng: &mut Rng, size: usize) -> String {
+    let timers: Vec<_> = (0..size.max(1))
+        .map(|_| rng.range(1..=5).to_string())
+        .collect();
+    timers.join(",") + "\n"
+}
+
+/// Seven maps, each shuffling the whole range of numbers in `size` pieces, so every seed has a
+/// location and every location a seed.
+fn almanac(rng: &mut Rng, size: usize) -> String {
+    const MAPS: [&str; 7] = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+
+    let size = size.max(1) as u64;
+    let extent = size * 1000;
+
+    let mut out = String::from("seeds:");
+    for _ in 0..10 {
+        let start = rng.range(0..=extent - 100);
+        write!(
+            out,
+            " {start} {}",
+            rng.range(1..=(extent - start).min(extent / 10))
+        )
+        .unwrap();
+    }
+    out.push('\n');
+
+    for map in MAPS {
+        write!(out, "\n{map} map:\n").unwrap();
+
+        let mut cuts: Vec<u64> = (0..size - 1).map(|_| rng.range(1..=extent - 1)).collect();
+        cuts.extend([0, extent]);
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        // lay the pieces end to end in a random order to find where each one lands
+        let mut pieces: Vec<_> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+        rng.shuffle(&mut pieces);
+        let mut dst = 0;
+        for (src, len) in pieces {
+            writeln!(out, "{dst} {src} {len}").unwrap();
+            dst += len;
+        }
+    }
+
+    out
+}
+
+/// Values of polynomials of up to degree five, which the day extrapolates with differences.
+fn histories(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let degree = rng.range(0..=5) as usize;
+        let coefficients: Vec<i64> = (0..=degree).map(|_| rng.range(0..=10) as i64 - 5).collect();
+
+        let values: Vec<_> = (0..21i64)
+            .map(|x| {
+                let value = coefficients.iter().rev().fold(0, |acc, c| acc * x + c);
+                value.to_string()
+            })
+            .collect();
+        values.join(" ")
+    }))
+}
+
+fn initialization_sequence(rng: &mut Rng, size: usize) -> String {
+    let mut labels: Vec<String> = vec![];
+    let steps: Vec<_> = (0..size.max(1))
+        .map(|_| {
+            // mostly reuse labels, so that lenses get replaced and removed
+            let label = if labels.is_empty() || rng.one_in(3) {
+                let len = rng.range(2..=6);
+                let label: String = (0..len)
+                    .map(|_| char::from(b'a' + rng.range(0..=25) as u8))
+                    .collect();
+                labels.push(label.clone());
+                label
+            } else {
+                rng.pick(&labels).clone()
+            };
+
+            if rng.one_in(3) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.range(1..=9))
+            }
+        })
+        .collect();
+
+    steps.join(",") + "\n"
+}
+
+fn location_lists(rng: &mut Rng, size: usize) -> String {
+    let left: Vec<u64> = (0..size).map(|_| rng.range(10000..=99999)).collect();
+    let right: Vec<u64> = (0..size)
+        .map(|_| {
+            // a third of the right list also appears on the left, for the similarity score
+            if rng.one_in(3) {
+                *rng.pick(&left)
+            } else {
+                rng.range(10000..=99999)
+            }
+        })
+        .collect();
+
+    lines(left.iter().zip(&right).map(|(l, r)| format!("{l}   {r}")))
+}
+
+/// Reports which mostly step steadily up or down, some with a bad level or two.
+fn reports(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let len = rng.range(5..=8);
+        let rising = rng.one_in(2);
+        let mut level = if rising {
+            rng.range(1..=50)
+        } else {
+            rng.range(50..=99)
+        };
+
+        let mut levels = vec![];
+        for _ in 0..len {
+            levels.push(level);
+            let step = rng.range(1..=3);
+            level = if rising { level + step } else { level - step };
+        }
+
+        for _ in 0..rng.range(0..=2) {
+            let i = rng.range(0..=len - 1) as usize;
+            levels[i] = rng.range(1..=99);
+        }
+
+        let levels: Vec<_> = levels.iter().map(u64::to_string).collect();
+        levels.join(" ")
+    }))
+}
+
+/// `mul`, `do` and `don't` instructions, some of them corrupted, between runs of junk.
+fn corrupted_memory(rng: &mut Rng, size: usize) -> String {
+    const JUNK: &[u8] = b"!@#$%^&*()[]{}<>,;:'? +-/whyselectfromwhere";
+
+    let mut out = String::new();
+    for _ in 0..size {
+        for _ in 0..rng.range(0..=8) {
+            out.push(char::from(*rng.pick(JUNK)));
+        }
+
+        let (x, y) = (rng.range(1..=999), rng.range(1..=999));
+        let instruction = match rng.range(0..=9) {
+            0 => "do()".to_string(),
+            1 => "don't()".to_string(),
+            2 => format!("mul({x},{y}]"),
+            3 => format!("mul[{x},{y})"),
+            4 => format!("mul({x} ,{y})"),
+            _ => format!("mul({x},{y})"),
+        };
+        out.push_str(&instruction);
+    }
+
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_input() {
+        for generator in GENERATORS {
+            let input = generator.generate(50, 7);
+            assert_eq!(input, generator.generate(50, 7));
+            assert_ne!(input, generator.generate(50, 8), "{}", generator.size);
+        }
+    }
+
+    #[test]
+    fn sizes() {
+        let count = |year, day, size| {
+            let input = generator(year, day).unwrap().generate(size, 1);
+            (input.lines().count(), input.trim().split(',').count())
+        };
+
+        assert_eq!(count(2020, 1, 200).0, 200);
+        assert_eq!(count(2020, 15, 6).1, 6);
+        assert_eq!(count(2021, 1, 2000).0, 2000);
+        assert_eq!(count(2021, 5, 500).0, 500);
+        assert_eq!(count(2021, 6, 300).1, 300);
+        assert_eq!(count(2023, 9, 200).0, 200);
+        assert_eq!(count(2023, 15, 4000).1, 4000);
+        assert_eq!(count(2024, 1, 1000).0, 1000);
+        assert_eq!(count(2024, 2, 1000).0, 1000);
+    }
+
+    fn numbers(line: &str) -> Vec<u64> {
+        line.split(|c: char| !c.is_ascii_digit())
+            .filter(|n| !n.is_empty())
+            .map(|n| n.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn expense_report_has_a_pair_and_triple() {
+        let input = generator(2020, 1).unwrap().generate(200, 3);
+        let expenses: Vec<u64> = input.lines().map(|l| l.parse().unwrap()).collect();
+
+        let pairs = expenses
+            .iter()
+            .filter(|&&a| a < 2020 && expenses.contains(&(2020 - a)))
+            .count();
+        assert_eq!(pairs, 2);
+
+        let mut sorted = expenses.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), expenses.len());
+    }
+
+    #[test]
+    fn vent_lines_stay_on_the_grid() {
+        let input = generator(2021, 5).unwrap().generate(1000, 4);
+        for line in input.lines() {
+            let [x1, y1, x2, y2] = numbers(line)[..] else {
+                panic!("bad line {line:?}");
+            };
+            assert!([x1, y1, x2, y2].iter().all(|&n| n < 1000));
+
+            let (dx, dy) = (x1.abs_diff(x2), y1.abs_diff(y2));
+            assert!(dx == 0 || dy == 0 || dx == dy, "{line:?}");
+            assert!(dx + dy > 0, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn almanac_maps_are_shuffles() {
+        let size = 20;
+        let input = generator(2023, 5).unwrap().generate(size, 5);
+        let sections: Vec<_> = input.split("\n\n").collect();
+
+        assert_eq!(sections.len(), 8);
+        assert!(sections[0].starts_with("seeds: "));
+        assert_eq!(numbers(sections[0]).len(), 20);
+
+        for section in &sections[1..] {
+            let mut ranges: Vec<_> = section
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let [dst, src, len] = numbers(line)[..] else {
+                        panic!("bad mapping {line:?}");
+                    };
+                    (dst, src, len)
+                })
+                .collect();
+            assert!(ranges.len() <= size);
+
+            // the sources and destinations both tile the whole range without gaps or overlaps
+            for key in [|r: &(u64, u64, u64)| r.0, |r: &(u64, u64, u64)| r.1] {
+                ranges.sort_unstable_by_key(key);
+                let end = ranges
+                    .iter()
+                    .try_fold(0, |next, r| (key(r) == next).then_some(next + r.2));
+                assert_eq!(end, Some(size as u64 * 1000));
+            }
+        }
+    }
+
+    #[test]
+    fn histories_are_polynomials() {
+        let input = generator(2023, 9).unwrap().generate(100, 6);
+        for line in input.lines() {
+            let mut values: Vec<i64> = line.split(' ').map(|n| n.parse().unwrap()).collect();
+            assert_eq!(values.len(), 21);
+
+            // six rounds of differences get any polynomial of degree five down to zeroes
+            for _ in 0..6 {
+                values = values.windows(2).map(|w| w[1] - w[0]).collect();
+            }
+            assert!(values.iter().all(|&v| v == 0), "{line:?}");
+        }
+    }
+
+    #[test]
+    fn initialization_steps() {
+        let input = generator(2023, 15).unwrap().generate(1000, 7);
+        for step in input.trim().split(',') {
+            let (label, op) = step.split_at(step.find(['=', '-']).unwrap());
+            assert!((2..=6).contains(&label.len()));
+            assert!(label.bytes().all(|b| b.is_ascii_lowercase()));
+            assert!(
+                op == "-" || matches!(op.as_bytes(), [b'=', b'1'..=b'9']),
+                "{step:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn reports_fit_in_a_byte() {
+        let input = generator(2024, 2).unwrap().generate(1000, 8);
+        for line in input.lines() {
+            let levels = numbers(line);
+            assert!((5..=8).contains(&levels.len()));
+            assert!(levels.iter().all(|&level| (1..=150).contains(&level)));
+        }
+    }
+}
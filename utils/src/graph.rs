@@ -0,0 +1,419 @@
+//! A graph of named nodes, for the puzzles whose input is a list of what connects to what.
+//!
+//! Each name is interned the first time it's seen, and from then on nodes are just indices, so
+//! nothing is looked up by name after parsing:
+//!
+//! ```
+//! use aoc_utils::graph::Graph;
+//!
+//! let mut caves = Graph::undirected();
+//! for line in ["start-A", "A-b", "b-end", "A-end"] {
+//!     let (from, to) = line.split_once('-').unwrap();
+//!     caves.add_edge(from, to, ());
+//! }
+//!
+//! let start = caves.id("start").unwrap();
+//! assert_eq!(caves.len(), 4);
+//! assert!(caves.can_reach(start, caves.id("end").unwrap()));
+//!
+//! let nearest_first: Vec<_> = caves.bfs(start).into_iter().map(|n| caves[n]).collect();
+//! assert_eq!(nearest_first, ["start", "A", "b", "end"]);
+//! println!("{}", caves.dot(|_| String::new()));
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+use std::ops::Index;
+
+use crate::bit_ops::BitSet;
+
+/// A node in a [`Graph`], numbered from 0 in the order they were added.
+pub type NodeId = usize;
+
+#[derive(Debug, Clone)]
+pub struct Graph<N, E = ()> {
+    labels: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    /// The edges out of each node, with their weights, in the order they were added.
+    edges: Vec<Vec<(NodeId, E)>>,
+    directed: bool,
+}
+
+impl<N: Clone + Eq + Hash, E> Graph<N, E> {
+    /// A graph where each edge only goes one way.
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    /// A graph where each edge goes both ways.
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Self {
+            labels: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+            directed,
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// The node called `label`, which is added if there isn't one yet.
+    pub fn intern(&mut self, label: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+        self.edges.push(vec![]);
+        id
+    }
+
+    /// The node called `label`, if there is one.
+    pub fn id<Q>(&self, label: &Q) -> Option<NodeId>
+    where
+        N: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, node: NodeId) -> &N {
+        &self.labels[node]
+    }
+
+    /// How many nodes there are.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..self.len()
+    }
+
+    /// Add an edge between the nodes called `from` and `to`, adding them if they're new.
+    ///
+    /// Adding the same edge twice gives two edges.
+    pub fn add_edge(&mut self, from: N, to: N, weight: E) -> (NodeId, NodeId)
+    where
+        E: Clone,
+    {
+        let (from, to) = (self.intern(from), self.intern(to));
+        self.connect(from, to, weight);
+        (from, to)
+    }
+
+    /// Add an edge between two nodes which are already in the graph.
+    pub fn connect(&mut self, from: NodeId, to: NodeId, weight: E)
+    where
+        E: Clone,
+    {
+        if !self.directed && from != to {
+            self.edges[to].push((from, weight.clone()));
+        }
+        self.edges[from].push((to, weight));
+    }
+
+    /// The edges out of `node`, and their weights, in the order they were added.
+    pub fn edges(&self, node: NodeId) -> &[(NodeId, E)] {
+        &self.edges[node]
+    }
+
+    /// The nodes one edge on from `node`.
+    pub fn neighbours(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[node].iter().map(|&(next, _)| next)
+    }
+
+    /// The same graph with every edge turned round.
+    pub fn reversed(&self) -> Self
+    where
+        E: Clone,
+    {
+        let mut edges = vec![vec![]; self.len()];
+        for (from, out) in self.edges.iter().enumerate() {
+            for (to, weight) in out {
+                edges[*to].push((from, weight.clone()));
+            }
+        }
+        Self {
+            labels: self.labels.clone(),
+            ids: self.ids.clone(),
+            edges,
+            directed: self.directed,
+        }
+    }
+
+    /// Every node reachable from `start`, nearest first.
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = BitSet::with_capacity(self.len());
+        seen.insert(start);
+        let mut order = vec![];
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in self.neighbours(node) {
+                if seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// Every node reachable from `start`, in the order a depth first search first comes to them.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = BitSet::with_capacity(self.len());
+        let mut order = vec![];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if !seen.insert(node) {
+                continue;
+            }
+            order.push(node);
+            // pushed backwards, so that the first edge is followed first
+            stack.extend(self.edges[node].iter().rev().map(|&(next, _)| next));
+        }
+        order
+    }
+
+    /// Every node reachable from `start`, including itself.
+    pub fn reachable_from(&self, start: NodeId) -> BitSet {
+        self.dfs(start).into_iter().collect()
+    }
+
+    pub fn can_reach(&self, from: NodeId, to: NodeId) -> bool {
+        self.reachable_from(from).contains(to)
+    }
+
+    /// The nodes ordered so that every edge goes forwards, or `None` if there's a cycle. Ties go
+    /// to the node added first.
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut in_degree = vec![0; self.len()];
+        for next in self.edges.iter().flatten().map(|&(next, _)| next) {
+            in_degree[next] += 1;
+        }
+
+        let mut ready: VecDeque<NodeId> = self.nodes().filter(|&n| in_degree[n] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for next in self.neighbours(node) {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// The groups of nodes which can all reach each other, by Tarjan's algorithm. A component
+    /// comes before any which can reach it.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        const UNVISITED: usize = usize::MAX;
+        let mut index = vec![UNVISITED; self.len()];
+        let mut low_link = vec![0; self.len()];
+        let mut on_stack = BitSet::with_capacity(self.len());
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut next_index = 0;
+
+        for root in self.nodes() {
+            if index[root] != UNVISITED {
+                continue;
+            }
+
+            // each node being visited, and how many of its edges have been followed
+            let mut calls = vec![(root, 0)];
+            index[root] = next_index;
+            low_link[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack.insert(root);
+
+            while let Some(&mut (node, ref mut followed)) = calls.last_mut() {
+                if let Some(&(next, _)) = self.edges[node].get(*followed) {
+                    *followed += 1;
+                    if index[next] == UNVISITED {
+                        index[next] = next_index;
+                        low_link[next] = next_index;
+                        next_index += 1;
+                        stack.push(next);
+                        on_stack.insert(next);
+                        calls.push((next, 0));
+                    } else if on_stack.contains(next) {
+                        low_link[node] = low_link[node].min(index[next]);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(caller, _)) = calls.last() {
+                    low_link[caller] = low_link[caller].min(low_link[node]);
+                }
+                if low_link[node] == index[node] {
+                    let mut component = vec![];
+                    while let Some(member) = stack.pop() {
+                        on_stack.remove(member);
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// The graph in Graphviz's DOT language, to look at with `dot -Tsvg`. Edges are labelled
+    /// with `edge_label` of their weight, unless it's empty.
+    pub fn dot(&self, edge_label: impl Fn(&E) -> String) -> String
+    where
+        N: Display,
+    {
+        let quote = |s: String| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let mut out = format!("{kind} {{\n");
+        for (node, label) in self.labels.iter().enumerate() {
+            writeln!(out, "    {node} [label={}];", quote(label.to_string())).unwrap();
+        }
+        for (from, out_edges) in self.edges.iter().enumerate() {
+            for (to, weight) in out_edges {
+                // undirected edges are stored both ways round, but only drawn once
+                if !self.directed && *to < from {
+                    continue;
+                }
+                write!(out, "    {from} {arrow} {to}").unwrap();
+                match edge_label(weight) {
+                    label if label.is_empty() => out.push_str(";\n"),
+                    label => writeln!(out, " [label={}];", quote(label)).unwrap(),
+                }
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl<N, E> Index<NodeId> for Graph<N, E> {
+    type Output = N;
+
+    fn index(&self, node: NodeId) -> &N {
+        &self.labels[node]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&'static str, &'static str)]) -> Graph<&'static str> {
+        let mut graph = Graph::directed();
+        for &(from, to) in edges {
+            graph.add_edge(from, to, ());
+        }
+        graph
+    }
+
+    fn names(graph: &Graph<&'static str>, nodes: &[NodeId]) -> Vec<&'static str> {
+        nodes.iter().map(|&n| graph[n]).collect()
+    }
+
+    #[test]
+    fn interning() {
+        let mut graph: Graph<String, u32> = Graph::undirected();
+        let (a, b) = graph.add_edge("a".to_string(), "b".to_string(), 3);
+        assert_eq!(graph.intern("a".to_string()), a);
+        assert_eq!(graph.id("b"), Some(b));
+        assert_eq!(graph.id("c"), None);
+        assert_eq!(graph.label(b), "b");
+        assert_eq!(graph.edges(b), [(a, 3)]);
+        assert_eq!(graph.len(), 2);
+        assert!(!graph.is_directed());
+    }
+
+    #[test]
+    fn traversals() {
+        let graph = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("e", "a")]);
+        let a = graph.id("a").unwrap();
+        assert_eq!(names(&graph, &graph.bfs(a)), ["a", "b", "c", "d"]);
+        assert_eq!(names(&graph, &graph.dfs(a)), ["a", "b", "d", "c"]);
+        assert!(graph.can_reach(a, graph.id("d").unwrap()));
+        assert!(!graph.can_reach(a, graph.id("e").unwrap()));
+        assert_eq!(graph.reachable_from(a).len(), 4);
+        assert_eq!(graph.reversed().reachable_from(a).len(), 2);
+    }
+
+    #[test]
+    fn ordering() {
+        let graph = graph(&[
+            ("shirt", "tie"),
+            ("socks", "shoes"),
+            ("tie", "jacket"),
+            ("trousers", "shoes"),
+        ]);
+        let order = graph.topological_sort().unwrap();
+        assert_eq!(
+            names(&graph, &order),
+            ["shirt", "socks", "trousers", "tie", "shoes", "jacket"]
+        );
+
+        let cyclic = self::graph(&[("a", "b"), ("b", "a")]);
+        assert_eq!(cyclic.topological_sort(), None);
+    }
+
+    #[test]
+    fn components() {
+        let graph = graph(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "d"),
+            ("f", "f"),
+        ]);
+        let components: Vec<Vec<_>> = graph
+            .strongly_connected_components()
+            .iter()
+            .map(|component| {
+                let mut names = names(&graph, component);
+                names.sort();
+                names
+            })
+            .collect();
+        assert_eq!(components, [vec!["d", "e"], vec!["a", "b", "c"], vec!["f"]]);
+    }
+
+    #[test]
+    fn dot_output() {
+        let mut graph = Graph::undirected();
+        graph.add_edge("x", "say \"y\"", 2);
+        assert_eq!(
+            graph.dot(|w| w.to_string()),
+            "graph {\n    0 [label=\"x\"];\n    1 [label=\"say \\\"y\\\"\"];\n    0 -- 1 [label=\"2\"];\n}\n"
+        );
+        assert!(self::graph(&[("a", "b")])
+            .dot(|_| String::new())
+            .contains("0 -> 1;\n"));
+    }
+}
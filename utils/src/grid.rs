@@ -0,0 +1,394 @@
+//! A rectangular grid, which is what half of the puzzles are drawn on.
+//!
+//! Cells are stored row by row in one `Vec`, and addressed by `(row, col)` with `(0, 0)` in the top
+//! left, so a grid reads the same way round as the input it was parsed from:
+//!
+//! ```
+//! use aoc_utils::grid::Grid;
+//!
+//! let grid = Grid::parse("#..\n.#.\n", "\".\" or \"#\"", |c| match c {
+//!     '.' => Some(false),
+//!     '#' => Some(true),
+//!     _ => None,
+//! })
+//! .unwrap();
+//!
+//! assert_eq!((grid.height(), grid.width()), (2, 3));
+//! assert!(grid[(1, 1)]);
+//! assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), [(0, 1), (1, 0)]);
+//! assert_eq!(grid.rotate_cw().map(|&c| if c { '#' } else { '.' }).to_string(), ".#\n#.\n..\n");
+//! ```
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::parse::{ParseError, Source};
+
+/// A `(row, col)` position in a grid.
+pub type Pos = (usize, usize);
+
+/// Offsets to the cells above, left, right and below, in reading order.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets to all eight surrounding cells, in reading order.
+pub const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `cells` given row by row, which must be a whole number of rows of `width`.
+    pub fn from_vec(cells: Vec<T>, width: usize) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't make rows of {width}",
+            cells.len()
+        );
+        Self { cells, width }
+    }
+
+    /// Parse a grid with one character per cell, where `cell` turns a character into a cell, or
+    /// `None` if it isn't `expected`.
+    pub fn parse(
+        s: &str,
+        expected: &str,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Self::parse_within(&Source::new(s), s, expected, cell)
+    }
+
+    /// [`Grid::parse`] for a grid which is `fragment` of some larger input.
+    pub fn parse_within(
+        src: &Source,
+        fragment: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let width = fragment
+            .lines()
+            .next()
+            .map_or(0, |line| line.chars().count());
+        if width == 0 {
+            return Err(src.error(fragment, "a grid"));
+        }
+
+        let mut cells = Vec::with_capacity(fragment.len());
+        for line in fragment.lines() {
+            let row_start = cells.len();
+            for (i, c) in line.char_indices() {
+                cells.push(cell(c).ok_or_else(|| src.error_at(line, i, expected))?);
+            }
+            if cells.len() - row_start != width {
+                return Err(src.error(line, format!("a row {width} wide")));
+            }
+        }
+
+        Ok(Self { cells, width })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height() && col < self.width
+    }
+
+    /// Where `pos` is in [`Grid::cells`].
+    pub fn index_of(&self, (row, col): Pos) -> usize {
+        row * self.width + col
+    }
+
+    /// The position of the cell at `index` in [`Grid::cells`].
+    pub fn pos_of(&self, index: usize) -> Pos {
+        (index / self.width, index % self.width)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.index_of(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if !self.contains(pos) {
+            return None;
+        }
+        let index = self.index_of(pos);
+        Some(&mut self.cells[index])
+    }
+
+    /// The position `(d_row, d_col)` away from `pos`, if it's in the grid.
+    pub fn offset(&self, (row, col): Pos, (d_row, d_col): (isize, isize)) -> Option<Pos> {
+        let pos = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The cells above, left, right and below `pos` which are in the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// The cells surrounding `pos`, diagonals included, which are in the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        SURROUNDING
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// Every cell, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell, row by row, for which `pred` holds.
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.cells.iter().position(pred).map(|i| self.pos_of(i))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..][..self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..][..self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.cells.chunks_exact(self.width)
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(
+            col < self.width,
+            "column {col} is outside a grid {} wide",
+            self.width
+        );
+        self.cells[col..].iter().step_by(self.width)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+        }
+    }
+
+    /// A grid `width` wide of the same cells, where each `pos` in it takes this grid's cell at `f(pos)`.
+    fn remap(&self, width: usize, mut f: impl FnMut(Pos) -> Pos) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.cells.len() / width;
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|pos| self.cells[self.index_of(f(pos))].clone())
+            .collect();
+        Self { cells, width }
+    }
+
+    /// Swap rows for columns, flipping the grid over its leading diagonal.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height(), |(row, col)| (col, row))
+    }
+
+    /// Rotate a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height();
+        self.remap(height, |(row, col)| (height - 1 - col, row))
+    }
+
+    /// Rotate a quarter turn anticlockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.height(), |(row, col)| (col, width - 1 - row))
+    }
+
+    /// Mirror left to right, in place.
+    pub fn flip_horizontal(&mut self) {
+        for row in self.cells.chunks_exact_mut(self.width) {
+            row.reverse();
+        }
+    }
+
+    /// Every way round the grid can be turned or turned over: its four quarter turns clockwise,
+    /// starting from how it is now, then the same four of its mirror image.
+    pub fn orientations(&self) -> Vec<Self>
+    where
+        T: Clone,
+    {
+        let mut mirrored = self.clone();
+        mirrored.flip_horizontal();
+
+        let mut all = Vec::with_capacity(8);
+        for grid in [self.clone(), mirrored] {
+            let mut turned = grid;
+            for _ in 0..3 {
+                let next = turned.rotate_cw();
+                all.push(turned);
+                turned = next;
+            }
+            all.push(turned);
+        }
+        all
+    }
+
+    /// Mirror top to bottom, in place.
+    pub fn flip_vertical(&mut self) {
+        let height = self.height();
+        for row in 0..height / 2 {
+            let (top, bottom) = self.cells.split_at_mut((height - 1 - row) * self.width);
+            top[row * self.width..][..self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{pos:?} is outside the grid");
+        &self.cells[self.index_of(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{pos:?} is outside the grid");
+        let index = self.index_of(pos);
+        &mut self.cells[index]
+    }
+}
+
+/// Each row on a line of its own, the way the input was drawn.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef\n", "a letter", Some).unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let grid = letters();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        let digits = |c: char| c.to_digit(10);
+        let error = Grid::parse("12\n3x\n", "a digit", digits).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.expected.as_str()),
+            (2, 2, "a digit")
+        );
+        let error = Grid::parse("12\n345\n", "a digit", digits).unwrap_err();
+        assert_eq!((error.line, error.expected.as_str()), (2, "a row 2 wide"));
+        let error = Grid::parse("12\n3\n4567\n", "a digit", digits).unwrap_err();
+        assert_eq!((error.line, error.expected.as_str()), (2, "a row 2 wide"));
+        assert!(Grid::parse("", "a digit", digits).is_err());
+    }
+
+    #[test]
+    fn positions() {
+        let grid = letters();
+        assert_eq!(grid.index_of((1, 2)), 5);
+        assert_eq!(grid.pos_of(5), (1, 2));
+        assert_eq!(grid.get((1, 3)), None);
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+        assert_eq!(grid.offset((0, 0), (2, 0)), None);
+
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0), (1, 2)]
+        );
+        assert_eq!(
+            grid.neighbours8((0, 2)).collect::<Vec<_>>(),
+            [(0, 1), (1, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn transformations() {
+        let mut grid = letters();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+
+        grid.flip_horizontal();
+        assert_eq!(grid.to_string(), "cba\nfed\n");
+        grid.flip_vertical();
+        assert_eq!(grid.to_string(), "fed\ncba\n");
+    }
+
+    #[test]
+    fn orientations() {
+        let grid = letters();
+        let all = grid.orientations();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[0], grid);
+        assert_eq!(all[1], grid.rotate_cw());
+        assert_eq!(all[4].to_string(), "cba\nfed\n");
+        assert!(all.contains(&grid.transpose()));
+        assert!((1..8).all(|i| !all[..i].contains(&all[i])));
+    }
+}
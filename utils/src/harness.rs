@@ -0,0 +1,674 @@
+//! Runs a day's solution, printing its answers or a timing report for the runner.
+//!
+//! Every day's `main` hands its input, parser and parts to [`run`] (or [`run_both`] for days which
+//! solve both parts at once). Run normally it prints the answers as before, run with `--report`
+//! it repeats each phase `--reps` times and prints a [`Report`] instead:
+//!
+//! ```text
+//! answer 1 436
+//! answer 2 175594
+//! time parse 2140 1980 2010
+//! time part1 41210 40012 40535
+//! time part2 1630021034 1629813102 1631020230
+//! ```
+//!
+//! Times are in nanoseconds, one per repetition. Built with the `memory` feature, each phase also
+//! reports `memory phase <peak bytes> <allocations>`, the most of each seen in any repetition.
+//!
+//! A part which finds no answer for its input returns a [`Maybe`] holding none, and its `answer`
+//! line is left out. A day which can't run at all, say because its
+//! input is missing, reports `skipped <reason>` from [`skip`] instead. Any other lines are the
+//! day's own output, and are ignored when parsing a report.
+//!
+//! `--input <path>` runs the day on another input than its own, such as one from
+//! [`generate`](crate::generate).
+//!
+//! `--record <dir>` saves animations of the days which draw their grids as they go, see
+//! [`record`](crate::record), as GIFs or with `--record-as ppm` as PPM images.
+//!
+//! `--trace <names>` prints what a day built with the `trace` feature reports from inside the
+//! spans or events in `names`, or into a file with `--trace-to <path>`, see
+//! [`trace`](crate::trace). Each phase is a span named after it.
+//!
+//! `--explain` has the days which can show their working print it as they go, in any build, see
+//! [`explaining`].
+
+use std::env;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::hint::black_box;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::inputs;
+use crate::record::{self, Format};
+use crate::trace::{self, Filter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+    /// Both parts computed together, for days which can't separate them.
+    Solve,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 4] = [Phase::Parse, Phase::Part1, Phase::Part2, Phase::Solve];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+            Phase::Solve => "solve",
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Phase::ALL
+            .into_iter()
+            .find(|phase| phase.name() == s)
+            .ok_or_else(|| anyhow!("Unknown phase {s:?}"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let mid = sorted.len() / 2;
+        let median = match sorted.len() {
+            0 => return None,
+            n if n % 2 == 0 => (sorted[mid - 1] + sorted[mid]) / 2,
+            _ => sorted[mid],
+        };
+
+        Some(Self {
+            median,
+            min: sorted[0],
+        })
+    }
+}
+
+/// The heap a phase used, see [`memory`](crate::memory).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Memory {
+    /// The most allocated at once, on top of what was live before the phase started.
+    pub peak_bytes: usize,
+    pub allocations: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub answers: [Option<String>; 2],
+    pub samples: Vec<(Phase, Vec<Duration>)>,
+    /// Only measured with the `memory` feature.
+    pub memory: Vec<(Phase, Memory)>,
+    /// Why the day didn't run, if it didn't.
+    pub skipped: Option<String>,
+}
+
+impl Report {
+    pub fn samples(&self, phase: Phase) -> &[Duration] {
+        self.samples
+            .iter()
+            .find(|(p, _)| *p == phase)
+            .map_or(&[], |(_, samples)| samples)
+    }
+
+    pub fn stats(&self, phase: Phase) -> Option<Stats> {
+        Stats::from_samples(self.samples(phase))
+    }
+
+    pub fn memory(&self, phase: Phase) -> Option<Memory> {
+        self.memory
+            .iter()
+            .find(|(p, _)| *p == phase)
+            .map(|&(_, memory)| memory)
+    }
+
+    fn record(&mut self, phase: Phase, elapsed: Duration, memory: Option<Memory>) {
+        match self.samples.iter_mut().find(|(p, _)| *p == phase) {
+            Some((_, samples)) => samples.push(elapsed),
+            None => self.samples.push((phase, vec![elapsed])),
+        }
+
+        let Some(memory) = memory else {
+            return;
+        };
+        match self.memory.iter_mut().find(|(p, _)| *p == phase) {
+            Some((_, most)) => {
+                most.peak_bytes = most.peak_bytes.max(memory.peak_bytes);
+                most.allocations = most.allocations.max(memory.allocations);
+            }
+            None => self.memory.push((phase, memory)),
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(reason) = &self.skipped {
+            writeln!(f, "skipped {}", escape(reason))?;
+        }
+
+        for (part, answer) in (1..).zip(&self.answers) {
+            if let Some(answer) = answer {
+                writeln!(f, "answer {part} {}", escape(answer))?;
+            }
+        }
+
+        for (phase, samples) in &self.samples {
+            write!(f, "time {}", phase.name())?;
+            for sample in samples {
+                write!(f, " {}", sample.as_nanos())?;
+            }
+            writeln!(f)?;
+        }
+
+        for (phase, memory) in &self.memory {
+            writeln!(
+                f,
+                "memory {} {} {}",
+                phase.name(),
+                memory.peak_bytes,
+                memory.allocations
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Report {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut report = Report::default();
+
+        for line in s.lines() {
+            match line.split_once(' ') {
+                Some(("answer", rest)) => {
+                    let (part, answer) = rest
+                        .split_once(' ')
+                        .ok_or_else(|| anyhow!("Missing answer in {line:?}"))?;
+                    let slot = match part {
+                        "1" => &mut report.answers[0],
+                        "2" => &mut report.answers[1],
+                        _ => bail!("Unknown part {part:?} in {line:?}"),
+                    };
+                    *slot = Some(unescape(answer));
+                }
+                Some(("time", rest)) => {
+                    let mut fields = rest.split(' ');
+                    let phase = fields.next().unwrap_or_default().parse()?;
+                    let samples = fields
+                        .map(|ns| Ok(Duration::from_nanos(ns.parse()?)))
+                        .collect::<Result<_>>()
+                        .with_context(|| format!("Bad sample in {line:?}"))?;
+                    report.samples.push((phase, samples));
+                }
+                Some(("memory", rest)) => {
+                    let fields: Vec<_> = rest.split(' ').collect();
+                    let [phase, peak_bytes, allocations] = fields[..] else {
+                        bail!("Expected a phase, peak and allocations in {line:?}");
+                    };
+                    let memory = Memory {
+                        peak_bytes: peak_bytes.parse()?,
+                        allocations: allocations.parse()?,
+                    };
+                    report.memory.push((phase.parse()?, memory));
+                }
+                Some(("skipped", reason)) => report.skipped = Some(unescape(reason)),
+                _ => {}
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Answers go on a single line, so escape any newlines (e.g. 2021 day 13's ASCII art).
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => out.push('\n'),
+            ('\\', Some('\\')) => out.push('\\'),
+            _ => {
+                out.push(c);
+                continue;
+            }
+        }
+        chars.next();
+    }
+
+    out
+}
+
+/// What a part gives back as its answer: anything which displays, or a [`Maybe`].
+pub trait IntoAnswer {
+    fn into_answer(self) -> Option<String>;
+}
+
+impl<T: Display> IntoAnswer for T {
+    fn into_answer(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+/// The answer of a part which mightn't have one for every input.
+pub struct Maybe<T>(pub Option<T>);
+
+impl<T: Display> IntoAnswer for Maybe<T> {
+    fn into_answer(self) -> Option<String> {
+        self.0.map(|answer| answer.to_string())
+    }
+}
+
+/// Run `f`, counting the heap it uses if the `memory` feature is on.
+fn counted<T>(f: impl FnOnce() -> T) -> (T, Option<Memory>) {
+    #[cfg(feature = "memory")]
+    {
+        let (value, memory) = crate::memory::count(f);
+        (value, Some(memory))
+    }
+
+    #[cfg(not(feature = "memory"))]
+    (f(), None)
+}
+
+fn timed<T>(report: &mut Report, phase: Phase, f: impl FnOnce() -> T) -> T {
+    let _span = trace::span(phase.name());
+    let start = Instant::now();
+    let (value, memory) = counted(|| black_box(f()));
+    report.record(phase, start.elapsed(), memory);
+    value
+}
+
+/// Time `reps` repetitions of parsing `input` and running each part on the result.
+pub fn measure<'a, I, E: Into<anyhow::Error>, A: IntoAnswer, B: IntoAnswer>(
+    reps: usize,
+    input: &'a str,
+    parse: impl Fn(&'a str) -> Result<I, E>,
+    part1: impl Fn(&I) -> A,
+    part2: impl Fn(&I) -> B,
+) -> Result<Report> {
+    let mut report = Report::default();
+
+    for _ in 0..reps.max(1) {
+        let parsed =
+            timed(&mut report, Phase::Parse, || parse(black_box(input))).map_err(Into::into)?;
+        let answer_1 = timed(&mut report, Phase::Part1, || part1(&parsed));
+        let answer_2 = timed(&mut report, Phase::Part2, || part2(&parsed));
+        report.answers = [answer_1.into_answer(), answer_2.into_answer()];
+    }
+
+    Ok(report)
+}
+
+/// Like [`measure`], for days which compute both parts in one go. As the last user of the parsed
+/// input, `solve` gets to take ownership of it.
+pub fn measure_both<'a, I, E: Into<anyhow::Error>, A: IntoAnswer, B: IntoAnswer>(
+    reps: usize,
+    input: &'a str,
+    parse: impl Fn(&'a str) -> Result<I, E>,
+    solve: impl Fn(I) -> (A, B),
+) -> Result<Report> {
+    let mut report = Report::default();
+
+    for _ in 0..reps.max(1) {
+        let parsed =
+            timed(&mut report, Phase::Parse, || parse(black_box(input))).map_err(Into::into)?;
+        let (answer_1, answer_2) = timed(&mut report, Phase::Solve, || solve(parsed));
+        report.answers = [answer_1.into_answer(), answer_2.into_answer()];
+    }
+
+    Ok(report)
+}
+
+/// Options a day binary understands, all of which the runner passes in when collecting reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Options {
+    report: bool,
+    reps: usize,
+    input: Option<PathBuf>,
+    record: Option<PathBuf>,
+    record_as: Format,
+    trace: Option<Filter>,
+    trace_to: Option<PathBuf>,
+    explain: bool,
+}
+
+static EXPLAINING: AtomicBool = AtomicBool::new(false);
+
+/// Whether the day was run with `--explain`, so should print how it came to its answers, such as
+/// why it turned something down.
+pub fn explaining() -> bool {
+    EXPLAINING.load(Ordering::Relaxed)
+}
+
+impl Options {
+    fn from_args() -> Result<Self> {
+        let mut options = Options {
+            report: false,
+            reps: 1,
+            input: None,
+            record: None,
+            record_as: Format::Gif,
+            trace: None,
+            trace_to: None,
+            explain: false,
+        };
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--report" => options.report = true,
+                "--reps" => {
+                    options.reps = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--reps needs a value"))?
+                        .parse()
+                        .context("--reps must be a number")?;
+                }
+                "--input" => {
+                    let path = args.next().ok_or_else(|| anyhow!("--input needs a path"))?;
+                    options.input = Some(path.into());
+                }
+                "--record" => {
+                    let dir = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--record needs a directory"))?;
+                    options.record = Some(dir.into());
+                }
+                "--record-as" => {
+                    options.record_as = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--record-as needs a format"))?
+                        .parse()?;
+                }
+                "--trace" => {
+                    options.trace = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--trace needs names to trace, or all"))?
+                            .parse()?,
+                    );
+                }
+                "--trace-to" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--trace-to needs a path"))?;
+                    options.trace_to = Some(path.into());
+                }
+                "--explain" => options.explain = true,
+                _ => bail!("Unknown argument {arg:?}"),
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// The input given with `--input`, or else the day's own.
+    fn input(&self, own: &'static str) -> Result<&'static str> {
+        self.input.as_deref().map_or(Ok(own), load_given)
+    }
+
+    /// Start handing out recorders, if `--record` asked for them.
+    fn start_recording(&self) -> Result<()> {
+        if let Some(dir) = &self.record {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Couldn't create {}", dir.display()))?;
+            record::record_into(dir.clone(), self.record_as);
+        }
+        Ok(())
+    }
+
+    /// Start writing out trace events, if `--trace` asked for them.
+    fn start_tracing(&self) -> Result<()> {
+        let Some(filter) = &self.trace else {
+            if self.trace_to.is_some() {
+                bail!("--trace-to needs --trace to say what to trace");
+            }
+            return Ok(());
+        };
+        if !trace::ENABLED {
+            bail!("This day was built without the trace feature, so has nothing to trace");
+        }
+
+        let out: Box<dyn io::Write + Send> = match &self.trace_to {
+            Some(path) => {
+                Box::new(BufWriter::new(File::create(path).with_context(|| {
+                    format!("Couldn't create {}", path.display())
+                })?))
+            }
+            None => Box::new(io::stderr()),
+        };
+        trace::trace_to(filter.clone(), out);
+        Ok(())
+    }
+
+    fn start_explaining(&self) {
+        if self.explain {
+            EXPLAINING.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn emit(&self, report: &Report) {
+        trace::flush();
+
+        if self.report {
+            print!("{report}");
+            return;
+        }
+
+        if let Some(reason) = &report.skipped {
+            println!("skipped: {reason}");
+        }
+
+        for (part, answer) in (1..).zip(&report.answers) {
+            match answer {
+                Some(answer) if answer.contains('\n') => println!("Part {part}:\n{answer}"),
+                Some(answer) => println!("Part {part}: {answer}"),
+                None if report.skipped.is_none() => println!("Part {part}: no answer"),
+                None => {}
+            }
+        }
+    }
+}
+
+/// Entry point for a day's binary, see the module docs.
+pub fn run<I, E: Into<anyhow::Error>, A: IntoAnswer, B: IntoAnswer>(
+    input: &'static str,
+    parse: impl Fn(&'static str) -> Result<I, E>,
+    part1: impl Fn(&I) -> A,
+    part2: impl Fn(&I) -> B,
+) -> Result<()> {
+    let options = Options::from_args()?;
+    let input = options.input(input)?;
+    options.start_recording()?;
+    options.start_tracing()?;
+    options.start_explaining();
+    options.emit(&measure(options.reps, input, parse, part1, part2)?);
+    Ok(())
+}
+
+/// Like [`run`], for days which compute both parts in one go.
+pub fn run_both<I, E: Into<anyhow::Error>, A: IntoAnswer, B: IntoAnswer>(
+    input: &'static str,
+    parse: impl Fn(&'static str) -> Result<I, E>,
+    solve: impl Fn(I) -> (A, B),
+) -> Result<()> {
+    let options = Options::from_args()?;
+    let input = options.input(input)?;
+    options.start_recording()?;
+    options.start_tracing()?;
+    options.start_explaining();
+    options.emit(&measure_both(options.reps, input, parse, solve)?);
+    Ok(())
+}
+
+/// Load the input at `path` like [`inputs::load`], unless `--input` gives another one to use.
+pub fn load_input(path: impl AsRef<Path>) -> Result<Option<&'static str>> {
+    match Options::from_args()?.input {
+        Some(input) => load_given(&input).map(Some),
+        None => inputs::load(path),
+    }
+}
+
+/// Unlike a day's own input, one given with `--input` had better be there.
+fn load_given(path: &Path) -> Result<&'static str> {
+    inputs::load(path)?.ok_or_else(|| anyhow!("There's no input at {}", path.display()))
+}
+
+/// Entry point for a day which can't run, i.e. because its input is missing.
+pub fn skip(reason: &str) -> Result<()> {
+    let options = Options::from_args()?;
+    options.emit(&Report {
+        skipped: Some(reason.to_string()),
+        ..Report::default()
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Vec<u32>> {
+        Ok(s.lines().map(str::parse).collect::<Result<_, _>>()?)
+    }
+
+    #[test]
+    fn measure_records_every_phase() {
+        let report = measure(
+            3,
+            "1\n2\n3\n",
+            parse,
+            |v| v.iter().sum::<u32>(),
+            |v| v.len(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            report.answers,
+            [Some("6".to_string()), Some("3".to_string())]
+        );
+        for phase in [Phase::Parse, Phase::Part1, Phase::Part2] {
+            assert_eq!(report.samples(phase).len(), 3);
+        }
+        assert!(report.samples(Phase::Solve).is_empty());
+    }
+
+    #[test]
+    fn measure_both_records_solve() {
+        let report = measure_both(2, "4\n5\n", parse, |v: Vec<u32>| (v[0], v[1])).unwrap();
+
+        assert_eq!(
+            report.answers,
+            [Some("4".to_string()), Some("5".to_string())]
+        );
+        assert_eq!(report.samples(Phase::Solve).len(), 2);
+        assert!(report.samples(Phase::Part1).is_empty());
+    }
+
+    #[test]
+    fn parts_without_an_answer() {
+        let report = measure(
+            1,
+            "1\n2\n",
+            parse,
+            |v| Maybe(v.iter().find(|&&n| n > 5).copied()),
+            |v| Maybe(v.first().copied()),
+        )
+        .unwrap();
+
+        assert_eq!(report.answers, [None, Some("1".to_string())]);
+        let text = report.to_string();
+        assert!(!text.contains("answer 1"), "{text}");
+        assert_eq!(text.parse::<Report>().unwrap().answers, report.answers);
+    }
+
+    #[test]
+    fn measure_propagates_parse_errors() {
+        assert!(measure(1, "x\n", parse, |v| v.len(), |v| v.len()).is_err());
+    }
+
+    #[test]
+    fn report_round_trip() {
+        let report = Report {
+            answers: [Some("17".to_string()), Some("█..█\n.\\█.".to_string())],
+            samples: vec![
+                (
+                    Phase::Parse,
+                    vec![Duration::from_nanos(10), Duration::from_nanos(12)],
+                ),
+                (
+                    Phase::Solve,
+                    vec![Duration::from_micros(3), Duration::from_micros(4)],
+                ),
+            ],
+            memory: vec![(
+                Phase::Parse,
+                Memory {
+                    peak_bytes: 4096,
+                    allocations: 3,
+                },
+            )],
+            skipped: None,
+        };
+
+        let text = report.to_string();
+        assert_eq!(text.lines().count(), 5);
+        assert!(text.contains("\nmemory parse 4096 3\n"), "{text}");
+        assert_eq!(text.parse::<Report>().unwrap(), report);
+
+        let noisy = format!("edge = 0011011101\n{text}(0, 1, 2203)\n");
+        assert_eq!(noisy.parse::<Report>().unwrap(), report);
+    }
+
+    #[test]
+    fn skipped_report() {
+        let report = Report {
+            skipped: Some("input missing".to_string()),
+            ..Report::default()
+        };
+
+        let text = report.to_string();
+        assert_eq!(text, "skipped input missing\n");
+        assert_eq!(text.parse::<Report>().unwrap(), report);
+    }
+
+    #[test]
+    fn stats_median_and_min() {
+        let samples: Vec<_> = [5, 1, 4, 2].into_iter().map(Duration::from_nanos).collect();
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
@@ -0,0 +1,76 @@
+//! Loading puzzle inputs at runtime.
+//!
+//! Puzzle inputs are personal, so not every year has them committed. `include_str!`ing a missing
+//! input stops the whole target building, so days, tests and benches load their inputs when they
+//! run instead, and skip themselves with "skipped: input missing" when there isn't one.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Why a day was skipped, when its input isn't there.
+pub const MISSING: &str = "input missing";
+
+/// The path of an input relative to the calling crate, i.e. `input_path!("inputs/day01.txt")`.
+#[macro_export]
+macro_rules! input_path {
+    ($path:literal) => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/", $path)
+    };
+}
+
+/// Load the input at `path`, or return from the enclosing test or bench after saying it was
+/// skipped.
+#[macro_export]
+macro_rules! input_or_skip {
+    ($path:expr) => {
+        match $crate::inputs::load($path) {
+            Ok(Some(input)) => input,
+            Ok(None) => {
+                println!("skipped: {} ({})", $crate::inputs::MISSING, $path);
+                return;
+            }
+            Err(e) => panic!("{e:#}"),
+        }
+    };
+}
+
+/// Read the input at `path`, or `None` if it doesn't exist.
+///
+/// Solutions are free to borrow from their input for as long as they like, so it is leaked.
+pub fn load(path: impl AsRef<Path>) -> Result<Option<&'static str>> {
+    let path = path.as_ref();
+    match fs::read_to_string(path) {
+        Ok(input) => Ok(Some(input.leak())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_input() {
+        assert_eq!(load(input_path!("inputs/no_such_day.txt")).unwrap(), None);
+    }
+
+    #[test]
+    fn present_input() {
+        let manifest = load(input_path!("Cargo.toml")).unwrap().unwrap();
+        assert!(manifest.contains("aoc_utils"));
+    }
+
+    #[test]
+    fn skips_when_missing() {
+        let mut ran = false;
+        (|| {
+            let _input = input_or_skip!(input_path!("inputs/no_such_day.txt"));
+            ran = true;
+        })();
+        assert!(!ran);
+    }
+}
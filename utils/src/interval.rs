@@ -0,0 +1,343 @@
+//! Sets of integers kept as ranges, and maps which shift each range by its own offset.
+//!
+//! Puzzles about seeds, ticket fields and the like deal in ranges billions wide, which are far
+//! too many values to handle one at a time but only a handful of ranges:
+//!
+//! ```
+//! use aoc_utils::interval::{IntervalSet, RangeMap};
+//!
+//! let seeds: IntervalSet = [79..93, 55..68].into_iter().collect();
+//! // 50..98 moves up by 2, and 98..100 down to 50..52
+//! let soil: RangeMap = [(98..100, -48), (50..98, 2)].into_iter().collect();
+//!
+//! assert_eq!(soil.get(79), 81);
+//! assert_eq!(soil.apply(&seeds).ranges(), [57..70, 81..95]);
+//! assert_eq!(seeds.len(), 27);
+//! ```
+//!
+//! Ranges are half-open, like `Range`, and everything is `i64` so that offsets can go either way.
+
+use std::ops::Range;
+
+use crate::checked;
+
+/// A set of integers, as sorted ranges which neither overlap nor touch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    ranges: Vec<Range<i64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sort `ranges` and merge any which overlap or touch, dropping the empty ones.
+    fn coalesce(mut ranges: Vec<Range<i64>>) -> Self {
+        ranges.retain(|r| !r.is_empty());
+        ranges.sort_unstable_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<i64>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    /// The ranges making up the set, in order.
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    pub fn insert(&mut self, range: Range<i64>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::coalesce(ranges);
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= n);
+        self.ranges.get(i).is_some_and(|r| r.start <= n)
+    }
+
+    /// How many integers are in the set.
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|r| r.start.abs_diff(r.end)).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.ranges.last().map(|r| r.end - 1)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::coalesce(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let overlap = x.start.max(y.start)..x.end.min(y.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            // whichever ends first can't overlap anything further on
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Everything in this set which isn't in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut others = other.ranges.iter().peekable();
+        for range in &self.ranges {
+            let mut start = range.start;
+            // skip those which end before this range, but not any which carry on into the next
+            while others.next_if(|cut| cut.end <= start).is_some() {}
+            while let Some(cut) = others.peek().filter(|cut| cut.start < range.end) {
+                if cut.start > start {
+                    ranges.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+                if cut.end > range.end {
+                    break;
+                }
+                others.next();
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl From<Range<i64>> for IntervalSet {
+    fn from(range: Range<i64>) -> Self {
+        Self::coalesce(vec![range])
+    }
+}
+
+impl FromIterator<Range<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        Self::coalesce(iter.into_iter().collect())
+    }
+}
+
+/// A function on the integers which adds its own offset to each of a set of ranges, and leaves
+/// everything else where it is.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    /// Sorted ranges which don't overlap, and what each adds.
+    pieces: Vec<(Range<i64>, i64)>,
+}
+
+impl RangeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `offset` to everything in `range`, apart from what an earlier piece already covers.
+    pub fn insert(&mut self, range: Range<i64>, offset: i64) {
+        let covered: IntervalSet = self.pieces.iter().map(|(r, _)| r.clone()).collect();
+        let uncovered = IntervalSet::from(range).difference(&covered);
+        self.pieces
+            .extend(uncovered.ranges.into_iter().map(|r| (r, offset)));
+        self.pieces.sort_unstable_by_key(|(r, _)| r.start);
+    }
+
+    pub fn get(&self, n: i64) -> i64 {
+        let i = self.pieces.partition_point(|(r, _)| r.end <= n);
+        match self.pieces.get(i) {
+            Some((r, offset)) if r.start <= n => checked::add(n, *offset),
+            _ => n,
+        }
+    }
+
+    /// `range` cut up wherever the offset changes, with the offset of each part.
+    fn split(&self, range: Range<i64>) -> impl Iterator<Item = (Range<i64>, i64)> + '_ {
+        let first = self.pieces.partition_point(|(r, _)| r.end <= range.start);
+        let mut start = range.start;
+        let mut pieces = self.pieces[first..]
+            .iter()
+            .take_while(move |(r, _)| r.start < range.end)
+            .peekable();
+
+        std::iter::from_fn(move || {
+            if start >= range.end {
+                return None;
+            }
+            match pieces.peek() {
+                Some((r, _)) if r.start > start => {
+                    let gap = start..r.start;
+                    start = r.start;
+                    Some((gap, 0))
+                }
+                Some((r, offset)) => {
+                    let part = start..r.end.min(range.end);
+                    start = part.end;
+                    let offset = *offset;
+                    pieces.next();
+                    Some((part, offset))
+                }
+                None => {
+                    let rest = start..range.end;
+                    start = range.end;
+                    Some((rest, 0))
+                }
+            }
+        })
+    }
+
+    /// Where everything in `set` ends up.
+    pub fn apply(&self, set: &IntervalSet) -> IntervalSet {
+        set.ranges
+            .iter()
+            .flat_map(|range| self.split(range.clone()))
+            .map(|(r, offset)| checked::add(r.start, offset)..checked::add(r.end, offset))
+            .collect()
+    }
+
+    /// The map which does this one and then `next`.
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        // every piece of this map, and the gaps between them, as far as they can go either way
+        let mut segments = vec![];
+        let mut start = i64::MIN;
+        for (range, offset) in &self.pieces {
+            segments.push((start..range.start, 0));
+            segments.push((range.clone(), *offset));
+            start = range.end;
+        }
+        segments.push((start..i64::MAX, 0));
+
+        let mut pieces: Vec<(Range<i64>, i64)> = vec![];
+        for (range, offset) in segments.into_iter().filter(|(r, _)| !r.is_empty()) {
+            let moved = range.start + offset..range.end + offset;
+            for (part, next_offset) in next.split(moved) {
+                let total = offset + next_offset;
+                let part = part.start - offset..part.end - offset;
+                match pieces.last_mut() {
+                    Some((last, last_offset))
+                        if last.end == part.start && *last_offset == total =>
+                    {
+                        last.end = part.end;
+                    }
+                    _ => pieces.push((part, total)),
+                }
+            }
+        }
+        pieces.retain(|&(_, offset)| offset != 0);
+        RangeMap { pieces }
+    }
+}
+
+impl FromIterator<(Range<i64>, i64)> for RangeMap {
+    fn from_iter<I: IntoIterator<Item = (Range<i64>, i64)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (range, offset) in iter {
+            map.insert(range, offset);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i64>]) -> IntervalSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn coalescing() {
+        let s = set(&[5..8, 0..2, 2..3, 7..10, 4..4]);
+        assert_eq!(s.ranges(), [0..3, 5..10]);
+        assert_eq!((s.len(), s.min(), s.max()), (8, Some(0), Some(9)));
+        assert!(s.contains(2) && s.contains(5) && s.contains(9));
+        assert!(!s.contains(3) && !s.contains(10) && !s.contains(-1));
+
+        let mut s = s;
+        s.insert(3..5);
+        assert_eq!(s, IntervalSet::from(0..10));
+        assert!(IntervalSet::new().is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..10, 20..30]);
+        let b = set(&[5..25, 28..40]);
+        assert_eq!(a.union(&b), IntervalSet::from(0..40));
+        assert_eq!(a.intersection(&b).ranges(), [5..10, 20..25, 28..30]);
+        assert_eq!(a.difference(&b).ranges(), [0..5, 25..28]);
+        assert_eq!(b.difference(&a).ranges(), [10..20, 30..40]);
+
+        let holes = set(&[1..2, 3..4, 8..20]);
+        assert_eq!(
+            set(&[0..5, 6..10]).difference(&holes).ranges(),
+            [0..1, 2..3, 4..5, 6..8]
+        );
+        assert_eq!(a.difference(&a), IntervalSet::new());
+    }
+
+    #[test]
+    fn set_operations_agree_with_values() {
+        let a = set(&[-3..4, 6..9, 12..13]);
+        let b = set(&[0..7, 8..15]);
+        for n in -5..20 {
+            assert_eq!(a.union(&b).contains(n), a.contains(n) || b.contains(n));
+            assert_eq!(
+                a.intersection(&b).contains(n),
+                a.contains(n) && b.contains(n)
+            );
+            assert_eq!(
+                a.difference(&b).contains(n),
+                a.contains(n) && !b.contains(n)
+            );
+        }
+    }
+
+    #[test]
+    fn earlier_pieces_win() {
+        let map: RangeMap = [(10..20, 5), (15..30, -5)].into_iter().collect();
+        assert_eq!(map.get(9), 9);
+        assert_eq!(map.get(17), 22);
+        assert_eq!(map.get(25), 20);
+        assert_eq!(map.get(30), 30);
+    }
+
+    #[test]
+    fn applying_and_composing() {
+        let first: RangeMap = [(0..10, 100), (20..30, -20)].into_iter().collect();
+        let second: RangeMap = [(5..15, 1), (100..105, -100)].into_iter().collect();
+        let both = first.then(&second);
+        for n in -5..40 {
+            assert_eq!(both.get(n), second.get(first.get(n)), "at {n}");
+        }
+
+        let everything = IntervalSet::from(-5..40);
+        let values: IntervalSet = (-5..40).map(|n| both.get(n)..both.get(n) + 1).collect();
+        assert_eq!(both.apply(&everything), values);
+        assert_eq!(second.apply(&first.apply(&everything)), values);
+        assert_eq!(RangeMap::new().then(&first), first);
+    }
+}
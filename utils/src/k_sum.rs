@@ -0,0 +1,206 @@
+//! Finding `k` numbers out of a list which add up to a target, as in 2020 day 1.
+//!
+//! Combinations are of positions in the list rather than values, so repeated values can each be
+//! picked, and are listed once each with their indices in increasing order:
+//!
+//! ```
+//! use aoc_utils::k_sum;
+//!
+//! let numbers = [1721, 979, 366, 299, 675, 1456];
+//! assert_eq!(k_sum::combinations(&numbers, 2, 2020), Some(vec![vec![0, 3]]));
+//! assert_eq!(k_sum::combinations(&numbers, 3, 2020), Some(vec![vec![1, 2, 4]]));
+//! assert_eq!(k_sum::combinations(&numbers, 2, 1), None);
+//! ```
+//!
+//! Pairs are found with a hash of the numbers seen so far, in a single pass, and triples with a
+//! pass like that after each first number. From four numbers on, every combination of the first
+//! half is hashed by its sum and looked up from every combination of the second half, which takes
+//! around `n^(k/2)` steps rather than `n^(k-1)`, though it also keeps that many combinations.
+//! Nothing is assumed about the numbers' signs or order. Sums are worked out as `i128`s, which
+//! any number of up to 64 bits fits in many times over, so a combination is still found when part
+//! of it goes outside the numbers' own type, as with `i64::MAX + 1 - 1`.
+
+use std::collections::HashMap;
+
+/// Every combination of `k` indices of `numbers` whose numbers add up to `target`, in order, or
+/// `None` if there aren't any.
+pub fn combinations<T: Copy + Into<i128>>(
+    numbers: &[T],
+    k: usize,
+    target: T,
+) -> Option<Vec<Vec<usize>>> {
+    let numbers: Vec<i128> = numbers.iter().map(|&n| n.into()).collect();
+    let numbers = &numbers[..];
+    let target = target.into();
+    let mut found = vec![];
+    match k {
+        0 => {
+            if target == 0 {
+                found.push(vec![]);
+            }
+        }
+        1 => found.extend(
+            (0..numbers.len())
+                .filter(|&i| numbers[i] == target)
+                .map(|i| vec![i]),
+        ),
+        2 => pairs(numbers, 0, target, &[], &mut found),
+        3 => {
+            for (first, &n) in numbers.iter().enumerate() {
+                pairs(numbers, first + 1, target - n, &[first], &mut found);
+            }
+        }
+        _ => meet_in_the_middle(numbers, k, target, &mut found),
+    }
+
+    found.sort_unstable();
+    (!found.is_empty()).then_some(found)
+}
+
+/// Every pair of indices from `from` on adding up to `target`, after `prefix`.
+fn pairs(
+    numbers: &[i128],
+    from: usize,
+    target: i128,
+    prefix: &[usize],
+    found: &mut Vec<Vec<usize>>,
+) {
+    let mut seen: HashMap<i128, Vec<usize>> = HashMap::new();
+    for (second, &n) in numbers.iter().enumerate().skip(from) {
+        if let Some(firsts) = seen.get(&(target - n)) {
+            for &first in firsts {
+                found.push([prefix, &[first, second]].concat());
+            }
+        }
+        seen.entry(n).or_default().push(second);
+    }
+}
+
+/// Every combination of `k` indices, splitting each into its first `k / 2` and the rest. A
+/// combination is only found the once, from the split where the halves don't interleave.
+fn meet_in_the_middle(numbers: &[i128], k: usize, target: i128, found: &mut Vec<Vec<usize>>) {
+    let mut firsts: HashMap<i128, Vec<Vec<usize>>> = HashMap::new();
+    each_combination(numbers, k / 2, |indices, sum| {
+        firsts.entry(sum).or_default().push(indices.to_vec());
+    });
+
+    each_combination(numbers, k - k / 2, |seconds, sum| {
+        let Some(matching) = firsts.get(&(target - sum)) else {
+            return;
+        };
+        for first in matching {
+            if first.last() < seconds.first() {
+                found.push([first, seconds].concat());
+            }
+        }
+    });
+}
+
+/// Call `f` with every combination of `k` indices of `numbers`, and the sum of their numbers.
+fn each_combination(numbers: &[i128], k: usize, mut f: impl FnMut(&[usize], i128)) {
+    fn go(
+        numbers: &[i128],
+        k: usize,
+        from: usize,
+        indices: &mut Vec<usize>,
+        sum: i128,
+        f: &mut impl FnMut(&[usize], i128),
+    ) {
+        if indices.len() == k {
+            f(indices, sum);
+            return;
+        }
+
+        // leave room for the rest of the combination after this index
+        let last = numbers.len() + indices.len() + 1 - k;
+        for i in from..last {
+            indices.push(i);
+            go(numbers, k, i + 1, indices, sum + numbers[i], f);
+            indices.pop();
+        }
+    }
+
+    if k <= numbers.len() {
+        go(numbers, k, 0, &mut Vec::with_capacity(k), 0, &mut f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every combination the slow way, going through every subset of the numbers.
+    fn brute_force(numbers: &[i64], k: usize, target: i64) -> Option<Vec<Vec<usize>>> {
+        let mut found = vec![];
+        for subset in 0..1u32 << numbers.len() {
+            let indices: Vec<usize> = (0..numbers.len())
+                .filter(|&i| subset & 1 << i != 0)
+                .collect();
+            let sum: i128 = indices.iter().map(|&i| i128::from(numbers[i])).sum();
+            if indices.len() == k && sum == i128::from(target) {
+                found.push(indices);
+            }
+        }
+        found.sort_unstable();
+        (!found.is_empty()).then_some(found)
+    }
+
+    #[test]
+    fn repeated_and_negative_numbers() {
+        assert_eq!(
+            combinations(&[5, 5, 5], 2, 10),
+            Some(vec![vec![0, 1], vec![0, 2], vec![1, 2]])
+        );
+        assert_eq!(
+            combinations(&[-3, 7, 0, -4, 4], 3, 0),
+            Some(vec![vec![0, 1, 3], vec![2, 3, 4]])
+        );
+        assert_eq!(combinations(&[-1, -1, 2], 3, 0), Some(vec![vec![0, 1, 2]]));
+        assert_eq!(combinations(&[1, 2], 3, 3), None);
+        assert_eq!(combinations::<i32>(&[], 2, 0), None);
+        assert_eq!(combinations(&[4], 0, 0), Some(vec![vec![]]));
+        assert_eq!(combinations(&[4, 2, 4], 1, 4), Some(vec![vec![0], vec![2]]));
+        assert_eq!(combinations(&[9u8, 1, 3, 2], 2, 4), Some(vec![vec![1, 2]]));
+    }
+
+    #[test]
+    fn sums_beyond_the_type() {
+        let numbers = [200u8, 100, 50, 3, 2, 1];
+        assert_eq!(combinations(&numbers, 4, 56), Some(vec![vec![2, 3, 4, 5]]));
+        assert_eq!(combinations(&numbers, 5, 255), None);
+        assert_eq!(
+            combinations(&[200u8, 100, 55], 2, 255),
+            Some(vec![vec![0, 2]])
+        );
+
+        // going past the type on the way to a sum which fits in it
+        let numbers = [i64::MAX, 1, -1, 0, 0];
+        for k in 3..=5 {
+            let expected = brute_force(&numbers, k, i64::MAX);
+            assert!(expected.is_some(), "{k} numbers");
+            assert_eq!(combinations(&numbers, k, i64::MAX), expected, "{k} numbers");
+        }
+        assert_eq!(
+            combinations(&[i64::MAX, 1, -1], 3, i64::MAX),
+            Some(vec![vec![0, 1, 2]])
+        );
+        assert_eq!(
+            combinations(&[i64::MIN, -1, 1, 0], 4, i64::MIN),
+            Some(vec![vec![0, 1, 2, 3]])
+        );
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let numbers = [3, -1, 4, 1, -5, 9, 2, -6, 5, 3, 5, -8, 9, 7];
+        for k in 0..=7 {
+            for target in -12..=25 {
+                assert_eq!(
+                    combinations(&numbers, k, target),
+                    brute_force(&numbers, k, target),
+                    "{k} numbers adding up to {target}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+pub mod answers;
+#[cfg(feature = "criterion")]
+pub mod bench;
+pub mod bit_ops;
+pub mod checked;
+pub mod counter;
+pub mod cycle;
+pub mod fuzz;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+pub mod harness;
+pub mod inputs;
+pub mod interval;
+pub mod k_sum;
+pub mod memo;
+#[cfg(feature = "memory")]
+pub mod memory;
+pub mod number_theory;
+pub mod parse;
+pub mod record;
+pub mod search;
+pub mod trace;
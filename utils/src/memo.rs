@@ -0,0 +1,157 @@
+//! Memoising recursive functions, so each distinct call is only worked out once.
+//!
+//! The cache is an ordinary value passed down through the recursion, rather than hidden in a
+//! global, so each solve starts from nothing and can say how well the cache did:
+//!
+//! ```
+//! use aoc_utils::memo::Memo;
+//!
+//! fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+//!     if n < 2 {
+//!         return n;
+//!     }
+//!     memo.get(n, |memo, &n| fib(memo, n - 1) + fib(memo, n - 2))
+//! }
+//!
+//! let mut memo = Memo::new();
+//! assert_eq!(fib(&mut memo, 90), 2_880_067_194_370_816_120);
+//! assert_eq!(memo.stats().to_string(), "89 entries, 87 hits, 89 misses");
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+/// A cache of results by the key they were worked out from.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V, S = RandomState> {
+    cache: HashMap<K, V, S>,
+    hits: u64,
+    misses: u64,
+}
+
+/// How big a [`Memo`] got, and how often it was used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} entries, {} hits, {} misses",
+            self.entries, self.hits, self.misses
+        )
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K: Eq + Hash, V: Clone, S: BuildHasher> Memo<K, V, S> {
+    /// The result for `key`, which `compute` works out the first time it's asked for. It's given
+    /// the memo too, so that it can recurse.
+    pub fn get(&mut self, key: K, compute: impl FnOnce(&mut Self, &K) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = compute(self, &key);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// How many results are stored.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forget every result, and the statistics with them.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            entries: self.cache.len(),
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+}
+
+/// An empty memo, with any hasher which has a default.
+impl<K, V, S: Default> Default for Memo<K, V, S> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::default(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ways to climb `n` stairs taking one, two or three at a time
+    fn climb(memo: &mut Memo<u32, u64>, n: u32) -> u64 {
+        match n {
+            0 => 1,
+            _ => memo.get(n, |memo, &n| {
+                (1..=3.min(n)).map(|step| climb(memo, n - step)).sum()
+            }),
+        }
+    }
+
+    #[test]
+    fn caching() {
+        let mut memo = Memo::new();
+        assert_eq!(climb(&mut memo, 4), 7);
+        assert_eq!(climb(&mut memo, 30), 53_798_080);
+        assert_eq!(memo.len(), 30);
+
+        // a second call is answered straight from the cache
+        let before = memo.stats();
+        assert_eq!(climb(&mut memo, 30), 53_798_080);
+        assert_eq!(memo.stats().hits, before.hits + 1);
+        assert_eq!(memo.stats().misses, before.misses);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                entries: 0,
+                hits: 0,
+                misses: 0
+            }
+        );
+    }
+
+    #[test]
+    fn other_hashers() {
+        use std::hash::BuildHasherDefault;
+
+        let mut memo: Memo<u32, u64, BuildHasherDefault<std::hash::DefaultHasher>> =
+            Memo::default();
+        assert_eq!(memo.get(3, |_, &n| u64::from(n) * 2), 6);
+        assert_eq!(memo.get(3, |_, _| unreachable!()), 6);
+    }
+}
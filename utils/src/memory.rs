@@ -0,0 +1,113 @@
+//! A global allocator which counts, for measuring how much heap each phase of a day uses.
+//!
+//! Only compiled in with the `memory` feature, as every allocation then pays for a few atomic
+//! operations. Turning it on in any crate which links `aoc_utils` counts every allocation in that
+//! program, which [`harness`](crate::harness) then attributes to parsing and each part.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use crate::harness::Memory;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+struct Counting;
+
+fn grew(bytes: usize) {
+    let now = CURRENT.fetch_add(bytes, Relaxed) + bytes;
+    PEAK.fetch_max(now, Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Relaxed);
+    }
+
+    // a growing Vec or map reallocates rather than allocating again, which counts as another
+    // allocation all the same
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            match new_size.checked_sub(layout.size()) {
+                Some(more) => grew(more),
+                None => {
+                    CURRENT.fetch_sub(layout.size() - new_size, Relaxed);
+                }
+            }
+        }
+        new
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Run `f`, counting the most heap it had allocated at once on top of what was already live, and
+/// how many allocations it made.
+///
+/// The counts are global, so anything allocating on another thread meanwhile is counted too.
+pub fn count<T>(f: impl FnOnce() -> T) -> (T, Memory) {
+    let base = CURRENT.load(Relaxed);
+    PEAK.store(base, Relaxed);
+    let allocations = ALLOCATIONS.load(Relaxed);
+
+    let value = f();
+
+    let memory = Memory {
+        peak_bytes: PEAK.load(Relaxed).saturating_sub(base),
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+    };
+    (value, memory)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hint::black_box;
+
+    use super::*;
+
+    #[test]
+    fn counts_peak_and_allocations() {
+        let (sum, memory) = count(|| {
+            let big = black_box(vec![1u8; 100_000]);
+            let small: Vec<u64> = black_box((0..1000).collect());
+            big.len() + small.len()
+        });
+
+        assert_eq!(sum, 101_000);
+        // both vectors were live at once
+        assert!(memory.peak_bytes >= 108_000, "{memory:?}");
+        assert!(memory.allocations >= 2, "{memory:?}");
+    }
+
+    #[test]
+    fn freed_memory_is_not_peak() {
+        let kept = black_box(vec![0u8; 1_000_000]);
+        let (_, memory) = count(|| black_box(vec![0u8; 10]).len());
+
+        assert!(memory.peak_bytes < 1_000_000, "{memory:?}");
+        drop(kept);
+    }
+}
@@ -0,0 +1,224 @@
+//! Greatest common divisors, modular arithmetic and the Chinese remainder theorem.
+//!
+//! Puzzles which line up cycles of different lengths end in one of these. The modular functions
+//! work in `i128` and never form a product which could overflow it, so any `i64` inputs are safe.
+//!
+//! ```
+//! use aoc_utils::number_theory::crt;
+//!
+//! // x ≡ 2 (mod 4) and x ≡ 4 (mod 6) have a solution, even though 4 and 6 share a factor
+//! assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+//! assert_eq!(crt([(1, 4), (2, 6)]), None);
+//! ```
+
+use std::ops::Div;
+
+use crate::checked::Int;
+
+/// The integer types, for [`gcd`] and [`lcm`].
+pub trait Integer: Int + Eq + Div<Output = Self> {
+    fn checked_rem(self, other: Self) -> Option<Self>;
+    fn checked_abs(self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    (unsigned: $($t:ty),*; signed: $($s:ty),*) => {
+        $(impl Integer for $t {
+            fn checked_rem(self, other: Self) -> Option<Self> {
+                <$t>::checked_rem(self, other)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                Some(self)
+            }
+        })*
+        $(impl Integer for $s {
+            fn checked_rem(self, other: Self) -> Option<Self> {
+                <$s>::checked_rem(self, other)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                <$s>::checked_abs(self)
+            }
+        })*
+    };
+}
+
+integer!(unsigned: u8, u16, u32, u64, u128, usize; signed: i8, i16, i32, i64, i128, isize);
+
+/// The greatest common divisor of `a` and `b`, which is never negative. `gcd(0, 0)` is 0.
+///
+/// `None` if that doesn't fit in `T`, which only happens with a signed type's `MIN` and either 0
+/// or `MIN` itself: `gcd(i32::MIN, 0)` is `2^31`.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> Option<T> {
+    while b != T::ZERO {
+        // only `MIN % -1` overflows, and it's 0
+        (a, b) = (b, a.checked_rem(b).unwrap_or(T::ZERO));
+    }
+    a.checked_abs()
+}
+
+/// The least common multiple of `a` and `b`, which is never negative. It's 0 if either is.
+///
+/// `None` if that doesn't fit in `T`, which for a signed type includes `lcm(MIN, 1)`.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    // divide first, so that only a result too big for `T` can overflow
+    (a / gcd(a, b)?).checked_mul(b)?.checked_abs()
+}
+
+/// `(g, x, y)` where `g` is [`gcd`]`(a, b)` and `a * x + b * y == g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let mut old = (a, 1, 0);
+    let mut now = (b, 0, 1);
+
+    while now.0 != 0 {
+        let q = old.0 / now.0;
+        let new = (old.0 - q * now.0, old.1 - q * now.1, old.2 - q * now.2);
+        old = now;
+        now = new;
+    }
+
+    if old.0 < 0 {
+        (-old.0, -old.1, -old.2)
+    } else {
+        old
+    }
+}
+
+/// `a * b % m`, for `a` and `b` already reduced mod `m`, without overflowing.
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    // double and add, where none of the sums can get past m
+    let add = |x: i128, y: i128| if x >= m - y { x - (m - y) } else { x + y };
+    let (mut a, mut b, mut total) = (a, b, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            total = add(total, a);
+        }
+        a = add(a, a);
+        b >>= 1;
+    }
+    total
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    assert!(m > 0, "modulus {m} isn't positive");
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base` to the power `exp`, mod `m`.
+pub fn mod_pow(base: i128, mut exp: u128, m: i128) -> i128 {
+    assert!(m > 0, "modulus {m} isn't positive");
+    let mut base = base.rem_euclid(m);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Solve `x ≡ residue (mod modulus)` for every `(residue, modulus)` together, giving the smallest
+/// non-negative `x` and the modulus it repeats with, which is the [`lcm`] of all of them.
+///
+/// The moduli don't need to be coprime. `None` means there's no such `x`. Panics if a modulus
+/// isn't positive, or if their `lcm` doesn't fit in an `i128`.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    let mut solution = (0, 1);
+
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus {modulus} isn't positive");
+        let (x, m) = solution;
+        let residue = residue.rem_euclid(modulus);
+
+        // x + m * k ≡ residue (mod modulus) has a k only if g divides the difference
+        let g = gcd(m, modulus).expect("the gcd of positive moduli fits");
+        let difference = residue - x;
+        if difference % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let combined = m
+            .checked_mul(step)
+            .unwrap_or_else(|| panic!("lcm of the moduli overflowed, at {modulus}"));
+        let inverse = mod_inverse(m / g, step).expect("m / g and modulus / g are coprime");
+        let k = mul_mod((difference / g).rem_euclid(step), inverse, step);
+        solution = (x + m * k, combined);
+    }
+
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn divisors() {
+        assert_eq!(gcd(12u32, 18), Some(6));
+        assert_eq!(gcd(-12i64, 18), Some(6));
+        assert_eq!(gcd(0u8, 5), Some(5));
+        assert_eq!(gcd(0u8, 0), Some(0));
+        assert_eq!(lcm(4u64, 6), Some(12));
+        assert_eq!(lcm(-4i32, 6), Some(12));
+        assert_eq!(lcm(0i32, i32::MIN), Some(0));
+        // the product alone would overflow
+        assert_eq!(lcm(u64::MAX / 3, 3), Some(u64::MAX));
+        assert_eq!(lcm(u64::MAX / 2, 3), None);
+
+        // 2^31 doesn't fit in an i32
+        assert_eq!(gcd(i32::MIN, 0), None);
+        assert_eq!(gcd(i32::MIN, i32::MIN), None);
+        assert_eq!(gcd(i32::MIN, -1), Some(1));
+        assert_eq!(gcd(-1, i32::MIN), Some(1));
+        assert_eq!(gcd(i64::MIN, 6), Some(2));
+        assert_eq!(lcm(i32::MIN, 1), None);
+        assert_eq!(lcm(i32::MIN / 2, 2), Some(1 << 30));
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 6), None);
+
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+        // 2^127 - 1 is prime, so Fermat's little theorem holds for it
+        let p = i128::MAX;
+        assert_eq!(mod_pow(3, p as u128 - 1, p), 1);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(0, 17), (-2, 13), (-3, 19)]), Some((3417, 4199)));
+        assert_eq!(crt([(3, 4), (1, 6), (0, 1)]), Some((7, 12)));
+        assert_eq!(crt([(3, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+
+        // moduli whose product overflows an i64
+        let big = [(1, 1_000_000_007), (2, 998_244_353), (3, 1_000_000_009)];
+        let (x, m) = crt(big).unwrap();
+        assert_eq!(m, 1_000_000_007 * 998_244_353 * 1_000_000_009);
+        assert!(big.iter().all(|&(r, n)| x % n == r));
+    }
+}
@@ -0,0 +1,262 @@
+//! Parse errors which say where in the input they happened.
+//!
+//! Parsers work by slicing up their input, so [`Source`] can tell the line and column of any slice
+//! from where it sits in memory, without the parsers having to keep count:
+//!
+//! ```
+//! use aoc_utils::parse::Source;
+//!
+//! let input = "1,2\n3;4\n";
+//! let src = Source::new(input);
+//! let line = input.lines().nth(1).unwrap();
+//!
+//! let error = src.split_once(line, ",").unwrap_err();
+//! assert_eq!(error.to_string(), r#"line 2, column 1: expected ",", found "3;4""#);
+//! ```
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// How much of the offending text an error quotes.
+const FOUND_CHARS: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Counting from 1.
+    pub line: usize,
+    /// In characters, counting from 1.
+    pub column: usize,
+    pub expected: String,
+    /// The start of the text which didn't match, or empty if there was none.
+    pub found: String,
+}
+
+impl ParseError {
+    /// Move an error from parsing `fragment` on its own to where `fragment` is in `src`.
+    pub fn within(self, src: &Source, fragment: &str) -> Self {
+        let (line, column) = src.position(fragment);
+        Self {
+            line: line + self.line - 1,
+            column: if self.line == 1 {
+                column + self.column - 1
+            } else {
+                self.column
+            },
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "nothing")
+        } else {
+            write!(f, "{:?}", self.found)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The whole input of a parser, for locating errors in slices of it.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input }
+    }
+
+    /// An empty slice at the end of the input, for when something is missing altogether.
+    pub fn end(&self) -> &'a str {
+        &self.input[self.input.len()..]
+    }
+
+    /// The byte offset of `fragment` in the input. Anything which isn't a slice of the input is
+    /// taken to be at its end, as that's usually where something was missing.
+    fn offset(&self, fragment: &str) -> usize {
+        let start = self.input.as_ptr() as usize;
+        let at = fragment.as_ptr() as usize;
+        match at.checked_sub(start) {
+            Some(offset) if offset + fragment.len() <= self.input.len() => offset,
+            _ => self.input.len(),
+        }
+    }
+
+    /// The line and column where `fragment` starts.
+    fn position(&self, fragment: &str) -> (usize, usize) {
+        let before = &self.input[..self.offset(fragment)];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+
+    /// An error at the start of `fragment`, which should have been `expected`.
+    pub fn error(&self, fragment: &str, expected: impl Into<String>) -> ParseError {
+        let (line, column) = self.position(fragment);
+        let found = fragment.lines().next().unwrap_or_default();
+        let found = match found.char_indices().nth(FOUND_CHARS) {
+            Some((cut, _)) => format!("{}...", &found[..cut]),
+            None => found.to_string(),
+        };
+
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+            found,
+        }
+    }
+
+    /// An error `index` bytes into `fragment`, for parsers which go byte by byte.
+    pub fn error_at(
+        &self,
+        fragment: &str,
+        index: usize,
+        expected: impl Into<String>,
+    ) -> ParseError {
+        let mut index = index.min(fragment.len());
+        while !fragment.is_char_boundary(index) {
+            index -= 1;
+        }
+        self.error(&fragment[index..], expected)
+    }
+
+    /// Parse the whole of `fragment` as a number.
+    pub fn number<T: FromStr>(&self, fragment: &str) -> Result<T, ParseError> {
+        fragment
+            .parse()
+            .map_err(|_| self.error(fragment, "a number"))
+    }
+
+    /// Parse `fragment` with a parser which locates its own errors within `fragment`.
+    pub fn parse<T: FromStr<Err = ParseError>>(&self, fragment: &str) -> Result<T, ParseError> {
+        fragment
+            .parse()
+            .map_err(|e: ParseError| e.within(self, fragment))
+    }
+
+    pub fn split_once<'f>(
+        &self,
+        fragment: &'f str,
+        separator: &str,
+    ) -> Result<(&'f str, &'f str), ParseError> {
+        fragment
+            .split_once(separator)
+            .ok_or_else(|| self.error(fragment, format!("{separator:?}")))
+    }
+
+    pub fn strip_prefix<'f>(&self, fragment: &'f str, prefix: &str) -> Result<&'f str, ParseError> {
+        fragment
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(fragment, format!("{prefix:?}")))
+    }
+
+    pub fn strip_suffix<'f>(&self, fragment: &'f str, suffix: &str) -> Result<&'f str, ParseError> {
+        fragment
+            .strip_suffix(suffix)
+            .ok_or_else(|| self.error(fragment, format!("something ending in {suffix:?}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions() {
+        let input = "ab\ncdé f\n\ng";
+        let src = Source::new(input);
+
+        assert_eq!(src.position(&input[0..]), (1, 1));
+        assert_eq!(src.position(&input[1..]), (1, 2));
+        assert_eq!(src.position(&input[3..]), (2, 1));
+        // columns count characters, not bytes
+        assert_eq!(src.position(input.split(' ').nth(1).unwrap()), (2, 5));
+        assert_eq!(src.position(&input[input.len() - 1..]), (4, 1));
+        assert_eq!(src.position(src.end()), (4, 2));
+        assert_eq!(src.position("elsewhere"), (4, 2));
+    }
+
+    #[test]
+    fn errors() {
+        let input = "x: 12\ny: 1z\n";
+        let src = Source::new(input);
+        let line = input.lines().nth(1).unwrap();
+        let (_, value) = src.split_once(line, ": ").unwrap();
+
+        let error = src.number::<u32>(value).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                line: 2,
+                column: 4,
+                expected: "a number".to_string(),
+                found: "1z".to_string(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: expected a number, found \"1z\""
+        );
+
+        let error = src.strip_prefix(line, "x").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected \"x\", found \"y: 1z\""
+        );
+
+        let error = src.error(src.end(), "another line");
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected another line, found nothing"
+        );
+    }
+
+    #[test]
+    fn long_and_multibyte_found() {
+        let input = "ééééééééééééééééééééééé\n";
+        let src = Source::new(input);
+
+        let error = src.error_at(input, 3, "ascii");
+        assert_eq!(error.column, 2);
+        assert_eq!(error.found.chars().count(), FOUND_CHARS + 3);
+        assert!(error.found.ends_with("..."));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Pair(u8, u8);
+
+    impl FromStr for Pair {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let src = Source::new(s);
+            let (a, b) = src.split_once(s, ",")?;
+            Ok(Pair(src.number(a)?, src.number(b)?))
+        }
+    }
+
+    #[test]
+    fn nested_errors() {
+        let input = "pairs: 1,2 3,x\n";
+        let src = Source::new(input);
+        assert_eq!(src.parse::<Pair>("1,2").unwrap(), Pair(1, 2));
+        let pairs = src.strip_prefix(input.trim_end(), "pairs: ").unwrap();
+
+        let parsed: Result<Vec<Pair>, _> = pairs.split(' ').map(|pair| src.parse(pair)).collect();
+        let error = parsed.unwrap_err();
+        assert_eq!((error.line, error.column), (1, 14));
+        assert_eq!(error.found, "x");
+    }
+}
@@ -0,0 +1,518 @@
+//! Recording a grid as it changes, as an animated GIF or a numbered sequence of PPM images.
+//!
+//! Days which step a grid along, like seats filling up or rocks rolling about, add each step to a
+//! [`Recorder`] as a frame, coloured by a [`Palette`] of what each cell is:
+//!
+//! ```
+//! use aoc_utils::grid::Grid;
+//! use aoc_utils::record::{Palette, Recorder};
+//!
+//! let palette = Palette::new([0, 0, 0]).with(b'#', [255, 255, 255]);
+//! let mut recorder = Recorder::new(palette).scale(2);
+//! recorder.frame(&Grid::parse(".#\n#.\n", "", |c| Some(c as u8)).unwrap());
+//! recorder.frame(&Grid::parse("#.\n.#\n", "", |c| Some(c as u8)).unwrap());
+//!
+//! let mut gif = vec![];
+//! recorder.write_gif(&mut gif).unwrap();
+//! assert!(gif.starts_with(b"GIF89a"));
+//! ```
+//!
+//! Days only get a recorder from [`Recorder::requested`] when run with `--record <dir>`, so they
+//! do next to nothing extra otherwise. Each recording is saved into `dir` under its own name, as
+//! `<name>.gif`, or as `<name>/0000.ppm` and so on with `--record-as ppm`.
+//!
+//! Both formats are written here rather than pulling in an image library: PPM is just a header
+//! and the raw pixels, and the GIF needs little more than its LZW compression.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::grid::{Grid, Pos};
+
+pub type Rgb = [u8; 3];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// One animated GIF, looping forever.
+    #[default]
+    Gif,
+    /// A directory of numbered PPM images, one per frame.
+    Ppm,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gif" => Ok(Format::Gif),
+            "ppm" => Ok(Format::Ppm),
+            _ => Err(anyhow!(
+                "Unknown recording format {s:?}, expected gif or ppm"
+            )),
+        }
+    }
+}
+
+static DESTINATION: OnceLock<(PathBuf, Format)> = OnceLock::new();
+
+/// Hand out recorders from [`Recorder::requested`] which save into `dir`. Only the first call
+/// has any effect.
+pub fn record_into(dir: PathBuf, format: Format) {
+    let _ = DESTINATION.set((dir, format));
+}
+
+/// The colour of each kind of cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette<T> {
+    colours: Vec<(T, Rgb)>,
+    /// For any cell without its own colour, and for padding.
+    fallback: Rgb,
+}
+
+impl<T: PartialEq> Palette<T> {
+    pub fn new(fallback: Rgb) -> Self {
+        Self {
+            colours: vec![],
+            fallback,
+        }
+    }
+
+    /// Colour `cell` in `colour`. A GIF has at most 256 colours, so there can be at most 255 of
+    /// these besides the fallback.
+    pub fn with(mut self, cell: T, colour: Rgb) -> Self {
+        assert!(self.colours.len() < 255, "too many colours for a palette");
+        self.colours.push((cell, colour));
+        self
+    }
+
+    /// Where `cell`'s colour is in [`Palette::table`].
+    fn index(&self, cell: &T) -> u8 {
+        self.colours
+            .iter()
+            .position(|(c, _)| c == cell)
+            .map_or(0, |i| i as u8 + 1)
+    }
+}
+
+impl<T> Palette<T> {
+    /// Every colour, with the fallback first.
+    fn table(&self) -> impl Iterator<Item = Rgb> + '_ {
+        std::iter::once(self.fallback).chain(self.colours.iter().map(|&(_, colour)| colour))
+    }
+}
+
+/// Frames of a grid, to be saved as an animation.
+#[derive(Debug, Clone)]
+pub struct Recorder<T> {
+    palette: Palette<T>,
+    /// How many pixels wide and high each cell is.
+    scale: usize,
+    /// How long each frame shows for in a GIF, in hundredths of a second.
+    delay: u16,
+    /// Each cell's index in the palette.
+    frames: Vec<Grid<u8>>,
+    save_to: Option<(PathBuf, Format)>,
+}
+
+impl<T: PartialEq> Recorder<T> {
+    pub fn new(palette: Palette<T>) -> Self {
+        Self {
+            palette,
+            scale: 4,
+            delay: 10,
+            frames: vec![],
+            save_to: None,
+        }
+    }
+
+    /// A recorder to save as `name`, if the day was run with `--record`.
+    pub fn requested(name: &str, palette: Palette<T>) -> Option<Self> {
+        let (dir, format) = DESTINATION.get()?;
+        Some(Self {
+            save_to: Some((dir.join(name), *format)),
+            ..Self::new(palette)
+        })
+    }
+
+    pub fn scale(mut self, scale: usize) -> Self {
+        assert!(scale > 0, "cells must be at least a pixel across");
+        self.scale = scale;
+        self
+    }
+
+    /// Show each frame of a GIF for `delay` hundredths of a second.
+    pub fn delay(mut self, delay: u16) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn frame(&mut self, grid: &Grid<T>) {
+        let cells = grid.cells().iter().map(|cell| self.palette.index(cell));
+        self.frames
+            .push(Grid::from_vec(cells.collect(), grid.width()));
+    }
+
+    /// Add a frame of `width` by `height` cells, where `cell` gives what's at each position. This
+    /// is for drawing things which aren't kept in a [`Grid`], or only a part of one.
+    pub fn frame_with(&mut self, width: usize, height: usize, mut cell: impl FnMut(Pos) -> T) {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|pos| self.palette.index(&cell(pos)))
+            .collect();
+        self.frames.push(Grid::from_vec(cells, width));
+    }
+}
+
+impl<T> Recorder<T> {
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The size in pixels of the whole animation, which fits the biggest frame. Smaller frames
+    /// are padded at the bottom and right with the fallback colour.
+    fn size(&self) -> (usize, usize) {
+        let width = self.frames.iter().map(Grid::width).max().unwrap_or(0);
+        let height = self.frames.iter().map(Grid::height).max().unwrap_or(0);
+        (width * self.scale, height * self.scale)
+    }
+
+    /// The palette index of every pixel of `frame`, row by row.
+    fn pixels(&self, frame: &Grid<u8>) -> Vec<u8> {
+        let (width, height) = self.size();
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            pixels.extend((0..width).map(|x| {
+                let pos = (y / self.scale, x / self.scale);
+                frame.get(pos).copied().unwrap_or(0)
+            }));
+        }
+        pixels
+    }
+
+    pub fn write_gif(&self, out: &mut impl Write) -> io::Result<()> {
+        let (width, height) = self.size();
+        let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{width}x{height} is too big for a GIF"),
+            ));
+        };
+
+        // the colour table has 2^(bits) entries, and LZW codes start from at least 2 bits
+        let colours: Vec<Rgb> = self.palette.table().collect();
+        let bits = colours.len().next_power_of_two().trailing_zeros().max(1);
+        let min_code_size = bits.max(2) as u8;
+
+        out.write_all(b"GIF89a")?;
+        out.write_all(&width.to_le_bytes())?;
+        out.write_all(&height.to_le_bytes())?;
+        // a global colour table, of 8 bit colours, with no background or aspect ratio
+        out.write_all(&[0xf0 | (bits as u8 - 1), 0, 0])?;
+        for i in 0..1 << bits {
+            out.write_all(&colours.get(i).copied().unwrap_or_default())?;
+        }
+
+        // loop forever
+        out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+        for frame in &self.frames {
+            let [delay_lo, delay_hi] = self.delay.to_le_bytes();
+            out.write_all(&[0x21, 0xf9, 4, 0, delay_lo, delay_hi, 0, 0])?;
+
+            out.write_all(&[0x2c, 0, 0, 0, 0])?;
+            out.write_all(&width.to_le_bytes())?;
+            out.write_all(&height.to_le_bytes())?;
+            out.write_all(&[0, min_code_size])?;
+
+            let data = lzw(&self.pixels(frame), min_code_size);
+            for block in data.chunks(255) {
+                out.write_all(&[block.len() as u8])?;
+                out.write_all(block)?;
+            }
+            out.write_all(&[0])?;
+        }
+
+        out.write_all(&[0x3b])
+    }
+
+    /// Write frame number `n` as a binary PPM image.
+    pub fn write_ppm(&self, n: usize, out: &mut impl Write) -> io::Result<()> {
+        let (width, height) = self.size();
+        let colours: Vec<Rgb> = self.palette.table().collect();
+
+        write!(out, "P6\n{width} {height}\n255\n")?;
+        for index in self.pixels(&self.frames[n]) {
+            out.write_all(&colours[usize::from(index)])?;
+        }
+        Ok(())
+    }
+
+    /// Save the recording at `path`, as a GIF file or a directory of PPM images.
+    pub fn save(&self, path: &Path, format: Format) -> Result<()> {
+        match format {
+            Format::Gif => {
+                let path = path.with_extension("gif");
+                let mut file = BufWriter::new(
+                    fs::File::create(&path)
+                        .with_context(|| format!("Couldn't create {}", path.display()))?,
+                );
+                self.write_gif(&mut file)?;
+                file.flush()?;
+            }
+            Format::Ppm => {
+                fs::create_dir_all(path)
+                    .with_context(|| format!("Couldn't create {}", path.display()))?;
+                for n in 0..self.frames.len() {
+                    let mut file =
+                        BufWriter::new(fs::File::create(path.join(format!("{n:04}.ppm")))?);
+                    self.write_ppm(n, &mut file)?;
+                    file.flush()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Save a recording from [`Recorder::requested`] where `--record` asked for it. Parts have
+    /// nowhere to return an error to, so a failure is only reported.
+    pub fn finish(self) {
+        let Some((path, format)) = &self.save_to else {
+            return;
+        };
+        if let Err(e) = self.save(path, *format) {
+            eprintln!("Couldn't save the recording: {e:#}");
+        }
+    }
+}
+
+/// Compress palette indices as GIF image data, before it's cut into blocks.
+fn lzw(pixels: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODES: u16 = 1 << 12;
+
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut out = Bits::default();
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut size = min_code_size + 1;
+    let mut next = end + 1;
+
+    out.push(clear, size);
+    let Some((&first, rest)) = pixels.split_first() else {
+        out.push(end, size);
+        return out.finish();
+    };
+
+    let mut prefix = u16::from(first);
+    for &pixel in rest {
+        if let Some(&code) = codes.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+
+        out.push(prefix, size);
+        if next < MAX_CODES {
+            codes.insert((prefix, pixel), next);
+            next += 1;
+            // the decoder adds each code a step behind, so only needs the extra bit after this
+            if next > 1 << size && size < 12 {
+                size += 1;
+            }
+        } else {
+            out.push(clear, size);
+            codes.clear();
+            next = end + 1;
+            size = min_code_size + 1;
+        }
+        prefix = u16::from(pixel);
+    }
+
+    out.push(prefix, size);
+    out.push(end, size);
+    out.finish()
+}
+
+/// Codes packed into bytes from the least significant bit up.
+#[derive(Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    pending: u32,
+    count: u8,
+}
+
+impl Bits {
+    fn push(&mut self, code: u16, size: u8) {
+        self.pending |= u32::from(code) << self.count;
+        self.count += size;
+        while self.count >= 8 {
+            self.bytes.push(self.pending as u8);
+            self.pending >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.pending as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Undo [`lzw`], the way a GIF decoder does.
+    fn unlzw(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let initial: Vec<Vec<u8>> = (0..clear)
+            .map(|i| vec![i as u8])
+            .chain([vec![], vec![]])
+            .collect();
+
+        let mut table = initial.clone();
+        let mut size = min_code_size + 1;
+        let mut previous: Option<usize> = None;
+        let mut out = vec![];
+        let (mut pending, mut count, mut bytes) = (0u32, 0u8, data.iter());
+
+        loop {
+            while count < size {
+                pending |= u32::from(*bytes.next().expect("ran out before the end code")) << count;
+                count += 8;
+            }
+            let code = (pending & ((1 << size) - 1)) as usize;
+            pending >>= size;
+            count -= size;
+
+            if code == clear {
+                table = initial.clone();
+                size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+
+            let entry = match (table.get(code), previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => {
+                    let mut entry = table[prev].clone();
+                    entry.push(table[prev][0]);
+                    entry
+                }
+                (None, None) => panic!("code {code} before it was defined"),
+            };
+            if let Some(prev) = previous {
+                let mut added = table[prev].clone();
+                added.push(entry[0]);
+                table.push(added);
+                if table.len() == 1 << size && size < 12 {
+                    size += 1;
+                }
+            }
+            out.extend_from_slice(&entry);
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trips() {
+        let mut state = 12345u32;
+        let noisy: Vec<u8> = (0..20_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (state >> 16) as u8 % 5
+            })
+            .collect();
+        let runs: Vec<u8> = (0..50_000).map(|i| (i / 37 % 3) as u8).collect();
+
+        for (pixels, min_code_size) in [
+            (vec![], 2),
+            (vec![1], 2),
+            (vec![0, 1, 0, 1, 0, 1, 0, 1], 2),
+            (noisy, 3),
+            (runs, 2),
+            ((0..=255).cycle().take(10_000).collect(), 8),
+        ] {
+            let data = lzw(&pixels, min_code_size);
+            assert_eq!(unlzw(&data, min_code_size), pixels);
+        }
+    }
+
+    fn checkerboard() -> Recorder<bool> {
+        let palette = Palette::new([0, 0, 0]).with(true, [255, 0, 0]);
+        let mut recorder = Recorder::new(palette).scale(2).delay(5);
+        recorder.frame_with(2, 1, |(_, col)| col == 0);
+        recorder.frame(&Grid::from_vec(vec![false, true, true, false], 2));
+        recorder
+    }
+
+    #[test]
+    fn ppm() {
+        let recorder = checkerboard();
+        let mut ppm = vec![];
+        recorder.write_ppm(0, &mut ppm).unwrap();
+
+        let (red, black) = ([255, 0, 0], [0, 0, 0]);
+        let row = [red, red, black, black].concat();
+        // the first frame is only one cell high, so padded out to the second's height
+        let expected = [
+            b"P6\n4 4\n255\n".to_vec(),
+            row.clone(),
+            row,
+            [black; 8].concat(),
+        ]
+        .concat();
+        assert_eq!(ppm, expected);
+    }
+
+    #[test]
+    fn gif() {
+        let recorder = checkerboard();
+        let mut gif = vec![];
+        recorder.write_gif(&mut gif).unwrap();
+
+        // header, 4x4 screen, and a two colour table
+        assert_eq!(&gif[..13], b"GIF89a\x04\x00\x04\x00\xf0\x00\x00");
+        assert_eq!(&gif[13..19], [0, 0, 0, 255, 0, 0]);
+        assert_eq!(gif.last(), Some(&0x3b));
+
+        // each frame's pixels come back out of its image data
+        let mut rest = &gif[19 + 19..];
+        for frame in &recorder.frames {
+            assert_eq!(&rest[..8], [0x21, 0xf9, 4, 0, 5, 0, 0, 0]);
+            assert_eq!(&rest[8..18], [0x2c, 0, 0, 0, 0, 4, 0, 4, 0, 0]);
+            let min_code_size = rest[18];
+            let mut data = vec![];
+            rest = &rest[19..];
+            while rest[0] != 0 {
+                let len = usize::from(rest[0]);
+                data.extend_from_slice(&rest[1..=len]);
+                rest = &rest[len + 1..];
+            }
+            rest = &rest[1..];
+            assert_eq!(unlzw(&data, min_code_size), recorder.pixels(frame));
+        }
+        assert_eq!(rest, [0x3b]);
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("ppm".parse::<Format>().unwrap(), Format::Ppm);
+        assert!("png".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,271 @@
+//! Shortest paths through any graph of states, by Dijkstra's algorithm or A*.
+//!
+//! A state is whatever a puzzle needs to know where it is, and `successors` gives the states one
+//! move away from it along with what that move costs:
+//!
+//! ```
+//! use aoc_utils::search;
+//!
+//! // the cheapest way from 1 to 10 by adding one (cost 1) or doubling (cost 2)
+//! let found = search::dijkstra(
+//!     [1u32],
+//!     |&n| [(n + 1, 1), (n * 2, 2)].into_iter().filter(|&(n, _)| n <= 10),
+//!     |&n| n == 10,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(found.cost, 6);
+//! assert_eq!(found.path(), [1, 2, 4, 5, 10]);
+//! // doubling 2 costs the same as adding one to it twice
+//! assert_eq!(found.all_paths(), [vec![1, 2, 4, 5, 10], vec![1, 2, 3, 4, 5, 10]]);
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a move can cost. `Default` is taken to be zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+#[derive(Debug)]
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    /// Every state which reaches this one at `cost`, as indices into the nodes.
+    parents: Vec<usize>,
+    expanded: bool,
+}
+
+/// The result of a search which reached a goal: its cost, and every way of getting there for it.
+#[derive(Debug)]
+pub struct ShortestPaths<S, C> {
+    pub cost: C,
+    nodes: Vec<Node<S, C>>,
+    /// Every goal reached at `cost`.
+    goals: Vec<usize>,
+}
+
+impl<S: Clone, C> ShortestPaths<S, C> {
+    /// The goal state reached, or the first of them if more than one was reached at the same cost.
+    pub fn goal(&self) -> &S {
+        &self.nodes[self.goals[0]].state
+    }
+
+    /// One cheapest route, from a start to [`ShortestPaths::goal`] inclusive.
+    pub fn path(&self) -> Vec<S> {
+        let mut path = vec![self.goals[0]];
+        while let Some(&parent) = self.nodes[*path.last().unwrap()].parents.first() {
+            path.push(parent);
+        }
+        path.iter()
+            .rev()
+            .map(|&i| self.nodes[i].state.clone())
+            .collect()
+    }
+
+    /// Every cheapest route, to every goal reached at the same cost.
+    pub fn all_paths(&self) -> Vec<Vec<S>> {
+        let mut paths = vec![];
+        // routes followed back from a goal, to be extended by each of their first state's parents
+        let mut partial: Vec<Vec<usize>> = self.goals.iter().map(|&goal| vec![goal]).collect();
+        while let Some(route) = partial.pop() {
+            let parents = &self.nodes[*route.last().unwrap()].parents;
+            if parents.is_empty() {
+                paths.push(
+                    route
+                        .iter()
+                        .rev()
+                        .map(|&i| self.nodes[i].state.clone())
+                        .collect(),
+                );
+            }
+            for &parent in parents.iter().rev() {
+                let mut route = route.clone();
+                route.push(parent);
+                partial.push(route);
+            }
+        }
+        paths
+    }
+}
+
+/// The cheapest way from any of `starts` to a state which `is_goal`, if there is one.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// [`dijkstra`], but trying first the states which `heuristic` guesses are closest to a goal.
+///
+/// The heuristic must never guess more than the real cost to a goal, and must not drop by more
+/// than the cost of a move when that move is taken, or the paths found might not be the cheapest.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut nodes: Vec<Node<S, C>> = vec![];
+    let mut indices: HashMap<S, usize> = HashMap::new();
+    // (estimated total cost, cost so far, node)
+    let mut queue = BinaryHeap::new();
+
+    for state in starts {
+        if let Entry::Vacant(entry) = indices.entry(state.clone()) {
+            entry.insert(nodes.len());
+            queue.push(Reverse((heuristic(&state), C::default(), nodes.len())));
+            nodes.push(Node {
+                state,
+                cost: C::default(),
+                parents: vec![],
+                expanded: false,
+            });
+        }
+    }
+
+    let mut goals: Vec<usize> = vec![];
+    while let Some(Reverse((estimate, cost, current))) = queue.pop() {
+        // carry on past the first goal only for as long as there might be ties with it
+        if let Some(&goal) = goals.first() {
+            if estimate > nodes[goal].cost {
+                break;
+            }
+        }
+
+        let node = &mut nodes[current];
+        if node.expanded || cost > node.cost {
+            continue;
+        }
+        node.expanded = true;
+
+        if is_goal(&node.state) {
+            goals.push(current);
+            continue;
+        }
+
+        let state = node.state.clone();
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            let index = match indices.entry(next) {
+                Entry::Occupied(entry) => {
+                    let node = &mut nodes[*entry.get()];
+                    if next_cost > node.cost || node.expanded {
+                        continue;
+                    }
+                    if next_cost == node.cost {
+                        node.parents.push(current);
+                        continue;
+                    }
+                    node.cost = next_cost;
+                    node.parents = vec![current];
+                    *entry.get()
+                }
+                Entry::Vacant(entry) => {
+                    let index = nodes.len();
+                    nodes.push(Node {
+                        state: entry.key().clone(),
+                        cost: next_cost,
+                        parents: vec![current],
+                        expanded: false,
+                    });
+                    entry.insert(index);
+                    index
+                }
+            };
+
+            let estimate = next_cost + heuristic(&nodes[index].state);
+            queue.push(Reverse((estimate, next_cost, index)));
+        }
+    }
+
+    let cost = nodes[*goals.first()?].cost;
+    Some(ShortestPaths { cost, nodes, goals })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Pos};
+
+    fn maze() -> Grid<bool> {
+        Grid::parse("....#\n.##.#\n.....\n#.##.\n", "a wall", |c| Some(c == '.')).unwrap()
+    }
+
+    fn moves(maze: &Grid<bool>) -> impl FnMut(&Pos) -> Vec<(Pos, u32)> + '_ {
+        |&pos| {
+            maze.neighbours4(pos)
+                .filter(|&n| maze[n])
+                .map(|n| (n, 1))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn ties() {
+        let maze = maze();
+        let found = dijkstra([(0, 0)], moves(&maze), |&pos| pos == (2, 3)).unwrap();
+        assert_eq!(found.cost, 5);
+        assert_eq!(found.goal(), &(2, 3));
+        assert_eq!(found.path().len(), 6);
+
+        let mut paths = found.all_paths();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                vec![(0, 0), (0, 1), (0, 2), (0, 3), (1, 3), (2, 3)],
+                vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (2, 3)],
+            ]
+        );
+    }
+
+    #[test]
+    fn heuristic_finds_the_same_cost() {
+        let maze = maze();
+        let goal: Pos = (3, 4);
+        let manhattan = |&(row, col): &Pos| (goal.0.abs_diff(row) + goal.1.abs_diff(col)) as u32;
+
+        let found = astar([(0, 0)], moves(&maze), manhattan, |&pos| pos == goal).unwrap();
+        assert_eq!(found.cost, 7);
+        assert_eq!(found.all_paths().len(), 2);
+        assert_eq!(found.path().first(), Some(&(0, 0)));
+        assert_eq!(found.path().last(), Some(&goal));
+    }
+
+    #[test]
+    fn several_starts_and_goals() {
+        let maze = maze();
+        let found = dijkstra([(0, 0), (0, 3)], moves(&maze), |&(row, _)| row == 3).unwrap();
+        assert_eq!(found.cost, 4);
+        let mut ends: Vec<_> = found
+            .all_paths()
+            .iter()
+            .map(|p| (p[0], p[p.len() - 1]))
+            .collect();
+        ends.sort();
+        assert_eq!(ends, [((0, 0), (3, 1)), ((0, 3), (3, 4))]);
+    }
+
+    #[test]
+    fn unreachable() {
+        let maze = maze();
+        assert!(dijkstra([(0, 0)], moves(&maze), |&pos| pos == (0, 4)).is_none());
+    }
+}
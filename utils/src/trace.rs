@@ -0,0 +1,282 @@
+//! Named spans and events from inside the days, for seeing what a solver did when its answer is
+//! wrong, without sprinkling it with `println!`s.
+//!
+//! A day wraps the interesting stretches of its work in spans, and reports what happens in them
+//! with [`trace!`](crate::trace!), naming the event and giving any values worth seeing:
+//!
+//! ```
+//! use aoc_utils::trace;
+//!
+//! let _span = trace::span("evolve");
+//! for round in 1..=3 {
+//!     trace!("round", round, changed = 10 - round);
+//! }
+//! ```
+//!
+//! Without the `trace` feature all of this compiles away. With it, `--trace <names>` prints the
+//! events inside any span, or with any name, in the comma separated `names` (or `all` of them) to
+//! stderr, and `--trace-to <path>` writes them into a file instead. The harness puts each phase in
+//! a span of its own, so `--trace part2` is everything part 2 did:
+//!
+//! ```text
+//! part2/evolve round round=1 changed=9
+//! part2/evolve round round=2 changed=8
+//!   seats:
+//!     #.##.L#.##
+//!     ...
+//! ```
+//!
+//! A value which takes more than a line, such as a grid, goes below the event's line. `aoc trace`
+//! builds a day with the feature and runs it like this.
+
+use std::cell::RefCell;
+use std::fmt::{self, Display, Write as _};
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+
+/// Whether the days were built to trace at all.
+pub const ENABLED: bool = cfg!(feature = "trace");
+
+/// Which spans and events to keep.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    /// Everything, if there are none.
+    names: Vec<String>,
+}
+
+impl Filter {
+    /// Whether to keep an event named `event`, inside `spans`.
+    pub fn matches(&self, spans: &[&str], event: &str) -> bool {
+        self.names.is_empty()
+            || self
+                .names
+                .iter()
+                .any(|name| name == event || spans.contains(&name.as_str()))
+    }
+}
+
+impl FromStr for Filter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Filter::default());
+        }
+
+        let names: Vec<_> = s
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+        if names.is_empty() {
+            anyhow::bail!("Expected span or event names to trace, or all of them, not {s:?}");
+        }
+        Ok(Filter { names })
+    }
+}
+
+struct Sink {
+    filter: Filter,
+    out: Box<dyn Write + Send>,
+}
+
+static SINK: OnceLock<Mutex<Sink>> = OnceLock::new();
+
+thread_local! {
+    static SPANS: RefCell<Vec<&'static str>> = const { RefCell::new(vec![]) };
+}
+
+/// Write the events `filter` keeps into `out`. Only the first call has any effect, and without
+/// the `trace` feature nothing is ever written.
+pub fn trace_to(filter: Filter, out: Box<dyn Write + Send>) {
+    let _ = SINK.set(Mutex::new(Sink { filter, out }));
+}
+
+/// Flush whatever has been traced so far.
+pub fn flush() {
+    if let Some(sink) = SINK.get() {
+        let _ = sink.lock().unwrap_or_else(|e| e.into_inner()).out.flush();
+    }
+}
+
+/// Inside a span until it's dropped.
+#[must_use = "the span ends as soon as it's dropped"]
+pub struct Span(());
+
+/// Start a span called `name`, which lasts until the returned guard is dropped.
+#[inline]
+pub fn span(name: &'static str) -> Span {
+    if ENABLED {
+        SPANS.with(|spans| spans.borrow_mut().push(name));
+    }
+    Span(())
+}
+
+impl Drop for Span {
+    #[inline]
+    fn drop(&mut self) {
+        if ENABLED {
+            SPANS.with(|spans| spans.borrow_mut().pop());
+        }
+    }
+}
+
+/// Whether an event called `event` would be written, so that its values needn't be worked out
+/// otherwise. Use [`trace!`](crate::trace!) rather than this.
+#[doc(hidden)]
+pub fn wanted(event: &str) -> bool {
+    let Some(sink) = SINK.get() else {
+        return false;
+    };
+    let filter = &sink.lock().unwrap_or_else(|e| e.into_inner()).filter;
+    SPANS.with(|spans| filter.matches(&spans.borrow(), event))
+}
+
+/// Write an event, see [`trace!`](crate::trace!).
+#[doc(hidden)]
+pub fn event(event: &str, fields: &[(&str, &dyn Display)]) {
+    let Some(sink) = SINK.get() else {
+        return;
+    };
+    let line = SPANS.with(|spans| format_event(&spans.borrow(), event, fields));
+    // a trace which can't be written is no reason to stop the day
+    let _ = sink
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .out
+        .write_all(line.as_bytes());
+}
+
+/// `spans/joined/up event key=value ...`, followed by any values which take more than a line.
+fn format_event(spans: &[&str], event: &str, fields: &[(&str, &dyn Display)]) -> String {
+    let mut line = spans.join("/");
+    if !line.is_empty() {
+        line.push(' ');
+    }
+    line.push_str(event);
+
+    let mut below = String::new();
+    for (key, value) in fields {
+        let value = value.to_string();
+        if value.contains('\n') {
+            writeln!(below, "  {key}:").unwrap();
+            for value_line in value.lines() {
+                match value_line {
+                    "" => below.push('\n'),
+                    _ => writeln!(below, "    {value_line}").unwrap(),
+                }
+            }
+        } else {
+            write!(line, " {key}={value}").unwrap();
+        }
+    }
+
+    line.push('\n');
+    line + &below
+}
+
+/// Report an event called `$event` from the current span, with some values:
+/// `trace!("round", round, changed = n)` gives `round round=3 changed=12`. The values are only
+/// worked out when the event is going to be written.
+#[macro_export]
+macro_rules! trace {
+    ($event:literal $(, $key:ident $(= $value:expr)?)* $(,)?) => {
+        if $crate::trace::ENABLED && $crate::trace::wanted($event) {
+            $crate::trace::event(
+                $event,
+                &[$((stringify!($key), &$crate::trace!(@value $key $($value)?) as &dyn ::std::fmt::Display)),*],
+            );
+        }
+    };
+    (@value $key:ident) => { $key };
+    (@value $key:ident $value:expr) => { $value };
+}
+
+/// Writes a value with [`fmt::Debug`], for values without a [`Display`] to trace.
+pub struct Debug<T>(pub T);
+
+impl<T: fmt::Debug> Display for Debug<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters() {
+        let all: Filter = "all".parse().unwrap();
+        assert!(all.matches(&[], "anything"));
+
+        let filter: Filter = "evolve, seen".parse().unwrap();
+        assert!(filter.matches(&["part1", "evolve"], "round"));
+        assert!(filter.matches(&["part2"], "seen"));
+        assert!(!filter.matches(&["part2"], "round"));
+
+        assert!(",".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn formats_events() {
+        assert_eq!(format_event(&[], "start", &[]), "start\n");
+        assert_eq!(
+            format_event(
+                &["part1", "evolve"],
+                "round",
+                &[("round", &3), ("cells", &Debug([1, 2]))]
+            ),
+            "part1/evolve round round=3 cells=[1, 2]\n"
+        );
+        assert_eq!(
+            format_event(&["part2"], "board", &[("seats", &"#.\n.L"), ("n", &1)]),
+            "part2 board n=1\n  seats:\n    #.\n    .L\n"
+        );
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn traces_wanted_events() {
+        use std::sync::Arc;
+
+        #[derive(Clone, Default)]
+        struct Shared(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for Shared {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let out = Shared::default();
+        trace_to("evolve".parse().unwrap(), Box::new(out.clone()));
+
+        let mut worked_out = 0;
+        {
+            let _span = span("evolve");
+            for round in 1..=2 {
+                trace!("round", round, changed = 5 * round);
+            }
+        }
+        trace!(
+            "round",
+            ignored = {
+                worked_out += 1;
+                worked_out
+            }
+        );
+
+        assert_eq!(worked_out, 0, "values of unwanted events aren't worked out");
+        assert_eq!(
+            String::from_utf8(out.0.lock().unwrap().clone()).unwrap(),
+            "evolve round round=1 changed=5\nevolve round round=2 changed=10\n"
+        );
+    }
+}